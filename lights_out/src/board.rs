@@ -0,0 +1,119 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use minesweeper::CoordinateU16;
+
+use crate::{
+    components::{board_marker::BoardMarker, hint_marker::HintMarker, light_marker::LightMarker},
+    helpers::board_layout,
+    lights::Lights,
+    options::LightsOutOptions,
+    resources::game::Game,
+    states::plugin_state::LightsOutState,
+    LightsOutPlugin, BACKGROUND_Z, FOREGROUND_Z, HINT_COLOR, UNLIT_COLOR,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > LightsOutPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn create_board(
+        mut commands: Commands,
+        windows: Query<&Window, With<PrimaryWindow>>,
+        options: Option<Res<LightsOutOptions>>,
+        old_board: Query<Entity, With<BoardMarker>>,
+        mut next_state: ResMut<NextState<LightsOutState>>,
+    ) {
+        let window = match windows.get_single() {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+
+        // Despawn old board if it exists
+        if let Ok(t) = old_board.get_single() {
+            commands.entity(t).despawn_recursive();
+        }
+
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+
+        let lights = Lights::random_solvable(
+            options.grid_size,
+            options.grid_size,
+            &mut rand::thread_rng(),
+        );
+        bevy::log::debug!("\n{}", lights);
+
+        let (tile_size, board_position) = board_layout(window, &options);
+        let mut game = Game::new(lights, tile_size);
+        game.origin = board_position.xy();
+
+        commands
+            .spawn((
+                Name::new("Board"),
+                SpatialBundle {
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                },
+                BoardMarker,
+            ))
+            .with_children(|parent| {
+                for y in 0..options.grid_size {
+                    for x in 0..options.grid_size {
+                        let coordinate = CoordinateU16 { x, y, z: 0 };
+                        parent
+                            .spawn((
+                                SpriteBundle {
+                                    sprite: Sprite {
+                                        color: UNLIT_COLOR,
+                                        custom_size: Some(Vec2::splat(
+                                            tile_size - options.tile_padding,
+                                        )),
+                                        ..Default::default()
+                                    },
+                                    transform: Transform::from_translation(
+                                        game.translation(coordinate, BACKGROUND_Z),
+                                    ),
+                                    ..Default::default()
+                                },
+                                Name::new(format!("Light: {}", coordinate)),
+                                coordinate,
+                                LightMarker,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    SpriteBundle {
+                                        sprite: Sprite {
+                                            color: HINT_COLOR,
+                                            custom_size: Some(Vec2::splat(tile_size * 0.25)),
+                                            ..Default::default()
+                                        },
+                                        transform: Transform::from_xyz(0., 0., FOREGROUND_Z),
+                                        visibility: Visibility::Hidden,
+                                        ..Default::default()
+                                    },
+                                    HintMarker,
+                                ));
+                            });
+                    }
+                }
+            });
+
+        commands.insert_resource(game);
+        next_state.set(LightsOutState::Running);
+    }
+
+    pub fn reset_event_handler(
+        mut state: ResMut<NextState<LightsOutState>>,
+        mut events: EventReader<TyResetEvent>,
+    ) {
+        if events.read().count() == 0 {
+            return;
+        }
+        state.set(LightsOutState::NewGame);
+    }
+}
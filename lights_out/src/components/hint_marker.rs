@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Marks the dot on a light that is shown when the light is the hint
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct HintMarker;
@@ -0,0 +1,4 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct LightMarker;
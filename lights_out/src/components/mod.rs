@@ -0,0 +1,3 @@
+pub mod board_marker;
+pub mod hint_marker;
+pub mod light_marker;
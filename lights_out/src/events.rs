@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+use minesweeper::CoordinateU16;
+
+/// Everything the player can do. Sent by the input system and applied to the [crate::resources::game::Game]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub(crate) enum LightsOutActionEvent {
+    /// Toggle the light and its neighbours
+    Press(CoordinateU16),
+    /// Show a light that is part of the shortest solution
+    Hint,
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::options::{LightsOutOptions, TileSize};
+
+/// Computes the tile size and the position of the bottom left corner of the board
+pub(crate) fn board_layout(window: &Window, options: &LightsOutOptions) -> (f32, Vec3) {
+    let tile_size = match options.tile_size {
+        TileSize::Fixed(v) => v,
+        TileSize::Adaptive { min, max } => (window.width() / options.grid_size as f32)
+            .min(window.height() / options.grid_size as f32)
+            .clamp(min, max),
+    };
+    let board_size = options.grid_size as f32 * tile_size;
+    (
+        tile_size,
+        Vec3::new(-(board_size / 2.), -(board_size / 2.), 0.),
+    )
+}
@@ -0,0 +1,157 @@
+use minesweeper::{topology::Topology, CoordinateU16, TileMap};
+use rand::{Rng, RngCore};
+
+use crate::solver;
+
+/// The lights of the grid. Pressing a light toggles it and its orthogonal neighbours, the goal is to turn all of them off
+#[derive(Debug, Clone)]
+pub struct Lights {
+    lit: Vec<bool>,
+    /// Only used for its neighbourhood, the lights themselves are stored in `lit`
+    tile_map: TileMap,
+}
+
+impl Lights {
+    pub fn off(width: u16, height: u16) -> Self {
+        Self {
+            lit: vec![false; width as usize * height as usize],
+            tile_map: TileMap::empty(width, height, Topology::Square),
+        }
+    }
+
+    /// Lights up random lights until the pattern is solvable. Only a part of all patterns can be solved on some grid sizes
+    pub fn random_solvable(width: u16, height: u16, rng: &mut dyn RngCore) -> Self {
+        let mut lights = Self::off(width, height);
+        loop {
+            lights.lit.iter_mut().for_each(|light| *light = rng.gen());
+            if !lights.is_solved() && lights.solve().is_some() {
+                return lights;
+            }
+        }
+    }
+
+    pub fn is_lit(&self, coordinate: CoordinateU16) -> bool {
+        self.lit[self.index(coordinate)]
+    }
+
+    /// Toggles the light and its orthogonal neighbours
+    pub fn press(&mut self, coordinate: CoordinateU16) {
+        let toggled: Vec<usize> = self
+            .pressed_by(coordinate)
+            .map(|coordinate| self.index(coordinate))
+            .collect();
+        toggled
+            .into_iter()
+            .for_each(|index| self.lit[index] ^= true);
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.lit.iter().all(|light| !light)
+    }
+
+    /// Finds the shortest list of presses that turns every light off. The order of the presses doesn't matter.
+    /// Returns `None` if the lights can't be turned off
+    pub fn solve(&self) -> Option<Vec<CoordinateU16>> {
+        // Column j of the matrix holds the lights toggled by pressing light j
+        let mut matrix = vec![vec![false; self.lit.len()]; self.lit.len()];
+        let lights = (0..self.lit.len()).map(|index| self.coordinate(index));
+        for (index, light) in lights.enumerate() {
+            for toggled in self.pressed_by(light) {
+                matrix[self.index(toggled)][index] = true;
+            }
+        }
+
+        let presses = solver::solve(&matrix, &self.lit)?;
+        Some(
+            presses
+                .into_iter()
+                .enumerate()
+                .filter(|(_, pressed)| *pressed)
+                .map(|(index, _)| self.coordinate(index))
+                .collect(),
+        )
+    }
+
+    pub fn width(&self) -> &u16 {
+        self.tile_map.width()
+    }
+
+    pub fn height(&self) -> &u16 {
+        self.tile_map.height()
+    }
+
+    fn pressed_by(&self, coordinate: CoordinateU16) -> impl Iterator<Item = CoordinateU16> + '_ {
+        std::iter::once(coordinate).chain(self.tile_map.true_neighbour_coordinates(coordinate))
+    }
+
+    fn index(&self, coordinate: CoordinateU16) -> usize {
+        coordinate.x as usize + coordinate.y as usize * *self.width() as usize
+    }
+
+    fn coordinate(&self, index: usize) -> CoordinateU16 {
+        CoordinateU16 {
+            x: (index % *self.width() as usize) as u16,
+            y: (index / *self.width() as usize) as u16,
+            z: 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Lights {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..*self.height() {
+            let row: String = (0..*self.width())
+                .map(|x| match self.is_lit(CoordinateU16 { x, y, z: 0 }) {
+                    true => '#',
+                    false => '.',
+                })
+                .collect();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn press_toggles_orthogonal_neighbours() {
+        let mut lights = Lights::off(3, 3);
+        lights.press(CoordinateU16 { x: 0, y: 0, z: 0 });
+
+        assert_eq!(lights.to_string(), "##.\n#..\n...\n");
+        lights.press(CoordinateU16 { x: 0, y: 0, z: 0 });
+        assert!(lights.is_solved());
+    }
+
+    #[test]
+    fn solution_turns_lights_off() {
+        let mut lights = Lights::random_solvable(5, 5, &mut rand::thread_rng());
+        for coordinate in lights.solve().unwrap() {
+            lights.press(coordinate);
+        }
+
+        assert!(lights.is_solved());
+    }
+
+    #[test]
+    fn single_corner_light_on_five_by_five_is_unsolvable() {
+        let mut lights = Lights::off(5, 5);
+        lights.lit[0] = true;
+
+        assert_eq!(lights.solve(), None);
+    }
+
+    #[test]
+    fn shortest_solution_is_found() {
+        let mut lights = Lights::off(5, 5);
+        lights.press(CoordinateU16 { x: 2, y: 2, z: 0 });
+
+        assert_eq!(
+            lights.solve(),
+            Some(vec![CoordinateU16 { x: 2, y: 2, z: 0 }])
+        );
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+/// Determines wether the tiles should be fixed (don't change with window scaling) or adaptive (change with window resizing but only above a minimum and below a maximum)
+#[derive(Debug, Clone, Resource)]
+pub enum TileSize {
+    Fixed(f32),
+    Adaptive { min: f32, max: f32 },
+}
+
+/// Vairous options relating to the lights
+#[derive(Debug, Clone, Resource)]
+pub struct LightsOutOptions {
+    /// Number of lights in each row and column
+    pub grid_size: u16,
+    /// Tile world size
+    pub tile_size: TileSize,
+    /// Padding between tiles
+    pub tile_padding: f32,
+}
+
+impl Default for TileSize {
+    fn default() -> Self {
+        Self::Adaptive {
+            min: 20.0,
+            max: 120.0,
+        }
+    }
+}
+
+impl Default for LightsOutOptions {
+    fn default() -> Self {
+        Self {
+            grid_size: 5,
+            tile_size: Default::default(),
+            tile_padding: 6.,
+        }
+    }
+}
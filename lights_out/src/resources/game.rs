@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use minesweeper::CoordinateU16;
+
+use crate::lights::Lights;
+
+#[derive(Debug, Clone, Resource)]
+pub struct Game {
+    pub lights: Lights,
+    /// The light the player should press next, shown after asking for a hint
+    pub hint: Option<CoordinateU16>,
+    pub moves: u32,
+    /// World position of the bottom left corner of the board
+    pub origin: Vec2,
+    pub tile_size: f32,
+    /// The won event is only sent once per game
+    pub won: bool,
+}
+
+impl Game {
+    pub fn new(lights: Lights, tile_size: f32) -> Self {
+        Self {
+            lights,
+            hint: None,
+            moves: 0,
+            origin: Vec2::ZERO,
+            tile_size,
+            won: false,
+        }
+    }
+
+    /// Local translation of the center of a light on the board. Row 0 is the top row
+    pub fn translation(&self, coordinate: CoordinateU16, z: f32) -> Vec3 {
+        Vec3::new(
+            coordinate.x as f32 * self.tile_size + self.tile_size * 0.5,
+            (self.lights.height() - coordinate.y - 1) as f32 * self.tile_size
+                + self.tile_size * 0.5,
+            z,
+        )
+    }
+
+    /// Converts a world position to the coordinates of the light below it
+    pub fn cursor_position(&self, position: Vec2) -> Option<CoordinateU16> {
+        let local = (position - self.origin) / self.tile_size;
+        if local.x < 0. || local.y < 0. {
+            return None;
+        }
+        let (x, y_from_bottom) = (local.x as u16, local.y as u16);
+        if x >= *self.lights.width() || y_from_bottom >= *self.lights.height() {
+            return None;
+        }
+        // The position 0:0 is top left but the world origin is bottom left
+        Some(CoordinateU16 {
+            x,
+            y: self.lights.height() - y_from_bottom - 1,
+            z: 0,
+        })
+    }
+}
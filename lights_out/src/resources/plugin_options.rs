@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// Can't pass the plugin as &self so I need to make a resource which holds the data the systems need
+#[derive(Debug, Clone, Resource)]
+pub struct PluginOptions<TyWonEvent>
+where
+    TyWonEvent: Event + Clone,
+{
+    pub won_event: TyWonEvent,
+}
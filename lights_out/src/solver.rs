@@ -0,0 +1,96 @@
+/// Solves the linear system `matrix * x = target` over GF(2), where addition is xor and multiplication is and.
+/// `matrix` is given row by row and has to be square. Returns the solution with the fewest set entries,
+/// or `None` if there is no solution
+pub fn solve(matrix: &[Vec<bool>], target: &[bool]) -> Option<Vec<bool>> {
+    let size = target.len();
+    // Augmented matrix, the last column is the target
+    let mut rows: Vec<Vec<bool>> = matrix
+        .iter()
+        .zip(target)
+        .map(|(row, t)| row.iter().copied().chain([*t]).collect())
+        .collect();
+
+    // Gauss-Jordan elimination. pivots[i] is the column of the pivot in row i
+    let mut pivots = Vec::new();
+    for column in 0..size {
+        let row = pivots.len();
+        let pivot = match (row..size).find(|r| rows[*r][column]) {
+            Some(t) => t,
+            None => continue,
+        };
+        rows.swap(row, pivot);
+        let pivot_row = rows[row].clone();
+        for (other, cells) in rows.iter_mut().enumerate() {
+            if other != row && cells[column] {
+                cells
+                    .iter_mut()
+                    .zip(&pivot_row)
+                    .for_each(|(cell, pivot)| *cell ^= pivot);
+            }
+        }
+        pivots.push(column);
+    }
+
+    // A row without pivot that still has to produce a one can't be satisfied
+    if rows[pivots.len()..].iter().any(|row| row[size]) {
+        return None;
+    }
+
+    let free: Vec<usize> = (0..size)
+        .filter(|column| !pivots.contains(column))
+        .collect();
+    let particular = |free_values: &[bool]| {
+        let mut solution = vec![false; size];
+        for (column, value) in free.iter().zip(free_values) {
+            solution[*column] = *value;
+        }
+        for (row, column) in pivots.iter().enumerate() {
+            solution[*column] = free
+                .iter()
+                .zip(free_values)
+                .fold(rows[row][size], |acc, (f, value)| {
+                    acc ^ (rows[row][*f] & value)
+                });
+        }
+        solution
+    };
+
+    // Every choice of the free variables is a solution. Try all of them if there aren't too many
+    let count = |solution: &Vec<bool>| solution.iter().filter(|x| **x).count();
+    match free.len() {
+        0..=MAX_FREE_VARIABLES => (0..1u32 << free.len())
+            .map(|bits| {
+                particular(
+                    &(0..free.len())
+                        .map(|i| bits & (1 << i) != 0)
+                        .collect::<Vec<bool>>(),
+                )
+            })
+            .min_by_key(count),
+        _ => Some(particular(&vec![false; free.len()])),
+    }
+}
+
+/// Above this many free variables only one solution is computed instead of the shortest
+const MAX_FREE_VARIABLES: usize = 12;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_invertible_system() {
+        let matrix = vec![vec![true, true], vec![false, true]];
+
+        assert_eq!(solve(&matrix, &[true, true]), Some(vec![false, true]));
+        assert_eq!(solve(&matrix, &[false, true]), Some(vec![true, true]));
+    }
+
+    #[test]
+    fn detects_contradiction() {
+        let matrix = vec![vec![true, true], vec![true, true]];
+
+        assert_eq!(solve(&matrix, &[true, false]), None);
+        assert_eq!(solve(&matrix, &[true, true]).map(|x| x.len()), Some(2));
+    }
+}
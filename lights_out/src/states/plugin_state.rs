@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, States)]
+pub enum LightsOutState {
+    #[default]
+    NewGame,
+    Running,
+}
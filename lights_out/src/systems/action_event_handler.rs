@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::LightsOutActionEvent,
+    resources::{game::Game, plugin_options::PluginOptions},
+    LightsOutPlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > LightsOutPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Applies the player actions to the game and sends the won event once every light is off
+    pub(crate) fn action_event_handler(
+        plugin_options: Res<PluginOptions<TyWonEvent>>,
+        mut game: ResMut<Game>,
+        mut action_evr: EventReader<LightsOutActionEvent>,
+        mut won_event_writer: EventWriter<TyWonEvent>,
+    ) {
+        for event in action_evr.read() {
+            match *event {
+                LightsOutActionEvent::Press(coordinate) => {
+                    game.lights.press(coordinate);
+                    game.moves += 1;
+                    game.hint = None;
+                }
+                LightsOutActionEvent::Hint => {
+                    game.hint = game
+                        .lights
+                        .solve()
+                        .and_then(|presses| presses.first().copied());
+                }
+            }
+        }
+
+        if !game.won && game.lights.is_solved() {
+            game.won = true;
+            bevy::log::info!("Solved in {} moves", game.moves);
+            won_event_writer.send(plugin_options.won_event.clone());
+        }
+    }
+}
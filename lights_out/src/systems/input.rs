@@ -0,0 +1,43 @@
+use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{events::LightsOutActionEvent, resources::game::Game};
+
+/// Clicking a light presses it, H shows a hint
+pub fn input(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    game: Res<Game>,
+    mut keyboard_event_read: EventReader<KeyboardInput>,
+    mut button_event_read: EventReader<MouseButtonInput>,
+    mut action_ewr: EventWriter<LightsOutActionEvent>,
+) {
+    if let Ok(window) = windows.get_single() {
+        for event in button_event_read.read() {
+            if event.state != ButtonState::Pressed || event.button != MouseButton::Left {
+                continue;
+            }
+            let cursor = match window.cursor_position() {
+                Some(t) => t,
+                None => continue,
+            };
+            // Window to world space. Window y grows downwards
+            let position = Vec2::new(
+                cursor.x - window.width() / 2.,
+                window.height() / 2. - cursor.y,
+            );
+            if let Some(coordinate) = game.cursor_position(position) {
+                bevy::log::debug!("Pressing {}", coordinate);
+                action_ewr.send(LightsOutActionEvent::Press(coordinate));
+            }
+        }
+    }
+
+    for event in keyboard_event_read.read() {
+        if event.state == ButtonState::Pressed && event.key_code == KeyCode::KeyH {
+            action_ewr.send(LightsOutActionEvent::Hint);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod action_event_handler;
+pub mod input;
+pub mod refresh;
+pub mod rescale;
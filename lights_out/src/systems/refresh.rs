@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+use minesweeper::CoordinateU16;
+
+use crate::{
+    components::{hint_marker::HintMarker, light_marker::LightMarker},
+    resources::game::Game,
+    LIT_COLOR, UNLIT_COLOR,
+};
+
+/// Updates light colours and the hint whenever the game changed
+pub fn refresh(
+    game: Res<Game>,
+    mut lights: Query<(&CoordinateU16, &mut Sprite, &Children), With<LightMarker>>,
+    mut hints: Query<&mut Visibility, With<HintMarker>>,
+) {
+    if !game.is_changed() {
+        return;
+    }
+
+    for (coordinate, mut sprite, children) in lights.iter_mut() {
+        sprite.color = match game.lights.is_lit(*coordinate) {
+            true => LIT_COLOR,
+            false => UNLIT_COLOR,
+        };
+        for child in children.iter() {
+            if let Ok(mut visibility) = hints.get_mut(*child) {
+                *visibility = match game.hint == Some(*coordinate) {
+                    true => Visibility::Inherited,
+                    false => Visibility::Hidden,
+                };
+            }
+        }
+    }
+}
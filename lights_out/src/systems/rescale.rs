@@ -0,0 +1,58 @@
+use bevy::{prelude::*, window::PrimaryWindow, window::WindowResized};
+use minesweeper::CoordinateU16;
+
+use crate::{
+    components::{board_marker::BoardMarker, hint_marker::HintMarker},
+    helpers::board_layout,
+    options::LightsOutOptions,
+    resources::game::Game,
+    BACKGROUND_Z,
+};
+
+/// Changes tile size and position if window size changed
+#[allow(clippy::type_complexity)]
+pub fn rescale(
+    mut board_component: Query<&mut Transform, With<BoardMarker>>,
+    mut lights: Query<(&mut Transform, &mut Sprite, &CoordinateU16), Without<BoardMarker>>,
+    mut hints: Query<&mut Sprite, (With<HintMarker>, Without<CoordinateU16>)>,
+    mut game: Option<ResMut<Game>>,
+    mut resize_event: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    options: Option<Res<LightsOutOptions>>,
+) {
+    if resize_event.read().count() == 0 {
+        return;
+    }
+
+    let game = match &mut game {
+        Some(t) => t,
+        None => return,
+    };
+
+    let window = match windows.get_single() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    let (tile_size, board_position) = board_layout(window, &options);
+    game.tile_size = tile_size;
+    game.origin = board_position.xy();
+
+    for mut transform in board_component.iter_mut() {
+        *transform = Transform::from_translation(board_position);
+    }
+
+    for (mut transform, mut sprite, coordinate) in lights.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
+        transform.translation = game.translation(*coordinate, BACKGROUND_Z);
+    }
+
+    for mut sprite in hints.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(tile_size * 0.25));
+    }
+}
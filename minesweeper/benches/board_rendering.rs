@@ -0,0 +1,196 @@
+//! Compares drawing every tile as sprites, as shapes and drawing chunks of tiles as one mesh.
+//! There is no renderer, so only the time the game spends in its own systems is measured.
+//! Run with `cargo bench -p minesweeper`
+
+use std::time::{Duration, Instant};
+
+use bevy::{
+    app::AppExit,
+    input::{mouse::MouseButtonInput, ButtonState, InputPlugin},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+    window::WindowResized,
+};
+use minesweeper::{
+    options::{BoardOptions, BoardRendering, TileSize},
+    texture_handles::TextureHandles,
+    MinesweeperPlugin,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SIZES: [u16; 3] = [100, 250, 500];
+const CHUNK_SIZE: u16 = 32;
+const FRAMES: u32 = 50;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
+enum BenchState {
+    #[default]
+    Playing,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
+enum PauseState {
+    #[default]
+    Running,
+    Paused,
+}
+
+#[derive(Debug, Clone, Event)]
+struct ResetEvent;
+
+#[derive(Debug, Clone, Event)]
+struct WonEvent;
+
+#[derive(Debug, Clone, Event)]
+struct LostEvent;
+
+struct Measurement {
+    entities: u32,
+    spawn: Duration,
+    idle: Duration,
+    click: Duration,
+    resize: Duration,
+}
+
+fn main() {
+    println!(
+        "{:<10} {:>9} {:>9} {:>11} {:>11} {:>11} {:>11}",
+        "rendering", "size", "entities", "spawn", "idle", "click", "resize"
+    );
+    for size in SIZES {
+        for rendering in [
+            BoardRendering::Sprites,
+            BoardRendering::Chunks { size: CHUNK_SIZE },
+            BoardRendering::Shapes,
+        ] {
+            let result = measure(size, rendering);
+            println!(
+                "{:<10} {:>9} {:>9} {:>11.2?} {:>11.2?} {:>11.2?} {:>11.2?}",
+                match rendering {
+                    BoardRendering::Sprites => "sprites",
+                    BoardRendering::Chunks { .. } => "chunks",
+                    BoardRendering::Shapes => "shapes",
+                },
+                format!("{}x{}", size, size),
+                result.entities,
+                result.spawn,
+                result.idle,
+                result.click,
+                result.resize,
+            );
+        }
+    }
+}
+
+/// Time of the frame that spawns the board and the average time of frames afterwards
+fn measure(size: u16, rendering: BoardRendering) -> Measurement {
+    let mut app = board_app(size, rendering);
+    let window = app.world.spawn(Window::default()).id();
+
+    let start = Instant::now();
+    app.update();
+    let spawn = start.elapsed();
+    // Lets the board go into its running state and the systems do their first run
+    for _ in 0..3 {
+        app.update();
+    }
+    let entities = app.world.entities().len();
+
+    let idle = average(|| app.update());
+
+    let mut rng = StdRng::seed_from_u64(size as u64);
+    let click = average(|| {
+        // Tiles are one pixel big and the board is in the middle of the window
+        let tile = Vec2::new(rng.gen_range(0..size) as f32, rng.gen_range(0..size) as f32);
+        let mut window_component = app.world.get_mut::<Window>(window).unwrap();
+        let corner = Vec2::new(window_component.width(), window_component.height()) / 2.
+            - Vec2::splat(size as f32 / 2.);
+        window_component.set_cursor_position(Some(corner + tile + 0.5));
+        // Tiles are uncovered when the button is released
+        for state in [ButtonState::Pressed, ButtonState::Released] {
+            app.world.send_event(MouseButtonInput {
+                button: MouseButton::Left,
+                state,
+                window,
+            });
+        }
+        app.update();
+    });
+
+    let resize = average(|| {
+        app.world.send_event(WindowResized {
+            window,
+            width: 1280.,
+            height: 720.,
+        });
+        app.update();
+    });
+
+    Measurement {
+        entities,
+        spawn,
+        idle,
+        click,
+        resize,
+    }
+}
+
+fn average(mut frame: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        frame();
+    }
+    start.elapsed() / FRAMES
+}
+
+/// An app with the plugin and nothing that needs a window or a GPU
+fn board_app(size: u16, rendering: BoardRendering) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AssetPlugin::default(), InputPlugin))
+        .init_asset::<Image>()
+        .init_asset::<TextureAtlasLayout>()
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_state::<BenchState>()
+        .init_state::<PauseState>()
+        .add_event::<WindowResized>()
+        .add_event::<AppExit>()
+        .add_event::<ResetEvent>()
+        .add_event::<WonEvent>()
+        .add_event::<LostEvent>()
+        .add_plugins(MinesweeperPlugin::new(
+            BenchState::Playing,
+            PauseState::Paused,
+            ResetEvent,
+            WonEvent,
+            LostEvent,
+        ));
+
+    let mut images = app.world.resource_mut::<Assets<Image>>();
+    let textures = TextureHandles::load_with(|_| images.add(tile_image()));
+    app.insert_resource(textures).insert_resource(BoardOptions {
+        map_size: (size, size),
+        bomb_count: (size as u32 * size as u32 / 8) as u16,
+        tile_size: TileSize::Fixed(1.),
+        rendering,
+        ..Default::default()
+    });
+    app
+}
+
+fn tile_image() -> Image {
+    Image::new_fill(
+        Extent3d {
+            width: 32,
+            height: 32,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[255; 4],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
@@ -0,0 +1,296 @@
+use std::io::Read;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+const FILE_MAGIC: u16 = 0xA5E0;
+const FRAME_MAGIC: u16 = 0xF1FA;
+const HEADER_SIZE: usize = 128;
+const LAYER_CHUNK: u16 = 0x2004;
+const CEL_CHUNK: u16 = 0x2005;
+const PALETTE_CHUNK: u16 = 0x2019;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AsepriteError {
+    #[error("The file ends too early")]
+    Truncated,
+    #[error("The file isn't an Aseprite file")]
+    Magic,
+    #[error("Colour depth {0} isn't supported")]
+    ColorDepth(u16),
+    #[error("Cel type {0} isn't supported")]
+    CelType(u16),
+    #[error("A layer name isn't valid UTF-8")]
+    Name,
+    #[error("Couldn't decompress a cel: {0}")]
+    Zlib(#[from] std::io::Error),
+}
+
+/// Layers that aren't named like the texture they replace, with the name of their texture
+const LAYER_TEXTURES: [(&str, &str); 1] = [("Base Tile", "Base")];
+
+/// A layer of the first frame of an Aseprite file, drawn on its own
+#[derive(Debug, Clone)]
+pub struct AsepriteLayer {
+    pub name: String,
+    pub image: Image,
+}
+
+impl AsepriteLayer {
+    /// The texture file this layer is used as
+    pub fn texture_file(&self) -> String {
+        let name = LAYER_TEXTURES
+            .iter()
+            .find(|(layer, _)| *layer == self.name)
+            .map_or(self.name.as_str(), |(_, texture)| texture);
+        format!("{}.png", name)
+    }
+}
+
+struct Layer {
+    name: String,
+    /// Group and tilemap layers have no pixels of their own
+    is_image: bool,
+    opacity: u8,
+}
+
+struct Cel {
+    layer: usize,
+    position: IVec2,
+    size: UVec2,
+    opacity: u8,
+    pixels: Vec<u8>,
+}
+
+/// Reads the layers of the first frame of an Aseprite file. Every layer is one tile texture, see
+/// [AsepriteLayer::texture_file]. Layers are read even if they are hidden
+pub fn parse(bytes: &[u8]) -> Result<Vec<AsepriteLayer>, AsepriteError> {
+    let mut header = Reader(bytes);
+    header.skip(4)?;
+    if header.u16()? != FILE_MAGIC {
+        return Err(AsepriteError::Magic);
+    }
+    let frames = header.u16()?;
+    let size = UVec2::new(header.u16()? as u32, header.u16()? as u32);
+    let depth = header.u16()?;
+    if !matches!(depth, 8 | 16 | 32) {
+        return Err(AsepriteError::ColorDepth(depth));
+    }
+    // Layer opacities are only valid if the first flag is set
+    let has_layer_opacity = header.u32()? & 1 == 1;
+    header.skip(10)?;
+    let transparent_index = header.u8()?;
+    if frames == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut frame = Reader(bytes.get(HEADER_SIZE..).ok_or(AsepriteError::Truncated)?);
+    let frame_size = frame.u32()? as usize;
+    if frame.u16()? != FRAME_MAGIC {
+        return Err(AsepriteError::Magic);
+    }
+    let old_chunk_count = frame.u16()?;
+    frame.skip(4)?;
+    let chunk_count = match frame.u32()? {
+        0 => old_chunk_count as u32,
+        count => count,
+    };
+    let mut frame = Reader(frame.bytes(frame_size.saturating_sub(16))?);
+
+    let mut layers = Vec::new();
+    let mut cels = Vec::new();
+    let mut palette = Vec::new();
+    for _ in 0..chunk_count {
+        let chunk_size = frame.u32()? as usize;
+        let chunk_type = frame.u16()?;
+        let mut chunk = Reader(frame.bytes(chunk_size.saturating_sub(6))?);
+        match chunk_type {
+            LAYER_CHUNK => {
+                chunk.skip(2)?;
+                let layer_type = chunk.u16()?;
+                chunk.skip(8)?;
+                let opacity = chunk.u8()?;
+                chunk.skip(3)?;
+                layers.push(Layer {
+                    name: chunk.string()?,
+                    is_image: layer_type == 0,
+                    opacity: match has_layer_opacity {
+                        true => opacity,
+                        false => 255,
+                    },
+                });
+            }
+            CEL_CHUNK => {
+                let layer = chunk.u16()? as usize;
+                let position = IVec2::new(chunk.i16()? as i32, chunk.i16()? as i32);
+                let opacity = chunk.u8()?;
+                let cel_type = chunk.u16()?;
+                chunk.skip(7)?;
+                if !matches!(cel_type, 0 | 2) {
+                    return Err(AsepriteError::CelType(cel_type));
+                }
+                let size = UVec2::new(chunk.u16()? as u32, chunk.u16()? as u32);
+                let pixels = match cel_type {
+                    0 => chunk.0.to_vec(),
+                    _ => {
+                        let mut pixels = Vec::new();
+                        flate2::read::ZlibDecoder::new(chunk.0).read_to_end(&mut pixels)?;
+                        pixels
+                    }
+                };
+                cels.push(Cel {
+                    layer,
+                    position,
+                    size,
+                    opacity,
+                    pixels,
+                });
+            }
+            PALETTE_CHUNK => {
+                palette.resize(chunk.u32()? as usize, [0; 4]);
+                let (first, last) = (chunk.u32()? as usize, chunk.u32()? as usize);
+                chunk.skip(8)?;
+                for index in first..=last {
+                    let has_name = chunk.u16()? & 1 == 1;
+                    let color = [chunk.u8()?, chunk.u8()?, chunk.u8()?, chunk.u8()?];
+                    if has_name {
+                        chunk.string()?;
+                    }
+                    if let Some(entry) = palette.get_mut(index) {
+                        *entry = color;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let to_rgba = |pixel: &[u8]| -> [u8; 4] {
+        match depth {
+            32 => [pixel[0], pixel[1], pixel[2], pixel[3]],
+            16 => [pixel[0], pixel[0], pixel[0], pixel[1]],
+            _ if pixel[0] == transparent_index => [0; 4],
+            _ => palette.get(pixel[0] as usize).copied().unwrap_or_default(),
+        }
+    };
+    let bytes_per_pixel = depth as usize / 8;
+
+    Ok(layers
+        .iter()
+        .enumerate()
+        .filter(|(_, layer)| layer.is_image)
+        .map(|(index, layer)| {
+            let mut data = vec![0; (size.x * size.y * 4) as usize];
+            for cel in cels.iter().filter(|cel| cel.layer == index) {
+                let pixels = cel.pixels.chunks_exact(bytes_per_pixel);
+                for (pixel_index, pixel) in pixels.enumerate() {
+                    let local = UVec2::new(
+                        pixel_index as u32 % cel.size.x.max(1),
+                        pixel_index as u32 / cel.size.x.max(1),
+                    );
+                    let target = cel.position + local.as_ivec2();
+                    if target.cmplt(IVec2::ZERO).any() || target.as_uvec2().cmpge(size).any() {
+                        continue;
+                    }
+                    let mut color = to_rgba(pixel);
+                    color[3] = (color[3] as u32 * cel.opacity as u32 * layer.opacity as u32
+                        / (255 * 255)) as u8;
+                    let start = ((target.y as u32 * size.x + target.x as u32) * 4) as usize;
+                    data[start..start + 4].copy_from_slice(&color);
+                }
+            }
+            AsepriteLayer {
+                name: layer.name.clone(),
+                image: Image::new(
+                    Extent3d {
+                        width: size.x,
+                        height: size.y,
+                        depth_or_array_layers: 1,
+                    },
+                    TextureDimension::D2,
+                    data,
+                    TextureFormat::Rgba8UnormSrgb,
+                    RenderAssetUsages::default(),
+                ),
+            }
+        })
+        .collect())
+}
+
+/// Little endian values from the front of a byte slice
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, count: usize) -> Result<&'a [u8], AsepriteError> {
+        if self.0.len() < count {
+            return Err(AsepriteError::Truncated);
+        }
+        let (bytes, rest) = self.0.split_at(count);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, count: usize) -> Result<(), AsepriteError> {
+        self.bytes(count).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Result<u8, AsepriteError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, AsepriteError> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn i16(&mut self) -> Result<i16, AsepriteError> {
+        Ok(self.u16()? as i16)
+    }
+
+    fn u32(&mut self) -> Result<u32, AsepriteError> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn string(&mut self) -> Result<String, AsepriteError> {
+        let length = self.u16()? as usize;
+        String::from_utf8(self.bytes(length)?.to_vec()).map_err(|_| AsepriteError::Name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::texture_handles::TextureHandles;
+
+    const TILES: &[u8] = include_bytes!("../../assets/minesweeper/minesweeper.aseprite");
+
+    #[test]
+    fn bundled_layers_are_tile_textures() {
+        let layers = parse(TILES).unwrap();
+        let files: Vec<String> = TextureHandles::files().collect();
+
+        assert_eq!(layers.len(), 12);
+        for layer in layers {
+            assert!(files.contains(&layer.texture_file()), "{}", layer.name);
+            assert_eq!(layer.image.size(), UVec2::splat(32));
+            // Tiles cover their whole square
+            assert!(layer.image.data.chunks(4).all(|pixel| pixel[3] == 255));
+        }
+    }
+
+    #[test]
+    fn broken_files_are_rejected() {
+        assert!(matches!(parse(&TILES[..20]), Err(AsepriteError::Truncated)));
+        assert!(matches!(parse(&[0; 128]), Err(AsepriteError::Magic)));
+        assert!(matches!(
+            parse(&TILES[..TILES.len() - 10]),
+            Err(AsepriteError::Truncated | AsepriteError::Zlib(_))
+        ));
+    }
+}
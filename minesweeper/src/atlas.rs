@@ -0,0 +1,201 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::texture_handles::TextureHandles;
+
+/// Space between the textures in the atlas, in pixels
+const PADDING: u32 = 2;
+
+#[derive(Debug, thiserror::Error)]
+pub enum AtlasError {
+    #[error("There are no textures to pack")]
+    Empty,
+    #[error("The texture {0} isn't loaded")]
+    Missing(String),
+    #[error("Texture {index} is {found} pixels big but the first texture is {expected}")]
+    Size {
+        index: usize,
+        expected: UVec2,
+        found: UVec2,
+    },
+    #[error("Texture {0} has an unsupported pixel format")]
+    Format(usize),
+}
+
+/// All tile textures of a theme packed into one image, so the tiles of a board are drawn in one batch.
+/// The textures are in the order of [TextureHandles::files], so the indices are the same for every theme
+#[derive(Debug, Clone, Resource)]
+pub struct TileAtlas {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+impl TileAtlas {
+    /// Index of the cover without flags
+    pub const BASE: usize = TextureHandles::MAX_NUMBER as usize + 1;
+    pub const BOMB: usize = Self::BASE + 1;
+    pub const FLAG: usize = Self::BASE + 2;
+
+    /// Packs the textures, which all have to be loaded and have the same size
+    pub fn build(
+        textures: &TextureHandles,
+        images: &mut Assets<Image>,
+        layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Result<Self, AtlasError> {
+        let tiles = textures
+            .handles()
+            .zip(TextureHandles::files())
+            .map(|(handle, file)| images.get(handle).ok_or(AtlasError::Missing(file)))
+            .collect::<Result<Vec<&Image>, AtlasError>>()?;
+        let (image, layout) = pack(&tiles)?;
+
+        Ok(Self {
+            image: images.add(image),
+            layout: layouts.add(layout),
+        })
+    }
+
+    /// Index of an uncovered tile with `count` bomb neighbours
+    pub fn number(count: u8) -> usize {
+        if count > TextureHandles::MAX_NUMBER {
+            panic!("Invalid number of bomb neighbours");
+        }
+        count as usize
+    }
+
+    /// Index of a cover with `count` flags on it
+    pub fn flags(count: u8) -> usize {
+        match count {
+            0 => Self::BASE,
+            _ if count <= TextureHandles::MAX_FLAGS => Self::FLAG + count as usize - 1,
+            _ => panic!("Invalid number of flags"),
+        }
+    }
+
+    /// Atlas component that shows the texture at `index`
+    pub fn sprite(&self, index: usize) -> TextureAtlas {
+        TextureAtlas {
+            layout: self.layout.clone(),
+            index,
+        }
+    }
+}
+
+/// Copies images of the same size into a grid, row by row
+pub(crate) fn pack(images: &[&Image]) -> Result<(Image, TextureAtlasLayout), AtlasError> {
+    let size = images.first().ok_or(AtlasError::Empty)?.size();
+    let columns = (images.len() as f32).sqrt().ceil() as u32;
+    let rows = (images.len() as u32).div_ceil(columns);
+    let cell = size + UVec2::splat(PADDING);
+    let atlas_size = UVec2::new(columns, rows) * cell - UVec2::splat(PADDING);
+
+    let mut data = vec![0; (atlas_size.x * atlas_size.y * 4) as usize];
+    for (index, image) in images.iter().enumerate() {
+        if image.size() != size {
+            return Err(AtlasError::Size {
+                index,
+                expected: size,
+                found: image.size(),
+            });
+        }
+        let image = image
+            .convert(TextureFormat::Rgba8UnormSrgb)
+            .ok_or(AtlasError::Format(index))?;
+
+        let origin = UVec2::new(index as u32 % columns, index as u32 / columns) * cell;
+        let row_length = (size.x * 4) as usize;
+        for y in 0..size.y {
+            let source = y as usize * row_length;
+            let target = (((origin.y + y) * atlas_size.x + origin.x) * 4) as usize;
+            data[target..target + row_length]
+                .copy_from_slice(&image.data[source..source + row_length]);
+        }
+    }
+
+    let image = Image::new(
+        Extent3d {
+            width: atlas_size.x,
+            height: atlas_size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let layout = TextureAtlasLayout::from_grid(
+        size.as_vec2(),
+        columns as usize,
+        rows as usize,
+        Some(Vec2::splat(PADDING as f32)),
+        None,
+    );
+    Ok((image, layout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(size: UVec2, value: u8) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[value; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn indices_match_the_texture_files() {
+        let files: Vec<String> = TextureHandles::files().collect();
+
+        assert_eq!(files[TileAtlas::number(7)], "7.png");
+        assert_eq!(files[TileAtlas::BASE], "Base.png");
+        assert_eq!(files[TileAtlas::BOMB], "Bomb.png");
+        assert_eq!(files[TileAtlas::flags(1)], "Flag.png");
+        assert_eq!(
+            files[TileAtlas::flags(TextureHandles::MAX_FLAGS)],
+            format!("Flag{}.png", TextureHandles::MAX_FLAGS)
+        );
+        assert_eq!(files.len(), TileAtlas::flags(TextureHandles::MAX_FLAGS) + 1);
+    }
+
+    #[test]
+    fn textures_are_packed_in_order() {
+        let images: Vec<Image> = (1..=3)
+            .map(|value| filled(UVec2::new(2, 2), value))
+            .collect();
+        let (atlas, layout) = pack(&images.iter().collect::<Vec<_>>()).unwrap();
+
+        // Two columns and two rows with padding between them
+        assert_eq!(atlas.size(), UVec2::new(6, 6));
+        assert_eq!(layout.len(), 4);
+        let pixel = |x: u32, y: u32| atlas.data[((y * 6 + x) * 4) as usize];
+        assert_eq!(pixel(0, 0), 1);
+        assert_eq!(pixel(4, 1), 2);
+        assert_eq!(pixel(1, 4), 3);
+        assert_eq!(pixel(2, 0), 0);
+        assert_eq!(layout.textures[1].min, Vec2::new(4., 0.));
+    }
+
+    #[test]
+    fn different_sizes_are_rejected() {
+        let images = [filled(UVec2::new(2, 2), 1), filled(UVec2::new(3, 2), 1)];
+
+        assert!(matches!(
+            pack(&images.iter().collect::<Vec<_>>()),
+            Err(AtlasError::Size { index: 1, .. })
+        ));
+    }
+}
@@ -1,26 +1,36 @@
 use std::collections::HashMap;
 
 use crate::{
+    atlas::TileAtlas,
     components::{
-        board_marker::BoardMarker, coordinates::CoordinateU16, tile_cover_marker::TileCoverMarker,
-        tile_marker::TileMarker,
+        board_marker::BoardMarker, coordinates::CoordinateU16, ghost_tile::GhostTile,
+        layer_label::LayerLabel, modifier_text::ModifierText, tile_chunk::TileChunk,
+        tile_cover_marker::TileCoverMarker, tile_marker::TileMarker, tile_shape::TileShape,
     },
-    helpers::adaptive_tile_size,
-    options::{self, BoardOptions, BoardPosition, TileSize},
+    fallback_theme::insert_fallback_theme,
+    helpers::{
+        adaptive_tile_size, layer_label_text, layer_label_translation, layer_visibility,
+        start_modifiers, unit_board_size,
+    },
+    neighbourhood::Neighbourhood,
+    options::{self, AccessibilityOptions, BoardOptions, BoardPosition, BoardRendering, TileSize},
     resources::{
         board::Board,
         bounds::Bounds2,
         plugin_options::{self, PluginOptions},
         tile::Tile,
         tile_map::TileMap,
+        tile_shapes::TileShapes,
     },
     states::plugin_state::MinesweeperState,
     texture_handles::TextureHandles,
-    BACKGROUND_Z, FOREGROUND_Z,
+    theme::{Theme, ThemeStyle},
+    tile_mesh::{chunk_mesh, chunk_tiles},
+    BACKGROUND_Z, FOREGROUND_Z, GHOST_COLOR,
 };
 
 use super::MinesweeperPlugin;
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
 
 impl<
         TyRunState: States + PartialEq,
@@ -30,12 +40,22 @@ impl<
         TyLostEvent: Event + Clone,
     > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_board(
         mut commands: Commands,
-        windows: Query<Option<&mut Window>>,
+        windows: Query<&Window>,
         options: Option<Res<BoardOptions>>,
+        accessibility: Res<AccessibilityOptions>,
         textures: Option<Res<TextureHandles>>,
+        atlas: Option<Res<TileAtlas>>,
+        layouts: Res<Assets<TextureAtlasLayout>>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
+        mut images: ResMut<Assets<Image>>,
+        mut themes: ResMut<Assets<Theme>>,
+        style: Option<Res<ThemeStyle>>,
         old_board: Query<Entity, With<BoardMarker>>,
+        old_modifier_texts: Query<Entity, With<ModifierText>>,
         mut app_exit_events: ResMut<Events<bevy::app::AppExit>>,
         mut next_state: ResMut<NextState<MinesweeperState>>,
     ) {
@@ -50,46 +70,109 @@ impl<
         }
 
         #[cfg(not(feature = "debug"))]
-        let window = windows.single().unwrap();
+        let window = windows.single();
 
         #[cfg(feature = "debug")]
-        let window = windows.iter().next().unwrap().unwrap();
+        let window = windows.iter().next().unwrap();
 
         // Despawn old board if it exists
         // Was previously in restart but caused flickering
         if let Ok(t) = old_board.get_single() {
             commands.entity(t).despawn_recursive();
         }
+        for entity in old_modifier_texts.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
 
+        // Apps that don't load a theme get the one drawn in code
         if textures.is_none() {
-            bevy::log::error!("No texture handles. Can't continue.");
-            app_exit_events.send(bevy::app::AppExit);
+            bevy::log::warn!("No theme was loaded, using the built-in one.");
+            let topology = options
+                .as_ref()
+                .map(|options| options.tile_topology())
+                .unwrap_or_default();
+            insert_fallback_theme(&mut commands, &mut images, &mut themes, topology);
             return;
         }
 
-        let textures = textures.unwrap();
+        // The atlas is packed from the textures before the next frame
+        let Some(atlas) = atlas else {
+            return;
+        };
+        let Some(layout) = layouts.get(&atlas.layout) else {
+            return;
+        };
+        let style = match style {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
 
-        let options = match options {
+        let mut options = match options {
             Some(t) => t.clone(),
             None => Default::default(),
         };
+        if let Some(mask) = &options.mask {
+            options.map_size = mask.size();
+        }
+        options.tile_size = accessibility.tile_size(&options.tile_size);
+        options.palette = accessibility.palette(&options.palette);
+        options.rendering = accessibility.rendering(options.rendering);
+
+        if options.wrap && !options.topology.can_wrap(options.map_size) {
+            bevy::log::warn!(
+                "{:?} boards of size {:?} don't line up when wrapping, turning wrapping off.",
+                options.topology,
+                options.map_size
+            );
+            options.wrap = false;
+        }
 
-        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1);
-        tile_map.spread_bombs(options.bomb_count);
+        if !options.neighbourhood.supports(options.topology) {
+            bevy::log::warn!(
+                "{:?} boards don't have a {:?} neighbourhood, using the standard one.",
+                options.topology,
+                options.neighbourhood
+            );
+            options.neighbourhood = Neighbourhood::Standard;
+        }
+
+        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1, options.topology)
+            .with_neighbourhood(options.neighbourhood)
+            .with_depth(options.depth)
+            .with_wrap(options.wrap);
+        if let Some(mask) = &options.mask {
+            tile_map = tile_map.with_mask(mask);
+        }
+        // Every number and flag count needs a texture
+        let max_bombs_per_tile = options
+            .max_bombs_per_tile
+            .clamp(1, TextureHandles::MAX_FLAGS)
+            .min(
+                (TextureHandles::MAX_NUMBER as usize / tile_map.max_neighbour_count().max(1)) as u8,
+            );
+        if max_bombs_per_tile != options.max_bombs_per_tile {
+            bevy::log::warn!(
+                "Can't have {} bombs per tile, using {}.",
+                options.max_bombs_per_tile,
+                max_bombs_per_tile
+            );
+        }
+        tile_map.spread_bombs(options.bomb_count, max_bombs_per_tile);
         #[cfg(feature = "debug")]
         bevy::log::info!("{}", tile_map);
 
         let tile_size = match options.tile_size {
             TileSize::Fixed(v) => v,
-            TileSize::Adaptive { min, max } => {
-                adaptive_tile_size(window, (min, max), (*tile_map.width(), *tile_map.height()))
-            }
+            TileSize::Adaptive { min, max } => adaptive_tile_size(
+                window,
+                (min, max),
+                unit_board_size(options.topology, options.map_size, options.wrap),
+            ),
         };
 
-        let board_size = Vec2::new(
-            *tile_map.width() as f32 * tile_size,
-            *tile_map.height() as f32 * tile_size,
-        );
+        let board_size = options.topology.board_size(options.map_size, tile_size);
+        let sprite_size =
+            options.topology.tile_sprite_size(tile_size) - Vec2::splat(options.tile_padding);
 
         bevy::log::info!("board_size: {}", board_size);
 
@@ -100,6 +183,17 @@ impl<
             options::BoardPosition::Custom(p) => p,
         };
 
+        let shapes = (options.rendering == BoardRendering::Shapes).then(|| {
+            TileShapes::new(
+                options.topology,
+                sprite_size,
+                &options.palette,
+                &style,
+                &mut meshes,
+                &mut materials,
+            )
+        });
+
         let mut covered = HashMap::new();
         let mut flagged = HashMap::new();
 
@@ -114,78 +208,253 @@ impl<
             ))
             .with_children(|parent| {
                 let mut count = 0;
-                for (index, tile) in tile_map.iter().enumerate() {
-                    let coordinate = CoordinateU16 {
-                        x: (index % *tile_map.width() as usize) as u16,
-                        y: (index / *tile_map.width() as usize) as u16,
-                    };
-                    let mut covered_id = None;
-                    let mut entity_commands = parent.spawn((
-                        SpriteBundle {
-                            sprite: Sprite {
-                                color: Color::WHITE,
-                                custom_size: Some(Vec2::splat(tile_size - options.tile_padding)),
-                                ..Default::default()
-                            },
-                            transform: Transform::from_xyz(
-                                (index % *tile_map.width() as usize) as f32 * tile_size
-                                    + tile_size * 0.5,
-                                (index / *tile_map.width() as usize) as f32 * tile_size
-                                    + tile_size * 0.5,
-                                BACKGROUND_Z,
-                            ),
-                            texture: match tile {
-                                Tile::Bomb => textures.tile_bomb.clone(),
-                                Tile::Empty => match tile_map.bomb_count_at_index(index) {
-                                    0 => textures.tile_0.clone(),
-                                    1 => textures.tile_1.clone(),
-                                    2 => textures.tile_2.clone(),
-                                    3 => textures.tile_3.clone(),
-                                    4 => textures.tile_4.clone(),
-                                    5 => textures.tile_5.clone(),
-                                    6 => textures.tile_6.clone(),
-                                    7 => textures.tile_7.clone(),
-                                    8 => textures.tile_8.clone(),
-                                    _ => panic!("Invalid number of bomb neighbours"),
-                                },
-                            },
-                            ..Default::default()
-                        },
-                        Name::new(format!("Tile: {}", index)),
-                        coordinate,
-                        TileMarker,
-                    ));
-                    entity_commands.with_children(|parent| {
-                        covered_id = Some(
-                            parent
+                match (options.rendering, &shapes) {
+                    (BoardRendering::Chunks { size }, _) => {
+                        let material = materials.add(ColorMaterial::from(atlas.image.clone()));
+                        for ((layer, x, y), tiles) in chunk_tiles(&tile_map, size) {
+                            let mesh = chunk_mesh(
+                                &tiles,
+                                |_| (TileAtlas::BASE, style.cover),
+                                options.topology,
+                                tile_size,
+                                sprite_size,
+                                layout,
+                            );
+                            let entity = parent
                                 .spawn((
-                                    SpriteBundle {
-                                        sprite: Sprite {
-                                            color: Color::WHITE,
-                                            custom_size: Some(Vec2::splat(
-                                                tile_size - options.tile_padding,
-                                            )),
-                                            ..Default::default()
-                                        },
-                                        texture: textures.tile_base.clone(),
-                                        transform: Transform::from_xyz(0., 0., FOREGROUND_Z),
+                                    MaterialMesh2dBundle {
+                                        mesh: meshes.add(mesh).into(),
+                                        material: material.clone(),
+                                        visibility: layer_visibility(layer, 0),
                                         ..Default::default()
                                     },
-                                    Name::new(format!("Tile Cover: {}", index)),
-                                    coordinate,
-                                    TileCoverMarker,
+                                    Name::new(format!("Tile Chunk: ({};{};{})", x, y, layer)),
+                                    TileChunk {
+                                        layer,
+                                        shown: vec![TileAtlas::BASE; tiles.len()],
+                                        tiles: tiles.clone(),
+                                        tile_size,
+                                    },
                                 ))
-                                .id(),
-                        );
-                    });
-                    covered.insert(coordinate, entity_commands.id());
-                    flagged.insert(
-                        coordinate,
-                        (entity_commands.id(), covered_id.unwrap(), false),
-                    );
-                    count += 1;
+                                .id();
+                            // The chunk stands in for the tile and cover entities of its tiles
+                            for coordinate in tiles {
+                                covered.insert(coordinate, entity);
+                                flagged.insert(coordinate, (entity, entity, 0));
+                            }
+                            count += 1;
+                        }
+                    }
+                    (_, Some(shapes)) => {
+                        for (index, tile) in tile_map.iter().enumerate() {
+                            let coordinate = tile_map.coordinate(index);
+                            if tile.is_void() {
+                                continue;
+                            }
+                            let shape = TileShape {
+                                upside_down: options.topology.is_upside_down(coordinate),
+                                ghost: false,
+                            };
+                            let shown = match tile {
+                                Tile::Bomb(_) => TileAtlas::BOMB,
+                                Tile::Empty => {
+                                    TileAtlas::number(tile_map.bomb_count_at_index(index))
+                                }
+                                Tile::Void => unreachable!(),
+                            };
+                            let mut covered_id = None;
+                            let mut entity_commands = parent.spawn((
+                                ColorMesh2dBundle {
+                                    mesh: shapes.mesh(&shape).into(),
+                                    material: shapes.material(shown, false),
+                                    transform: Transform::from_translation(
+                                        options
+                                            .topology
+                                            .tile_center(coordinate, tile_size)
+                                            .extend(BACKGROUND_Z),
+                                    ),
+                                    visibility: layer_visibility(coordinate.z, 0),
+                                    ..Default::default()
+                                },
+                                atlas.sprite(shown),
+                                shape,
+                                Name::new(format!("Tile: {}", index)),
+                                coordinate,
+                                TileMarker,
+                            ));
+                            entity_commands.with_children(|parent| {
+                                covered_id = Some(
+                                    parent
+                                        .spawn((
+                                            ColorMesh2dBundle {
+                                                mesh: shapes.mesh(&shape).into(),
+                                                material: shapes.material(TileAtlas::BASE, false),
+                                                transform: Transform::from_xyz(
+                                                    0.,
+                                                    0.,
+                                                    FOREGROUND_Z,
+                                                ),
+                                                ..Default::default()
+                                            },
+                                            atlas.sprite(TileAtlas::BASE),
+                                            shape,
+                                            Name::new(format!("Tile Cover: {}", index)),
+                                            coordinate,
+                                            TileCoverMarker,
+                                        ))
+                                        .id(),
+                                );
+                            });
+                            covered.insert(coordinate, entity_commands.id());
+                            flagged
+                                .insert(coordinate, (entity_commands.id(), covered_id.unwrap(), 0));
+                            count += 1;
+                        }
+                    }
+                    _ => {
+                        for (index, tile) in tile_map.iter().enumerate() {
+                            let coordinate = tile_map.coordinate(index);
+                            if tile.is_void() {
+                                continue;
+                            }
+                            let mut covered_id = None;
+                            let mut entity_commands = parent.spawn((
+                                SpriteSheetBundle {
+                                    sprite: Sprite {
+                                        color: style.tile,
+                                        custom_size: Some(sprite_size),
+                                        rect: options.topology.texture_rect(coordinate),
+                                        ..Default::default()
+                                    },
+                                    transform: Transform::from_translation(
+                                        options
+                                            .topology
+                                            .tile_center(coordinate, tile_size)
+                                            .extend(BACKGROUND_Z),
+                                    ),
+                                    atlas: atlas.sprite(match tile {
+                                        Tile::Bomb(_) => TileAtlas::BOMB,
+                                        Tile::Empty => {
+                                            TileAtlas::number(tile_map.bomb_count_at_index(index))
+                                        }
+                                        Tile::Void => unreachable!(),
+                                    }),
+                                    texture: atlas.image.clone(),
+                                    visibility: layer_visibility(coordinate.z, 0),
+                                    ..Default::default()
+                                },
+                                Name::new(format!("Tile: {}", index)),
+                                coordinate,
+                                TileMarker,
+                            ));
+                            entity_commands.with_children(|parent| {
+                                covered_id = Some(
+                                    parent
+                                        .spawn((
+                                            SpriteSheetBundle {
+                                                sprite: Sprite {
+                                                    color: style.cover,
+                                                    custom_size: Some(sprite_size),
+                                                    rect: options.topology.texture_rect(coordinate),
+                                                    ..Default::default()
+                                                },
+                                                atlas: atlas.sprite(TileAtlas::BASE),
+                                                texture: atlas.image.clone(),
+                                                transform: Transform::from_xyz(
+                                                    0.,
+                                                    0.,
+                                                    FOREGROUND_Z,
+                                                ),
+                                                ..Default::default()
+                                            },
+                                            Name::new(format!("Tile Cover: {}", index)),
+                                            coordinate,
+                                            TileCoverMarker,
+                                        ))
+                                        .id(),
+                                );
+                            });
+                            covered.insert(coordinate, entity_commands.id());
+                            flagged
+                                .insert(coordinate, (entity_commands.id(), covered_id.unwrap(), 0));
+                            count += 1;
+                        }
+                    }
                 }
                 bevy::log::debug!("Ran loop {} times", count);
+
+                if *tile_map.depth() > 1 {
+                    parent.spawn((
+                        Text2dBundle {
+                            text: Text::from_section(
+                                layer_label_text(0, *tile_map.depth()),
+                                TextStyle {
+                                    font: style.font.clone(),
+                                    font_size: tile_size * 0.8,
+                                    color: style.text,
+                                },
+                            )
+                            .with_justify(JustifyText::Center),
+                            transform: Transform::from_translation(layer_label_translation(
+                                board_size, tile_size,
+                            )),
+                            ..Default::default()
+                        },
+                        Name::new("Layer Label"),
+                        LayerLabel,
+                    ));
+                }
+
+                if !options.wrap {
+                    return;
+                }
+                // Copies of the tiles on the opposite edges, so the wrap can be seen
+                let period = options.topology.wrap_period(options.map_size, tile_size);
+                for ghost in ghost_tiles(options.map_size, *tile_map.depth())
+                    .into_iter()
+                    .filter(|ghost| !tile_map[ghost.source].is_void())
+                {
+                    let transform = Transform::from_translation(
+                        (options.topology.tile_center(ghost.source, tile_size)
+                            + period * Vec2::new(ghost.shift.0 as f32, ghost.shift.1 as f32))
+                        .extend(BACKGROUND_Z),
+                    );
+                    let visibility = layer_visibility(ghost.source.z, 0);
+                    let mut ghost_commands = match &shapes {
+                        Some(shapes) => {
+                            let shape = TileShape {
+                                upside_down: options.topology.is_upside_down(ghost.source),
+                                ghost: true,
+                            };
+                            parent.spawn((
+                                ColorMesh2dBundle {
+                                    mesh: shapes.mesh(&shape).into(),
+                                    material: shapes.material(TileAtlas::BASE, true),
+                                    transform,
+                                    visibility,
+                                    ..Default::default()
+                                },
+                                atlas.sprite(TileAtlas::BASE),
+                                shape,
+                            ))
+                        }
+                        None => parent.spawn(SpriteSheetBundle {
+                            sprite: Sprite {
+                                color: GHOST_COLOR,
+                                custom_size: Some(sprite_size),
+                                rect: options.topology.texture_rect(ghost.source),
+                                ..Default::default()
+                            },
+                            transform,
+                            atlas: atlas.sprite(TileAtlas::BASE),
+                            texture: atlas.image.clone(),
+                            visibility,
+                            ..Default::default()
+                        }),
+                    };
+                    ghost_commands
+                        .insert((Name::new(format!("Ghost Tile: {}", ghost.source)), ghost));
+                }
             });
         commands.insert_resource(Board {
             tile_map,
@@ -197,7 +466,15 @@ impl<
             covered,
             flagged,
             flag_count: 0,
+            exploded: 0,
+            layer: 0,
         });
+        match shapes {
+            Some(shapes) => commands.insert_resource(shapes),
+            // The shapes of the last board aren't used anymore
+            None => commands.remove_resource::<TileShapes>(),
+        }
+        start_modifiers(&mut commands, &options, &style);
         next_state.set(MinesweeperState::Running);
     }
 
@@ -211,3 +488,39 @@ impl<
         state.set(MinesweeperState::NewGame);
     }
 }
+
+/// The ghost tiles that form a ring of one tile around every layer of a wrapping board
+pub(crate) fn ghost_tiles((width, height): (u16, u16), depth: u16) -> Vec<GhostTile> {
+    let (width, height) = (width as i32, height as i32);
+    let mut ghosts = Vec::new();
+    for (z, y) in (0..depth).flat_map(|z| (0..height).map(move |y| (z, y))) {
+        for x in 0..width {
+            for (shift_x, shift_y) in GHOST_SHIFTS {
+                let ghost_x = x + shift_x as i32 * width;
+                let ghost_y = y + shift_y as i32 * height;
+                if (-1..=width).contains(&ghost_x) && (-1..=height).contains(&ghost_y) {
+                    ghosts.push(GhostTile {
+                        source: CoordinateU16 {
+                            x: x as u16,
+                            y: y as u16,
+                            z,
+                        },
+                        shift: (shift_x, shift_y),
+                    });
+                }
+            }
+        }
+    }
+    ghosts
+}
+
+const GHOST_SHIFTS: [(i8, i8); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
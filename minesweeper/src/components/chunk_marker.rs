@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Parent of the tiles of one chunk of an endless board. Contains the chunk position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct ChunkMarker(pub IVec2);
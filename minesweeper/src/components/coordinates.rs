@@ -4,6 +4,30 @@ use bevy::prelude::*;
 pub struct CoordinateU16 {
     pub x: u16,
     pub y: u16,
+    /// Layer of the tile. Flat boards only have layer 0
+    pub z: u16,
+}
+
+impl CoordinateU16 {
+    /// Moves the coordinate by an offset inside its layer. Returns `None` if it leaves a board of `(width, height)` tiles,
+    /// unless `wrap` is set, then it comes back in on the opposite edge
+    pub fn offset(self, (x, y): (i8, i8), (width, height): (u16, u16), wrap: bool) -> Option<Self> {
+        let (x, y) = (self.x as i32 + x as i32, self.y as i32 + y as i32);
+        let (width, height) = (width as i32, height as i32);
+        match wrap {
+            true => Some(Self {
+                x: x.rem_euclid(width) as u16,
+                y: y.rem_euclid(height) as u16,
+                z: self.z,
+            }),
+            false if (0..width).contains(&x) && (0..height).contains(&y) => Some(Self {
+                x: x as u16,
+                y: y as u16,
+                z: self.z,
+            }),
+            false => None,
+        }
+    }
 }
 
 impl std::ops::Add for CoordinateU16 {
@@ -13,6 +37,7 @@ impl std::ops::Add for CoordinateU16 {
         Self {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
+            z: self.z + rhs.z,
         }
     }
 }
@@ -24,6 +49,7 @@ impl std::ops::Add<(i8, i8)> for CoordinateU16 {
         Self {
             x: (self.x as i16 + x as i16) as u16,
             y: (self.y as i16 + y as i16) as u16,
+            z: self.z,
         }
     }
 }
@@ -35,6 +61,7 @@ impl std::ops::Sub for CoordinateU16 {
         Self {
             x: self.x - rhs.x,
             y: self.y - rhs.y,
+            z: self.z - rhs.z,
         }
     }
 }
@@ -46,12 +73,16 @@ impl std::ops::Sub<(i8, i8)> for CoordinateU16 {
         Self {
             x: (self.x as i16 - x as i16) as u16,
             y: (self.y as i16 - y as i16) as u16,
+            z: self.z,
         }
     }
 }
 
 impl std::fmt::Display for CoordinateU16 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({};{})", self.x, self.y)
+        match self.z {
+            0 => write!(f, "({};{})", self.x, self.y),
+            z => write!(f, "({};{};{})", self.x, self.y, z),
+        }
     }
 }
@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// Tile that was uncovered while [crate::options::Animations::uncover] is on. The component moves to its cover,
+/// which is despawned once the animation is over
+#[derive(Debug, Clone, Copy, Component)]
+pub struct CoverAnimation {
+    /// Time since startup when the animation starts. Tiles further away from the clicked tile start later
+    pub start: Duration,
+}
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// A tile of an endless board. Contains the tile position, which can be negative
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct EndlessTile(pub IVec2);
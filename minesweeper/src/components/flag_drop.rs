@@ -0,0 +1,10 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// Cover that just got a flag, which drops onto the tile
+#[derive(Debug, Clone, Copy, Component)]
+pub struct FlagDrop {
+    /// Time since startup when the flag was placed
+    pub start: Duration,
+}
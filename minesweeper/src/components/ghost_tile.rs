@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use super::coordinates::CoordinateU16;
+
+/// A see through copy of a tile from the opposite edge, drawn around wrapping boards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub struct GhostTile {
+    pub source: CoordinateU16,
+    /// In which direction the ghost is moved away from the source, by one board size
+    pub shift: (i8, i8),
+}
@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Text above layered boards that shows which layer is visible
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct LayerLabel;
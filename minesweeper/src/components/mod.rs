@@ -1,5 +1,18 @@
 pub mod board_marker;
+pub mod chunk_marker;
 pub mod coordinates;
-pub mod tile_marker;
+pub mod cover_animation;
+pub mod endless_tile;
+pub mod flag_drop;
+pub mod ghost_tile;
+pub mod layer_label;
+pub mod modifier_text;
+pub mod music;
+pub mod particle;
+pub mod score_text;
+pub mod tile_chunk;
 pub mod tile_cover_marker;
-pub mod uncover_marker;
\ No newline at end of file
+pub mod tile_drawing;
+pub mod tile_marker;
+pub mod tile_shape;
+pub mod uncover_marker;
@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Text in the corner of the window that shows the remaining time and lives
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct ModifierText;
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// The background music of the theme
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Music;
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// Sprite of an explosion or the win celebration that flies around and fades out until its lifetime is over
+#[derive(Debug, Clone, Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    /// Downwards acceleration
+    pub gravity: f32,
+    pub lifetime: Timer,
+}
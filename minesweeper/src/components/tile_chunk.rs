@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use super::coordinates::CoordinateU16;
+
+/// Part of a board that is drawn as one mesh with a quad for every tile.
+/// Used instead of tile and cover entities by [crate::options::BoardRendering::Chunks]
+#[derive(Debug, Clone, Component)]
+pub struct TileChunk {
+    pub layer: u16,
+    /// Tiles in the order of their quads
+    pub tiles: Vec<CoordinateU16>,
+    /// Atlas indices the mesh shows right now
+    pub shown: Vec<usize>,
+    /// Tile size the mesh was built for
+    pub tile_size: f32,
+}
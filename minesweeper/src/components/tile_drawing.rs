@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Number, bomb or flag drawn on a [super::tile_shape::TileShape]. They are drawn again when the tile changes
+#[derive(Debug, Clone, Copy, Component)]
+pub struct TileDrawing;
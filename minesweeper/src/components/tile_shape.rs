@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// Tile, cover or ghost tile drawn with [crate::options::BoardRendering::Shapes]. What it shows is the index of its
+/// [TextureAtlas] like on sprites, so the systems that change tiles work the same for both
+#[derive(Debug, Clone, Copy, Component)]
+pub struct TileShape {
+    /// Triangle that points downwards
+    pub upside_down: bool,
+    pub ghost: bool,
+}
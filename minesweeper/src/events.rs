@@ -2,15 +2,20 @@ use bevy::prelude::*;
 
 use crate::components::coordinates::CoordinateU16;
 
-/// Event that is sent when a tile is left clicked. Contains the board coordinates of the tile
+/// Event that is sent when a tile is left clicked, and for the tiles around empty tiles. Dereferences to the board
+/// coordinates of the tile
 #[derive(Debug, Clone, Copy, Event)]
-pub(crate) struct TileUncoverEvent(pub CoordinateU16);
+pub(crate) struct TileUncoverEvent {
+    pub coordinate: CoordinateU16,
+    /// Sent because a neighbour is empty, not because of a click
+    pub cascade: bool,
+}
 
 impl std::ops::Deref for TileUncoverEvent {
     type Target = CoordinateU16;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.coordinate
     }
 }
 
@@ -25,3 +30,19 @@ impl std::ops::Deref for TileFlagEvent {
         &self.0
     }
 }
+
+/// Event that is sent when a tile of an endless board is left clicked. Contains the tile position
+#[derive(Debug, Clone, Copy, Event)]
+pub(crate) struct EndlessUncoverEvent(pub IVec2);
+
+/// Event that is sent when a tile of an endless board is right clicked. Contains the tile position
+#[derive(Debug, Clone, Copy, Event)]
+pub(crate) struct EndlessFlagEvent(pub IVec2);
+
+/// Event that is sent when a bomb was uncovered, on any kind of board. The rule modifiers decide if the game is lost
+#[derive(Debug, Clone, Copy, Event)]
+pub(crate) struct BombHitEvent {
+    /// World position of the center of the bomb's tile
+    pub position: Vec2,
+    pub tile_size: f32,
+}
@@ -0,0 +1,331 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::{
+    palette::TilePalette,
+    texture_handles::TextureHandles,
+    theme::{Theme, ThemeSounds, ThemeStyle},
+    topology::{Topology, TRIANGLE_TEXTURE},
+};
+
+/// Size of the square and hexagon textures, the same as the bundled ones
+const TEXTURE_SIZE: f32 = 32.;
+/// Width of the darker border around tiles, in pixels
+const EDGE: f32 = 2.;
+
+const COVER_EDGE: [u8; 4] = [128, 128, 128, 255];
+const TILE_EDGE: [u8; 4] = [136, 136, 136, 255];
+const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+/// Digits of 3 by 5 pixels. One row per number from the top, the left pixel is the highest bit
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// A theme that is drawn in code instead of loaded from the assets folder. The game still runs with it when the
+/// theme files are missing or broken
+pub fn fallback_theme(images: &mut Assets<Image>) -> Theme {
+    let mut textures =
+        |topology| TextureHandles::load_with(|file| images.add(draw_texture(topology, file)));
+    Theme {
+        name: "Built-in".to_string(),
+        square: textures(Topology::Square),
+        hexagon: textures(Topology::Hexagon),
+        triangle: textures(Topology::Triangle),
+        style: ThemeStyle::default(),
+        sounds: ThemeSounds::default(),
+    }
+}
+
+/// Makes the [fallback_theme] the current theme
+pub fn insert_fallback_theme(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    themes: &mut Assets<Theme>,
+    topology: Topology,
+) {
+    let theme = fallback_theme(images);
+    let handle = themes.add(theme.clone());
+    theme.insert_resources(commands, handle, topology);
+}
+
+/// Draws the texture of one of the [TextureHandles::files] in the layout the textures of `topology` have.
+/// The colours are the ones of the classic [TilePalette]
+fn draw_texture(topology: Topology, file: &str) -> Image {
+    let palette = TilePalette::classic();
+    let [cover, tile, bomb, flag, pole] = [
+        palette.cover,
+        palette.tile,
+        palette.bomb,
+        palette.flag,
+        palette.pole,
+    ]
+    .map(|color| color.as_rgba_u8());
+    let mut canvas = Canvas::new(topology);
+    let name = file.trim_end_matches(".png");
+    match (name, name.strip_prefix("Flag")) {
+        ("Base", _) => canvas.tile(cover, COVER_EDGE),
+        ("Bomb", _) => {
+            canvas.tile(tile, TILE_EDGE);
+            canvas.bomb(bomb);
+        }
+        (_, Some(count)) => {
+            canvas.tile(cover, COVER_EDGE);
+            match count.parse::<u8>() {
+                Ok(count) => {
+                    canvas.flag(Vec2::new(-3., 0.), flag, pole);
+                    canvas.number(count, Vec2::new(8., 0.), 2., pole);
+                }
+                Err(_) => canvas.flag(Vec2::ZERO, flag, pole),
+            }
+        }
+        (number, None) => {
+            canvas.tile(tile, TILE_EDGE);
+            let number: u8 = number.parse().unwrap_or_default();
+            if number > 0 {
+                canvas.number(number, Vec2::ZERO, 3., palette.number(number).as_rgba_u8());
+            }
+        }
+    }
+    canvas.image()
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Shape {
+    Square,
+    Hexagon,
+    /// Triangle with the tip at the top
+    Up,
+    Down,
+}
+
+/// Part of a texture with one tile shape. Triangle textures have two of them
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    shape: Shape,
+    /// Horizontal position in the texture
+    x: u32,
+    size: Vec2,
+    /// How much bigger the drawings are than on square tiles
+    scale: f32,
+}
+
+impl Cell {
+    fn contains(&self, pixel: Vec2) -> bool {
+        let from_center = (pixel - self.size / 2.).abs();
+        let inside = pixel.cmpge(Vec2::ZERO).all() && pixel.cmplt(self.size).all();
+        inside
+            && match self.shape {
+                Shape::Square => true,
+                // Pointy topped, the sides are half as high as the texture
+                Shape::Hexagon => {
+                    from_center.y
+                        <= self.size.y / 2. - from_center.x * self.size.y / self.size.x / 2.
+                }
+                Shape::Up => from_center.x <= pixel.y / self.size.y * self.size.x / 2.,
+                Shape::Down => {
+                    from_center.x <= (self.size.y - pixel.y) / self.size.y * self.size.x / 2.
+                }
+            }
+    }
+
+    /// Pixels close to the outline of the shape
+    fn is_edge(&self, pixel: Vec2) -> bool {
+        [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y]
+            .into_iter()
+            .any(|direction| !self.contains(pixel + direction * EDGE * self.scale))
+    }
+
+    /// Where the drawings on the tile go. Triangles have them at their centroid
+    fn center(&self) -> Vec2 {
+        match self.shape {
+            Shape::Square | Shape::Hexagon => self.size / 2.,
+            Shape::Up => Vec2::new(self.size.x / 2., self.size.y * 2. / 3.),
+            Shape::Down => Vec2::new(self.size.x / 2., self.size.y / 3.),
+        }
+    }
+}
+
+/// RGBA pixels with the origin at the top left
+struct Canvas {
+    size: UVec2,
+    cells: Vec<Cell>,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(topology: Topology) -> Self {
+        let cell = |shape, x, size, scale| Cell {
+            shape,
+            x,
+            size,
+            scale,
+        };
+        let cells = match topology {
+            Topology::Square => vec![cell(Shape::Square, 0, Vec2::splat(TEXTURE_SIZE), 1.)],
+            Topology::Hexagon => vec![cell(Shape::Hexagon, 0, Vec2::splat(TEXTURE_SIZE), 1.)],
+            Topology::Triangle => vec![
+                cell(Shape::Up, 0, TRIANGLE_TEXTURE, 1.5),
+                cell(
+                    Shape::Down,
+                    TRIANGLE_TEXTURE.x as u32,
+                    TRIANGLE_TEXTURE,
+                    1.5,
+                ),
+            ],
+        };
+        let size = UVec2::new(
+            cells.iter().map(|cell| cell.size.x as u32).sum(),
+            cells[0].size.y as u32,
+        );
+        Self {
+            size,
+            cells,
+            data: vec![0; (size.x * size.y * 4) as usize],
+        }
+    }
+
+    /// Sets the pixels of every cell that `color` gives a colour. It gets the cell and the position of the pixel
+    /// relative to the center of the cell, divided by the scale of the cell
+    fn paint(&mut self, color: impl Fn(&Cell, Vec2) -> Option<[u8; 4]>) {
+        for cell in &self.cells {
+            for y in 0..cell.size.y as u32 {
+                for x in 0..cell.size.x as u32 {
+                    let pixel = Vec2::new(x as f32, y as f32) + 0.5;
+                    if !cell.contains(pixel) {
+                        continue;
+                    }
+                    if let Some(color) = color(cell, (pixel - cell.center()) / cell.scale) {
+                        let index = ((y * self.size.x + cell.x + x) * 4) as usize;
+                        self.data[index..index + 4].copy_from_slice(&color);
+                    }
+                }
+            }
+        }
+    }
+
+    fn tile(&mut self, fill: [u8; 4], edge: [u8; 4]) {
+        self.paint(|cell, position| {
+            let pixel = position * cell.scale + cell.center();
+            Some(match cell.is_edge(pixel) {
+                true => edge,
+                false => fill,
+            })
+        });
+    }
+
+    fn bomb(&mut self, color: [u8; 4]) {
+        self.paint(|_, position| {
+            let spike = position.abs().min_element() < 1. && position.abs().max_element() < 10.;
+            if position.distance(Vec2::splat(-2.5)) < 1.5 {
+                Some(WHITE)
+            } else if position.length() < 7. || spike {
+                Some(color)
+            } else {
+                None
+            }
+        });
+    }
+
+    fn flag(&mut self, offset: Vec2, flag: [u8; 4], pole: [u8; 4]) {
+        self.paint(|_, position| {
+            let position = position - offset;
+            let on_pole = (1. ..3.).contains(&position.x) && (-8. ..7.).contains(&position.y);
+            let on_foot = (-4. ..7.).contains(&position.x) && (6. ..8.).contains(&position.y);
+            // Points to the left from the top of the pole
+            let on_cloth = (-7. ..1.).contains(&position.x)
+                && (position.y + 4.).abs() <= (position.x + 7.) / 2.;
+            if on_pole || on_foot {
+                Some(pole)
+            } else if on_cloth {
+                Some(flag)
+            } else {
+                None
+            }
+        });
+    }
+
+    /// Writes a number with the [DIGITS], which are made bigger by `size`
+    fn number(&mut self, number: u8, offset: Vec2, size: f32, color: [u8; 4]) {
+        let digits: Vec<usize> = number
+            .to_string()
+            .bytes()
+            .map(|digit| (digit - b'0') as usize)
+            .collect();
+        // One pixel between digits
+        let width = (digits.len() * 4 - 1) as f32 * size;
+        let top_left = offset - Vec2::new(width, 5. * size) / 2.;
+        self.paint(|_, position| {
+            let pixel = ((position - top_left) / size).floor();
+            if pixel.x < 0. || pixel.y < 0. || pixel.y >= 5. {
+                return None;
+            }
+            let (digit, column) = (pixel.x as usize / 4, pixel.x as usize % 4);
+            let row = DIGITS[*digits.get(digit)?][pixel.y as usize];
+            (column < 3 && row & (0b100 >> column) != 0).then_some(color)
+        });
+    }
+
+    fn image(self) -> Image {
+        Image::new(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            self.data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::atlas::TileAtlas;
+
+    #[test]
+    fn fallback_textures_can_be_packed() {
+        let mut images = Assets::<Image>::default();
+        let mut layouts = Assets::<TextureAtlasLayout>::default();
+        let theme = fallback_theme(&mut images);
+
+        for (topology, size) in [
+            (Topology::Square, UVec2::splat(32)),
+            (Topology::Hexagon, UVec2::splat(32)),
+            (Topology::Triangle, UVec2::new(128, 56)),
+        ] {
+            let textures = theme.textures(topology);
+            assert_eq!(images.get(&textures.tile_base).unwrap().size(), size);
+            assert!(TileAtlas::build(textures, &mut images, &mut layouts).is_ok());
+        }
+    }
+
+    #[test]
+    fn numbers_are_drawn_in_their_colour() {
+        let image = draw_texture(Topology::Square, "3.png");
+        let color = |pixel: &[u8]| pixel == TilePalette::classic().number(3).as_rgba_u8();
+
+        assert!(image.data.chunks(4).any(color));
+        assert!(!draw_texture(Topology::Square, "0.png")
+            .data
+            .chunks(4)
+            .any(color));
+    }
+}
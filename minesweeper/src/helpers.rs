@@ -1,11 +1,109 @@
-use bevy::prelude::Window;
-
-pub(crate) fn adaptive_tile_size(
-    window: &Window,
-    (min, max): (f32, f32),
-    (width, height): (u16, u16),
-) -> f32 {
-    let max_width = window.width() / width as f32;
-    let max_height = window.height() / height as f32;
+use bevy::prelude::*;
+
+use crate::{
+    components::{endless_tile::EndlessTile, modifier_text::ModifierText},
+    options::BoardOptions,
+    resources::{board::Board, endless_board::EndlessBoard, modifier_state::ModifierState},
+    theme::ThemeStyle,
+    topology::Topology,
+};
+
+/// Biggest tile size that fits the board into the window. `board_size` is the size of the board for a tile size of 1
+pub(crate) fn adaptive_tile_size(window: &Window, (min, max): (f32, f32), board_size: Vec2) -> f32 {
+    let max_width = window.width() / board_size.x;
+    let max_height = window.height() / board_size.y;
     max_width.min(max_height).clamp(min, max)
 }
+
+/// Size of the board for a tile size of 1, including the ghost tiles around wrapping boards
+pub(crate) fn unit_board_size(topology: Topology, (width, height): (u16, u16), wrap: bool) -> Vec2 {
+    match wrap {
+        true => topology.board_size((width + 2, height + 2), 1.),
+        false => topology.board_size((width, height), 1.),
+    }
+}
+
+/// Only the tiles of the selected layer are shown
+pub(crate) fn layer_visibility(z: u16, layer: u16) -> Visibility {
+    match z == layer {
+        true => Visibility::Inherited,
+        false => Visibility::Hidden,
+    }
+}
+
+pub(crate) fn layer_label_text(layer: u16, depth: u16) -> String {
+    format!("Layer {}/{}", layer + 1, depth)
+}
+
+/// The layer label is centered above the board, out of the way of the ghost tiles
+pub(crate) fn layer_label_translation(board_size: Vec2, tile_size: f32) -> Vec3 {
+    Vec3::new(
+        board_size.x / 2.,
+        board_size.y + tile_size * 1.5,
+        crate::FOREGROUND_Z,
+    )
+}
+
+/// Run condition for the systems of endless boards
+pub(crate) fn is_endless(options: Option<Res<BoardOptions>>) -> bool {
+    options.is_some_and(|options| options.endless.is_some())
+}
+
+/// Run condition for the systems that place flags, which the no flags modifier turns off
+pub(crate) fn flags_allowed(options: Option<Res<BoardOptions>>) -> bool {
+    !options.is_some_and(|options| options.modifiers.no_flags)
+}
+
+/// Starts the modifiers of a new game and spawns the text that shows them, if there is anything to show
+pub(crate) fn start_modifiers(commands: &mut Commands, options: &BoardOptions, style: &ThemeStyle) {
+    let mut state = ModifierState::new(&options.modifiers);
+    if let Some(time_limit) = options.win_condition().time_limit() {
+        state.countdown = Some(Timer::new(time_limit, TimerMode::Once));
+    }
+    if let Some(text) = state.text(options, 0) {
+        commands.spawn((
+            TextBundle::from_section(
+                text,
+                TextStyle {
+                    font: style.font.clone(),
+                    font_size: 30.,
+                    color: style.text,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.),
+                right: Val::Px(10.),
+                ..Default::default()
+            }),
+            Name::new("Modifiers"),
+            ModifierText,
+        ));
+    }
+    commands.insert_resource(state);
+}
+
+/// Score of the current game on either kind of board
+pub(crate) fn current_score(board: Option<&Board>, endless_board: Option<&EndlessBoard>) -> usize {
+    match (board, endless_board) {
+        (_, Some(endless_board)) => endless_board.score(),
+        (Some(board), None) => board.score(),
+        (None, None) => 0,
+    }
+}
+
+/// Shows the current state of endless board tiles on their sprites. Tiles that aren't loaded are skipped
+pub(crate) fn refresh_endless_tiles(
+    board: &mut EndlessBoard,
+    tiles: &mut Query<&mut TextureAtlas, With<EndlessTile>>,
+    positions: impl IntoIterator<Item = IVec2>,
+) {
+    for position in positions {
+        let Some(entity) = board.tiles.get(&position).copied() else {
+            continue;
+        };
+        if let Ok(mut atlas) = tiles.get_mut(entity) {
+            atlas.index = board.atlas_index(position);
+        }
+    }
+}
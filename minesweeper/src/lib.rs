@@ -1,24 +1,49 @@
+pub mod aseprite;
+pub mod atlas;
 pub(crate) mod board;
 pub(crate) mod components;
 pub mod events;
+pub mod fallback_theme;
 pub(crate) mod helpers;
+pub mod mask;
+pub mod neighbourhood;
 pub mod options;
+pub mod palette;
 pub(crate) mod resources;
 pub(crate) mod states;
 pub(crate) mod systems;
 pub mod texture_handles;
+pub mod theme;
+pub(crate) mod tile_mesh;
+pub mod topology;
+pub mod win_condition;
 
 use bevy::prelude::*;
 
+/// The grid helpers are shared with other grid games of the collection
+pub use crate::{components::coordinates::CoordinateU16, resources::tile_map::TileMap};
+
 use crate::{
-    events::{TileFlagEvent, TileUncoverEvent},
-    resources::plugin_options::PluginOptions,
+    events::{
+        BombHitEvent, EndlessFlagEvent, EndlessUncoverEvent, TileFlagEvent, TileUncoverEvent,
+    },
+    helpers::{flags_allowed, is_endless},
+    options::{AccessibilityOptions, AudioOptions},
+    resources::{
+        board::Board, plugin_options::PluginOptions, screen_shake::ScreenShake,
+        tile_press::TilePress, tile_shapes::TileShapes,
+    },
     states::plugin_state::MinesweeperState,
+    texture_handles::TextureHandles,
+    theme::{Theme, ThemeLoader},
 };
 
 pub(crate) const BACKGROUND_Z: f32 = 0.;
 pub(crate) const FOREGROUND_Z: f32 = 1.;
 
+/// Ghost tiles around wrapping boards are see through so they aren't mistaken for real tiles
+pub(crate) const GHOST_COLOR: Color = Color::rgba(1., 1., 1., 0.35);
+
 pub struct MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
 where
     TyRunState: States + PartialEq,
@@ -48,6 +73,16 @@ impl<
         app.init_state::<MinesweeperState>()
             .add_event::<TileUncoverEvent>()
             .add_event::<TileFlagEvent>()
+            .add_event::<EndlessUncoverEvent>()
+            .add_event::<EndlessFlagEvent>()
+            .add_event::<BombHitEvent>()
+            .init_asset::<Theme>()
+            .register_asset_loader(ThemeLoader)
+            .init_resource::<ScreenShake>()
+            .init_resource::<AudioOptions>()
+            .init_resource::<TilePress>()
+            .init_resource::<TilePress<IVec2>>()
+            .init_resource::<AccessibilityOptions>()
             .insert_resource(PluginOptions {
                 run_state: self.run_state.clone(),
                 pause_state: self.pause_state.clone(),
@@ -55,26 +90,163 @@ impl<
                 won_event: self.won_event.clone(),
                 lost_event: self.lost_event.clone(),
             })
+            // Tiles are drawn from an atlas of the current textures, it's ready before boards are created
+            .add_systems(
+                PreUpdate,
+                crate::systems::tile_atlas::build_tile_atlas
+                    .run_if(resource_exists_and_changed::<TextureHandles>),
+            )
             .add_systems(
                 Update,
                 (
                     Self::create_board
                         .run_if(in_state(MinesweeperState::NewGame))
-                        .run_if(in_state(self.run_state.clone())),
+                        .run_if(in_state(self.run_state.clone()))
+                        .run_if(not(is_endless)),
                     crate::systems::rescale::rescale,
                     crate::systems::input::input.run_if(
                         in_state(MinesweeperState::Running)
                             .and_then(in_state(self.run_state.clone()))
-                            .and_then(not(in_state(self.pause_state.clone()))),
+                            .and_then(not(in_state(self.pause_state.clone())))
+                            .and_then(not(is_endless)),
+                    ),
+                    crate::systems::input::board_cursor::<CoordinateU16>
+                        .after(crate::systems::input::input),
+                    crate::systems::tile_feedback::show_tile_feedback
+                        .after(crate::systems::input::input)
+                        .after(crate::systems::tile_shapes::draw_tile_shapes)
+                        .before(crate::systems::tile_chunks::update_tile_chunks)
+                        .run_if(resource_exists::<Board>.and_then(not(is_endless))),
+                    crate::systems::layer_selector::layer_selector.run_if(
+                        in_state(MinesweeperState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone())))
+                            .and_then(not(is_endless)),
                     ),
                     crate::systems::remove_cover::remove_cover
                         .run_if(in_state(MinesweeperState::Running)),
-                    Self::tile_uncover_event_handler.run_if(in_state(MinesweeperState::Running)),
-                    crate::systems::tile_flag_event_handler::tile_flag_event_handler
-                        .run_if(in_state(MinesweeperState::Running)),
-                    Self::game_end_loop.run_if(in_state(MinesweeperState::Running)),
+                    Self::tile_uncover_event_handler
+                        .run_if(in_state(MinesweeperState::Running).and_then(not(is_endless))),
+                    crate::systems::tile_flag_event_handler::tile_flag_event_handler.run_if(
+                        in_state(MinesweeperState::Running)
+                            .and_then(not(is_endless))
+                            .and_then(flags_allowed),
+                    ),
+                    Self::game_end_loop
+                        .run_if(in_state(MinesweeperState::Running).and_then(not(is_endless))),
+                    crate::systems::ghost_tiles::ghost_tiles
+                        .run_if(in_state(MinesweeperState::Running).and_then(not(is_endless))),
+                    crate::systems::tile_chunks::update_tile_chunks
+                        .run_if(resource_exists::<Board>.and_then(not(is_endless))),
+                    crate::systems::tile_shapes::switch_tile_rendering
+                        .before(crate::systems::tile_shapes::draw_tile_shapes)
+                        .run_if(
+                            resource_exists::<Board>
+                                .and_then(resource_changed::<AccessibilityOptions>)
+                                .and_then(not(is_endless)),
+                        ),
+                    crate::systems::tile_shapes::draw_tile_shapes.run_if(
+                        resource_exists::<Board>
+                            .and_then(resource_exists::<TileShapes>)
+                            .and_then(not(is_endless)),
+                    ),
                     Self::reset_event_handler,
                 ),
+            )
+            // Rule modifiers sit between hitting a bomb and losing the game
+            .add_systems(
+                Update,
+                (
+                    Self::bomb_hit_event_handler,
+                    Self::countdown.run_if(
+                        in_state(self.run_state.clone())
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    crate::systems::modifiers::update_modifier_text,
+                )
+                    .run_if(in_state(MinesweeperState::Running)),
+            )
+            // Endless boards have no [crate::resources::board::Board], they have their own systems
+            .add_systems(
+                Update,
+                (
+                    crate::systems::create_endless_board::create_endless_board
+                        .run_if(in_state(MinesweeperState::NewGame))
+                        .run_if(in_state(self.run_state.clone())),
+                    crate::systems::endless_camera::endless_camera.run_if(
+                        in_state(MinesweeperState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    crate::systems::endless_input::endless_input.run_if(
+                        in_state(MinesweeperState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    crate::systems::input::board_cursor::<IVec2>
+                        .after(crate::systems::endless_input::endless_input),
+                    crate::systems::tile_feedback::show_endless_tile_feedback
+                        .after(crate::systems::endless_input::endless_input)
+                        .after(crate::systems::chunk_streaming::chunk_streaming),
+                    crate::systems::chunk_streaming::chunk_streaming
+                        .run_if(in_state(MinesweeperState::Running)),
+                    Self::endless_uncover_event_handler.run_if(in_state(MinesweeperState::Running)),
+                    crate::systems::endless_flag_event_handler::endless_flag_event_handler
+                        .run_if(in_state(MinesweeperState::Running).and_then(flags_allowed)),
+                    crate::systems::create_endless_board::update_score
+                        .run_if(in_state(MinesweeperState::Running)),
+                )
+                    .run_if(is_endless),
+            )
+            // Animations only change how the game looks, the board is already up to date
+            .add_systems(
+                Update,
+                (
+                    crate::systems::animations::animate_covers,
+                    crate::systems::animations::drop_flags,
+                    crate::systems::animations::explode,
+                    crate::systems::animations::move_particles,
+                    crate::systems::animations::shake_camera,
+                    Self::celebrate,
+                ),
+            )
+            // Sounds of the theme. Uncovering is heard before the tiles are uncovered, while it's known which were covered
+            .add_systems(
+                Update,
+                (
+                    crate::systems::audio::play_uncover_sound
+                        .after(crate::systems::input::input)
+                        .after(crate::systems::endless_input::endless_input)
+                        .before(Self::tile_uncover_event_handler),
+                    crate::systems::audio::play_flag_sound.run_if(flags_allowed),
+                    crate::systems::audio::play_explosion_sound,
+                    Self::play_game_over_sound,
+                    crate::systems::audio::play_music,
+                    crate::systems::audio::update_volume,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    crate::systems::theme_switcher::switch_theme.run_if(
+                        in_state(self.run_state.clone())
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    crate::systems::theme_switcher::apply_pending_theme,
+                ),
+            )
+            // Accessibility settings apply to the running game, the board systems pick up what concerns the tiles
+            .add_systems(
+                Update,
+                (
+                    crate::systems::accessibility::accessibility_keys.run_if(
+                        in_state(self.run_state.clone())
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    crate::systems::accessibility::apply_accessibility
+                        .after(crate::systems::accessibility::accessibility_keys)
+                        .run_if(resource_changed::<AccessibilityOptions>),
+                ),
             );
         bevy::log::info!("Plugin loaded.");
     }
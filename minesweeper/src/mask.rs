@@ -0,0 +1,211 @@
+use std::path::Path;
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::TextureFormat,
+        texture::{CompressedImageFormats, ImageSampler, ImageType},
+    },
+};
+
+use crate::components::coordinates::CoordinateU16;
+
+/// Masks that ship with the game. Every one of them is checked in the tests
+pub const BUNDLED_MASKS: [&str; 3] = [
+    include_str!("../masks/heart.txt"),
+    include_str!("../masks/donut.txt"),
+    include_str!("../masks/letter_m.txt"),
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum MaskError {
+    #[error("The mask has no cells")]
+    Empty,
+    #[error("Row {row} has {found} cells but the first row has {expected}")]
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Unknown character '{character}' in row {row}")]
+    UnknownCharacter { row: usize, character: char },
+    #[error("Couldn't read the mask file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Couldn't decode the mask image: {0}")]
+    Image(String),
+}
+
+/// Marks which cells of the board exist, so boards don't have to be rectangles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardMask {
+    width: u16,
+    height: u16,
+    /// Row 0 is the bottom row, like on the board
+    cells: Vec<bool>,
+}
+
+impl BoardMask {
+    /// Parses the mask text format. Every row of the board is one line where `#` is a cell and `.` a hole.
+    /// The first line is the top row and lines starting with `;` are comments.
+    ///
+    /// ```text
+    /// ; A tiny ring
+    /// ###
+    /// #.#
+    /// ###
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, MaskError> {
+        let mut rows: Vec<Vec<bool>> = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            let row = rows.len();
+            let cells = line
+                .chars()
+                .map(|character| match character {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(MaskError::UnknownCharacter { row, character }),
+                })
+                .collect::<Result<Vec<bool>, MaskError>>()?;
+
+            if let Some(first) = rows.first() {
+                if first.len() != cells.len() {
+                    return Err(MaskError::RowLength {
+                        row,
+                        expected: first.len(),
+                        found: cells.len(),
+                    });
+                }
+            }
+            rows.push(cells);
+        }
+
+        Self::from_rows(rows)
+    }
+
+    /// Every opaque pixel of the image is a cell, transparent pixels are holes
+    pub fn from_image(image: &Image) -> Result<Self, MaskError> {
+        let image = image
+            .convert(TextureFormat::Rgba8UnormSrgb)
+            .ok_or_else(|| MaskError::Image("Unsupported pixel format".to_string()))?;
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let rows = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| image.data[(y * width + x) * 4 + 3] >= 128)
+                    .collect()
+            })
+            .collect();
+
+        Self::from_rows(rows)
+    }
+
+    /// Loads a mask from a text file, or from an image if the file has an image extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, MaskError> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "png" | "jpg" | "jpeg" | "bmp" => {
+                let image = Image::from_buffer(
+                    &std::fs::read(path)?,
+                    ImageType::Extension(&extension),
+                    CompressedImageFormats::NONE,
+                    true,
+                    ImageSampler::Default,
+                    RenderAssetUsages::default(),
+                )
+                .map_err(|error| MaskError::Image(error.to_string()))?;
+                Self::from_image(&image)
+            }
+            _ => Self::parse(&std::fs::read_to_string(path)?),
+        }
+    }
+
+    /// All bundled masks. Panics if one of them doesn't parse, which the tests make sure never happens
+    pub fn bundled() -> Vec<Self> {
+        BUNDLED_MASKS
+            .iter()
+            .map(|text| Self::parse(text).expect("Bundled mask is invalid"))
+            .collect()
+    }
+
+    /// `rows` has the top row first
+    fn from_rows(mut rows: Vec<Vec<bool>>) -> Result<Self, MaskError> {
+        if !rows.iter().flatten().any(|cell| *cell) {
+            return Err(MaskError::Empty);
+        }
+
+        rows.reverse();
+        Ok(Self {
+            width: rows[0].len() as u16,
+            height: rows.len() as u16,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn contains(&self, coordinate: CoordinateU16) -> bool {
+        coordinate.x < self.width
+            && coordinate.y < self.height
+            && self.cells[coordinate.x as usize + coordinate.y as usize * self.width as usize]
+    }
+
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Number of cells that exist
+    pub fn cell_count(&self) -> usize {
+        self.cells.iter().filter(|cell| **cell).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_masks_parse() {
+        assert_eq!(BoardMask::bundled().len(), BUNDLED_MASKS.len());
+    }
+
+    #[test]
+    fn first_line_is_the_top_row() {
+        let mask = BoardMask::parse("#..\n###").unwrap();
+
+        assert_eq!(mask.size(), (3, 2));
+        assert_eq!(mask.cell_count(), 4);
+        assert!(mask.contains(CoordinateU16 { x: 2, y: 0, z: 0 }));
+        assert!(!mask.contains(CoordinateU16 { x: 2, y: 1, z: 0 }));
+        assert!(!mask.contains(CoordinateU16 { x: 3, y: 0, z: 0 }));
+    }
+
+    #[test]
+    fn invalid_masks_are_rejected() {
+        assert!(matches!(
+            BoardMask::parse("; nothing"),
+            Err(MaskError::Empty)
+        ));
+        assert!(matches!(BoardMask::parse("..\n.."), Err(MaskError::Empty)));
+        assert!(matches!(
+            BoardMask::parse("##\n#"),
+            Err(MaskError::RowLength { row: 1, .. })
+        ));
+        assert!(matches!(
+            BoardMask::parse("#x"),
+            Err(MaskError::UnknownCharacter {
+                row: 0,
+                character: 'x'
+            })
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{components::coordinates::CoordinateU16, topology::Topology};
+
+/// Which tiles count as neighbours for the numbers and for uncovering empty areas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Neighbourhood {
+    /// Every tile that touches the tile
+    #[default]
+    Standard,
+    /// Only the tiles that share an edge with the tile
+    Orthogonal,
+    /// The tiles a chess knight can jump to. Only exists on square boards
+    Knight,
+    /// Every tile up to two tiles away. Only exists on square boards
+    Radius2,
+}
+
+impl Neighbourhood {
+    /// Offsets to the neighbours of the tile at `coordinate`
+    pub fn offsets(&self, topology: Topology, coordinate: CoordinateU16) -> &'static [(i8, i8)] {
+        match self {
+            Self::Standard => topology.neighbour_offsets(coordinate),
+            Self::Orthogonal => topology.edge_neighbour_offsets(coordinate),
+            Self::Knight => &KNIGHT_NEIGHBOUR_COORDINATES,
+            Self::Radius2 => &RADIUS_2_NEIGHBOUR_COORDINATES,
+        }
+    }
+
+    /// Whether the neighbourhood exists on boards of a topology
+    pub fn supports(&self, topology: Topology) -> bool {
+        match self {
+            Self::Standard | Self::Orthogonal => true,
+            Self::Knight | Self::Radius2 => topology == Topology::Square,
+        }
+    }
+}
+
+const KNIGHT_NEIGHBOUR_COORDINATES: [(i8, i8); 8] = [
+    (-1, -2),
+    (1, -2),
+    (-2, -1),
+    (2, -1),
+    (-2, 1),
+    (2, 1),
+    (-1, 2),
+    (1, 2),
+];
+
+const RADIUS_2_NEIGHBOUR_COORDINATES: [(i8, i8); 24] = [
+    (-2, -2),
+    (-1, -2),
+    (0, -2),
+    (1, -2),
+    (2, -2),
+    (-2, -1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (2, -1),
+    (-2, 0),
+    (-1, 0),
+    (1, 0),
+    (2, 0),
+    (-2, 1),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (2, 1),
+    (-2, 2),
+    (-1, 2),
+    (0, 2),
+    (1, 2),
+    (2, 2),
+];
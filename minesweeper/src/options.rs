@@ -1,4 +1,16 @@
+use std::{path::Path, time::Duration};
+
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mask::BoardMask,
+    neighbourhood::Neighbourhood,
+    palette::{NumberColors, TilePalette},
+    theme::{BUNDLED_THEMES, HIGH_CONTRAST_THEME},
+    topology::Topology,
+    win_condition::WinCondition,
+};
 
 /// Determines wether the tiles should be fixed (don't change with window scaling) or adaptive (change with window resizing but only above a minimum and below a maximum)
 #[derive(Debug, Clone, Resource)]
@@ -31,6 +43,146 @@ pub struct BoardOptions {
     pub tile_padding: f32,
     /// Does the board generate a safe place to start
     pub safe_start: bool,
+    /// Shape and arrangement of the tiles
+    pub topology: Topology,
+    /// Tiles that count for the numbers and get uncovered around empty tiles
+    pub neighbourhood: Neighbourhood,
+    /// Tiles on opposite edges are neighbours. Hexagon boards need an even height and triangle boards an even
+    /// width and height to wrap cleanly, boards that don't are played without wrapping
+    pub wrap: bool,
+    /// Cells that exist on the board. Overrides the map size
+    pub mask: Option<BoardMask>,
+    /// Number of layers stacked on top of each other. Only one layer is shown at a time
+    pub depth: u16,
+    /// Most bombs a single tile can hold. Tiles with more than one bomb get more than one flag
+    pub max_bombs_per_tile: u8,
+    /// Play on an endless board instead. The map size, bomb count and shape options don't apply to it
+    pub endless: Option<EndlessOptions>,
+    /// Optional rules that make the game harder or more forgiving
+    pub modifiers: RuleModifiers,
+    /// What has to be done to win
+    pub win_condition: WinCondition,
+    /// Path of the theme file inside the assets folder. Themes can be switched while playing with T
+    pub theme: String,
+    /// How the tiles are drawn. Doesn't apply to endless boards
+    pub rendering: BoardRendering,
+    /// Colours of [BoardRendering::Shapes] tiles
+    pub palette: TilePalette,
+    /// How changes of the board are animated. [Animations::none] turns all of them off
+    pub animations: Animations,
+}
+
+/// How the tiles of a board are drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BoardRendering {
+    /// Every tile is a sprite with a cover sprite on top
+    #[default]
+    Sprites,
+    /// Every layer is split into squares of `size` tiles that are drawn as one mesh each.
+    /// Huge boards only need a few entities this way
+    Chunks { size: u16 },
+    /// Tiles, numbers, bombs and flags are drawn with meshes and text in the colours of the
+    /// [BoardOptions::palette] instead of textures. They stay sharp at every tile size
+    Shapes,
+}
+
+/// Animations of the board. Covers and flags of chunked and endless boards aren't animated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Animations {
+    /// How the cover of an uncovered tile disappears
+    pub uncover: UncoverAnimation,
+    /// Length of the cover and flag animations
+    pub duration: Duration,
+    /// Delay per tile of distance from the clicked tile, so empty areas are uncovered in a ripple
+    pub ripple: Duration,
+    /// Flags drop onto their tile
+    pub flag_drop: bool,
+    /// Number of particles a hit bomb explodes into
+    pub explosion_particles: u16,
+    /// How far the screen shakes when a bomb is hit, in tiles
+    pub screen_shake: f32,
+    /// Confetti rains down when the game is won
+    pub celebration: bool,
+}
+
+/// How the cover of an uncovered tile disappears
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UncoverAnimation {
+    /// The cover is removed right away
+    None,
+    #[default]
+    Fade,
+    /// The cover turns around until only its edge can be seen
+    Flip,
+}
+
+/// Volumes of the theme's sounds from 0 to 1. Changes are heard right away
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct AudioOptions {
+    /// Volume of everything
+    pub master: f32,
+    /// Volume of the clicks, flags, explosions and the sounds at the end of a game
+    pub effects: f32,
+    /// Volume of the background music
+    pub music: f32,
+    /// Nothing can be heard while the window isn't focused
+    pub mute_unfocused: bool,
+}
+
+/// Settings that make the game easier to see and play. They are saved by the game and apply to the running game
+/// right away, on top of the [BoardOptions]
+#[derive(Debug, Clone, Copy, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccessibilityOptions {
+    /// Colours of the numbers. Boards of sprites are drawn as [BoardRendering::Shapes] while they aren't the ones of
+    /// the palette, see [Self::rendering]
+    pub number_colors: NumberColors,
+    /// Plays with the bundled high contrast theme instead of the one of the [BoardOptions]
+    pub high_contrast: bool,
+    /// Numbers are ringed by as many dots, so they can be told apart without their colour. Boards of sprites are
+    /// drawn as [BoardRendering::Shapes] while they are on
+    pub number_patterns: bool,
+    /// Tiles are never smaller than this, even if the board doesn't fit into the window anymore. Endless boards
+    /// only pick it up when they are created
+    pub min_tile_size: f32,
+    /// Leaves out the animations that move things around. Covers still fade
+    pub reduced_motion: bool,
+    /// Size of the text and everything else on top of the board, 1 is the normal size
+    pub ui_scale: f32,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum SettingsError {
+    #[error("Couldn't access the settings file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Couldn't read the settings: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Couldn't write the settings: {0}")]
+    Write(#[from] ron::Error),
+}
+
+/// Rules on top of the normal minesweeper rules. The default changes nothing
+#[derive(Debug, Clone, Default)]
+pub struct RuleModifiers {
+    /// The game is lost when the time runs out
+    pub time_limit: Option<Duration>,
+    /// Number of bomb hits that are survived. Every hit bomb stays uncovered
+    pub lives: u8,
+    /// Flags can't be placed
+    pub no_flags: bool,
+}
+
+/// Options of endless boards, which are generated in chunks while the camera moves around
+#[derive(Debug, Clone)]
+pub struct EndlessOptions {
+    /// Seed of the bombs. A random seed is picked if there is none
+    pub seed: Option<u64>,
+    /// Chance of a tile being a bomb, from 0 to 1
+    pub density: f64,
+    /// Width and height of a chunk in tiles
+    pub chunk_size: u16,
+    /// Number of chunks that stay loaded around the ones in view
+    pub view_distance: u16,
 }
 
 impl Default for TileSize {
@@ -42,6 +194,19 @@ impl Default for TileSize {
     }
 }
 
+impl TileSize {
+    /// The same size, but tiles never get smaller than `min`
+    pub fn at_least(&self, min: f32) -> Self {
+        match *self {
+            Self::Fixed(v) => Self::Fixed(v.max(min)),
+            Self::Adaptive { min: low, max } => Self::Adaptive {
+                min: low.max(min),
+                max: max.max(min),
+            },
+        }
+    }
+}
+
 impl Default for BoardPosition {
     fn default() -> Self {
         Self::Centered {
@@ -59,6 +224,329 @@ impl Default for BoardOptions {
             tile_size: Default::default(),
             tile_padding: 0.,
             safe_start: false,
+            topology: Default::default(),
+            neighbourhood: Default::default(),
+            wrap: false,
+            mask: None,
+            depth: 1,
+            max_bombs_per_tile: 1,
+            endless: None,
+            modifiers: Default::default(),
+            win_condition: Default::default(),
+            theme: BUNDLED_THEMES[0].to_string(),
+            rendering: Default::default(),
+            palette: Default::default(),
+            animations: Default::default(),
+        }
+    }
+}
+
+impl BoardOptions {
+    /// Win condition that is actually used. Games without flags can't be won by flagging, they are classic games
+    pub fn win_condition(&self) -> WinCondition {
+        match self.win_condition.supports(&self.modifiers) {
+            true => self.win_condition.clone(),
+            false => WinCondition::Classic,
+        }
+    }
+
+    /// Shape of the tiles that are actually used. Endless boards always have square tiles
+    pub fn tile_topology(&self) -> Topology {
+        match self.endless {
+            Some(_) => Topology::Square,
+            None => self.topology,
+        }
+    }
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Self {
+            uncover: Default::default(),
+            duration: Duration::from_millis(200),
+            ripple: Duration::from_millis(25),
+            flag_drop: true,
+            explosion_particles: 40,
+            screen_shake: 0.3,
+            celebration: true,
+        }
+    }
+}
+
+impl Animations {
+    /// Everything happens right away, for speedruns
+    pub fn none() -> Self {
+        Self {
+            uncover: UncoverAnimation::None,
+            duration: Duration::ZERO,
+            ripple: Duration::ZERO,
+            flag_drop: false,
+            explosion_particles: 0,
+            screen_shake: 0.,
+            celebration: false,
+        }
+    }
+
+    /// How far an animation that started at `start` is at `now`, from 0 to 1
+    pub fn progress(&self, start: Duration, now: Duration) -> f32 {
+        if self.duration.is_zero() {
+            return 1.;
+        }
+        (now.saturating_sub(start).as_secs_f32() / self.duration.as_secs_f32()).min(1.)
+    }
+}
+
+impl Default for AudioOptions {
+    fn default() -> Self {
+        Self {
+            master: 1.,
+            effects: 1.,
+            music: 0.5,
+            mute_unfocused: true,
+        }
+    }
+}
+
+impl AudioOptions {
+    pub fn effects_volume(&self, focused: bool) -> f32 {
+        self.volume(self.effects, focused)
+    }
+
+    pub fn music_volume(&self, focused: bool) -> f32 {
+        self.volume(self.music, focused)
+    }
+
+    fn volume(&self, volume: f32, focused: bool) -> f32 {
+        match !focused && self.mute_unfocused {
+            true => 0.,
+            false => self.master * volume,
+        }
+    }
+}
+
+impl Default for AccessibilityOptions {
+    fn default() -> Self {
+        Self {
+            number_colors: Default::default(),
+            high_contrast: false,
+            number_patterns: false,
+            min_tile_size: 0.,
+            reduced_motion: false,
+            ui_scale: 1.,
+        }
+    }
+}
+
+impl AccessibilityOptions {
+    /// Reads settings that were saved with [Self::save]. Settings missing from the file keep their default
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SettingsError> {
+        Ok(ron::de::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Writes the settings to a file. The folders of the path are created if they don't exist yet
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), SettingsError> {
+        let path = path.as_ref();
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        if let Some(folder) = path.parent() {
+            std::fs::create_dir_all(folder)?;
+        }
+        Ok(std::fs::write(path, text)?)
+    }
+
+    /// Path of the theme to play with instead of `theme`
+    pub fn theme<'a>(&self, theme: &'a str) -> &'a str {
+        match self.high_contrast {
+            true => HIGH_CONTRAST_THEME,
+            false => theme,
+        }
+    }
+
+    pub fn tile_size(&self, tile_size: &TileSize) -> TileSize {
+        tile_size.at_least(self.min_tile_size)
+    }
+
+    pub fn palette(&self, palette: &TilePalette) -> TilePalette {
+        palette.with_number_colors(self.number_colors)
+    }
+
+    /// Number colours and patterns are only drawn on [BoardRendering::Shapes], so boards of sprites become shapes
+    /// while they are used. Chunked boards stay chunked, they are for boards that are too big for a shape per tile
+    pub fn rendering(&self, rendering: BoardRendering) -> BoardRendering {
+        match rendering {
+            BoardRendering::Sprites
+                if self.number_colors != NumberColors::Palette || self.number_patterns =>
+            {
+                BoardRendering::Shapes
+            }
+            rendering => rendering,
+        }
+    }
+
+    /// The animations that are left with reduced motion. Flipping covers fade instead
+    pub fn animations(&self, animations: Animations) -> Animations {
+        if !self.reduced_motion {
+            return animations;
+        }
+        Animations {
+            uncover: match animations.uncover {
+                UncoverAnimation::Flip => UncoverAnimation::Fade,
+                uncover => uncover,
+            },
+            ripple: Duration::ZERO,
+            flag_drop: false,
+            explosion_particles: 0,
+            screen_shake: 0.,
+            celebration: false,
+            ..animations
+        }
+    }
+}
+
+impl Default for EndlessOptions {
+    fn default() -> Self {
+        Self {
+            seed: None,
+            density: 0.15,
+            chunk_size: 16,
+            view_distance: 1,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn animations_without_duration_are_over_right_away() {
+        let start = Duration::from_secs(3);
+
+        assert_eq!(Animations::none().progress(start, start), 1.);
+        assert_eq!(Animations::default().progress(start, Duration::ZERO), 0.);
+        assert_eq!(
+            Animations::default().progress(start, start + Duration::from_millis(100)),
+            0.5
+        );
+    }
+
+    #[test]
+    fn unfocused_windows_are_muted() {
+        let options = AudioOptions {
+            master: 0.5,
+            ..Default::default()
+        };
+
+        assert_eq!(options.effects_volume(true), 0.5);
+        assert_eq!(options.music_volume(true), 0.25);
+        assert_eq!(options.effects_volume(false), 0.);
+        assert_eq!(
+            AudioOptions {
+                mute_unfocused: false,
+                ..options
+            }
+            .music_volume(false),
+            0.25
+        );
+    }
+
+    #[test]
+    fn tiles_are_never_smaller_than_the_minimum() {
+        assert!(matches!(TileSize::Fixed(20.).at_least(30.), TileSize::Fixed(v) if v == 30.));
+        assert!(matches!(TileSize::Fixed(40.).at_least(30.), TileSize::Fixed(v) if v == 40.));
+        assert!(matches!(
+            TileSize::Adaptive { min: 10., max: 50. }.at_least(30.),
+            TileSize::Adaptive { min, max } if min == 30. && max == 50.
+        ));
+        assert!(matches!(
+            TileSize::Adaptive { min: 10., max: 50. }.at_least(60.),
+            TileSize::Adaptive { min, max } if min == 60. && max == 60.
+        ));
+    }
+
+    #[test]
+    fn sprites_become_shapes_for_number_colours_and_patterns() {
+        let chunks = BoardRendering::Chunks { size: 16 };
+        let default = AccessibilityOptions::default();
+        let patterns = AccessibilityOptions {
+            number_patterns: true,
+            ..Default::default()
+        };
+        let colours = AccessibilityOptions {
+            number_colors: NumberColors::ColourBlind,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            default.rendering(BoardRendering::Sprites),
+            BoardRendering::Sprites
+        );
+        assert_eq!(
+            patterns.rendering(BoardRendering::Sprites),
+            BoardRendering::Shapes
+        );
+        assert_eq!(
+            colours.rendering(BoardRendering::Sprites),
+            BoardRendering::Shapes
+        );
+        assert_eq!(colours.rendering(chunks), chunks);
+        assert_eq!(
+            default.rendering(BoardRendering::Shapes),
+            BoardRendering::Shapes
+        );
+    }
+
+    #[test]
+    fn reduced_motion_only_keeps_the_covers_fading() {
+        let options = AccessibilityOptions {
+            reduced_motion: true,
+            ..Default::default()
+        };
+        let animations = options.animations(Animations {
+            uncover: UncoverAnimation::Flip,
+            ..Default::default()
+        });
+
+        assert_eq!(animations.uncover, UncoverAnimation::Fade);
+        assert_eq!(animations.duration, Animations::default().duration);
+        assert_eq!(animations.ripple, Duration::ZERO);
+        assert!(!animations.flag_drop && !animations.celebration);
+        assert_eq!(animations.explosion_particles, 0);
+        assert_eq!(
+            AccessibilityOptions::default().animations(Animations::default()),
+            Animations::default()
+        );
+    }
+
+    #[test]
+    fn saved_accessibility_options_are_read_back() {
+        let options = AccessibilityOptions {
+            number_colors: NumberColors::ColourBlind,
+            number_patterns: true,
+            ui_scale: 1.5,
+            ..Default::default()
+        };
+        // Tests that run at the same time don't share the folder, which is created when saving
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let folder = std::env::temp_dir().join(format!(
+            "minesweeper_accessibility_{}_{}",
+            std::process::id(),
+            nanos
+        ));
+        let path = folder.join("accessibility.ron");
+        options.save(&path).unwrap();
+
+        assert_eq!(AccessibilityOptions::load(&path).unwrap(), options);
+        std::fs::remove_dir_all(folder).unwrap();
+        // Older files don't have every setting
+        assert_eq!(
+            ron::de::from_str::<AccessibilityOptions>("(reduced_motion: true)").unwrap(),
+            AccessibilityOptions {
+                reduced_motion: true,
+                ..Default::default()
+            }
+        );
+    }
+}
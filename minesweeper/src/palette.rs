@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Colours of tiles that are drawn with [crate::options::BoardRendering::Shapes]. The tile and cover colours are
+/// tinted by the theme like the textures are
+#[derive(Debug, Clone, PartialEq)]
+pub struct TilePalette {
+    /// Colours of the numbers from 1 up. They repeat for numbers above their count
+    pub numbers: Vec<Color>,
+    pub tile: Color,
+    pub cover: Color,
+    pub bomb: Color,
+    pub flag: Color,
+    /// Flag poles and the number of flags on covers with more than one
+    pub pole: Color,
+}
+
+/// Colours of the numbers, for players who can't tell the ones of the palette apart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberColors {
+    /// The numbers of the [TilePalette]
+    #[default]
+    Palette,
+    /// The Okabe-Ito colours, which stay apart with every kind of colour blindness
+    ColourBlind,
+    /// Dark colours that stand out from light tiles
+    HighContrast,
+}
+
+impl NumberColors {
+    /// The next kind of colours, after the last one comes the first again
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Palette => Self::ColourBlind,
+            Self::ColourBlind => Self::HighContrast,
+            Self::HighContrast => Self::Palette,
+        }
+    }
+
+    /// Colours of the numbers from 1 up, [NumberColors::Palette] has none of its own
+    pub fn colors(&self) -> Option<Vec<Color>> {
+        match self {
+            Self::Palette => None,
+            Self::ColourBlind => Some(vec![
+                Color::rgb_u8(0, 114, 178),
+                Color::rgb_u8(0, 158, 115),
+                Color::rgb_u8(213, 94, 0),
+                Color::rgb_u8(204, 121, 167),
+                Color::rgb_u8(230, 159, 0),
+                Color::rgb_u8(86, 180, 233),
+                Color::rgb_u8(0, 0, 0),
+                Color::rgb_u8(240, 228, 66),
+            ]),
+            Self::HighContrast => Some(vec![
+                Color::rgb_u8(0, 0, 205),
+                Color::rgb_u8(0, 100, 0),
+                Color::rgb_u8(190, 0, 0),
+                Color::rgb_u8(80, 0, 140),
+                Color::rgb_u8(120, 50, 0),
+                Color::rgb_u8(0, 90, 100),
+                Color::rgb_u8(0, 0, 0),
+                Color::rgb_u8(70, 70, 70),
+            ]),
+        }
+    }
+}
+
+impl TilePalette {
+    /// The colours of the classic game
+    pub fn classic() -> Self {
+        Self {
+            numbers: vec![
+                Color::rgb_u8(0, 0, 255),
+                Color::rgb_u8(0, 128, 0),
+                Color::rgb_u8(255, 0, 0),
+                Color::rgb_u8(0, 0, 128),
+                Color::rgb_u8(128, 0, 0),
+                Color::rgb_u8(0, 128, 128),
+                Color::rgb_u8(0, 0, 0),
+                Color::rgb_u8(128, 128, 128),
+            ],
+            tile: Color::rgb_u8(160, 160, 160),
+            cover: Color::rgb_u8(198, 198, 198),
+            bomb: Color::BLACK,
+            flag: Color::rgb_u8(224, 32, 32),
+            pole: Color::BLACK,
+        }
+    }
+
+    /// The same palette with other number colours
+    pub fn with_number_colors(&self, colors: NumberColors) -> Self {
+        Self {
+            numbers: colors.colors().unwrap_or_else(|| self.numbers.clone()),
+            ..self.clone()
+        }
+    }
+
+    /// Colour of a tile with `count` bomb neighbours
+    pub fn number(&self, count: u8) -> Color {
+        match (count, self.numbers.len()) {
+            (0, _) | (_, 0) => Color::NONE,
+            (count, len) => self.numbers[(count as usize - 1) % len],
+        }
+    }
+}
+
+impl Default for TilePalette {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_colours_repeat() {
+        let palette = TilePalette::classic();
+
+        assert_eq!(palette.number(1), Color::rgb_u8(0, 0, 255));
+        assert_eq!(palette.number(9), palette.number(1));
+        assert_eq!(palette.number(74), palette.number(2));
+        assert_eq!(palette.number(0), Color::NONE);
+    }
+
+    #[test]
+    fn number_colours_can_be_told_apart() {
+        let mut colors = NumberColors::default();
+        loop {
+            let numbers = TilePalette::classic().with_number_colors(colors).numbers;
+            for (index, color) in numbers.iter().enumerate() {
+                assert!(!numbers[index + 1..].contains(color), "{:?}", colors);
+            }
+
+            colors = colors.next();
+            if colors == NumberColors::default() {
+                break;
+            }
+        }
+        assert_eq!(
+            TilePalette::classic().with_number_colors(NumberColors::Palette),
+            TilePalette::classic()
+        );
+    }
+}
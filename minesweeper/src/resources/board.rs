@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use crate::components::coordinates::CoordinateU16;
+use crate::{atlas::TileAtlas, components::coordinates::CoordinateU16};
 
-use super::{bounds::Bounds2, tile_map::TileMap};
+use super::{bounds::Bounds2, tile::Tile, tile_map::TileMap};
 use bevy::prelude::*;
 
 #[derive(Debug, Clone, Resource)]
@@ -11,40 +11,176 @@ pub struct Board {
     pub bounds: Bounds2,
     pub tile_size: f32,
     pub covered: HashMap<CoordinateU16, Entity>,
-    pub flagged: HashMap<CoordinateU16, (Entity, Entity, bool)>,
+    /// Tile entity, cover entity and the number of flags on the tile
+    pub flagged: HashMap<CoordinateU16, (Entity, Entity, u8)>,
     pub flag_count: u16,
+    /// Bombs on tiles that were uncovered and survived with a life. They count as flagged
+    pub exploded: u16,
+    /// The layer that is shown on layered boards
+    pub layer: u16,
 }
 
 impl Board {
     pub fn cursor_position(&self, window: &Window, cursor_position: Vec2) -> Option<CoordinateU16> {
-        // Window to world space
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = cursor_position - window_size / 2.;
+        // Window to world space. Window y grows downwards
+        let position = Vec2::new(
+            cursor_position.x - window.width() / 2.,
+            window.height() / 2. - cursor_position.y,
+        );
 
         if !self.bounds.in_bounds(position) {
             return None;
         }
 
         // World space to board space
-        let coordinates = position - self.bounds.origin;
-        Some(CoordinateU16 {
-            x: (coordinates.x / self.tile_size) as u16,
-            // The position 1:1 is top left but the world origin is bottom left, so we have to flip position logic upside down
-            y: self.tile_map.height() - (coordinates.y / self.tile_size) as u16 - 1,
-        })
+        self.tile_map
+            .topology()
+            .tile_at(
+                position - self.bounds.origin,
+                (*self.tile_map.width(), *self.tile_map.height()),
+                self.tile_size,
+            )
+            .map(|coordinate| CoordinateU16 {
+                z: self.layer,
+                ..coordinate
+            })
+            .filter(|coordinate| !self.tile_map[*coordinate].is_void())
+    }
+
+    /// Number of uncovered tiles without bombs, which is the score of score attack games
+    pub fn score(&self) -> usize {
+        self.tile_map
+            .iter()
+            .enumerate()
+            .filter(|(index, tile)| {
+                **tile == Tile::Empty
+                    && !self.covered.contains_key(&self.tile_map.coordinate(*index))
+            })
+            .count()
+    }
+
+    /// Atlas index of what a tile shows. Covered tiles show their flags
+    pub fn atlas_index(&self, coordinate: CoordinateU16) -> usize {
+        if self.covered.contains_key(&coordinate) {
+            let flags = self
+                .flagged
+                .get(&coordinate)
+                .map_or(0, |(_, _, flags)| *flags);
+            return TileAtlas::flags(flags);
+        }
+        match self.tile_map[coordinate] {
+            Tile::Bomb(_) => TileAtlas::BOMB,
+            _ => TileAtlas::number(self.tile_map.bomb_count_at(coordinate)),
+        }
     }
 
     pub fn try_uncover(&mut self, coord: &CoordinateU16) -> Option<Entity> {
         self.covered.remove(coord)
     }
 
-    pub fn try_toggle_flag(&mut self, coord: &CoordinateU16) -> Option<(Entity, Entity, bool)> {
+    /// Adds a flag to a tile. Once the tile has as many flags as a tile can hold bombs, the flags are removed
+    pub fn try_add_flag(&mut self, coord: &CoordinateU16) -> Option<(Entity, Entity, u8)> {
+        let max_flags = *self.tile_map.max_bombs_per_tile();
         match self.flagged.get_mut(coord) {
-            Some((tile, cover, bool)) => {
-                *bool = !*bool;
-                Some((*tile, *cover, *bool))
+            Some((tile, cover, flags)) => {
+                *flags = (*flags + 1) % (max_flags + 1);
+                Some((*tile, *cover, *flags))
             }
             None => None,
         }
     }
+
+    /// Covered tiles around an uncovered number that has as many flags around it as bombs. Chording on the number
+    /// uncovers them. Hit bombs count as flagged
+    pub fn chord_targets(&self, coordinate: CoordinateU16) -> Vec<CoordinateU16> {
+        if self.covered.contains_key(&coordinate) || self.tile_map[coordinate].is_bomb() {
+            return Vec::new();
+        }
+        let flags = |coordinate: &CoordinateU16| {
+            self.flagged
+                .get(coordinate)
+                .map_or(0, |(_, _, flags)| *flags)
+        };
+        let neighbours: Vec<CoordinateU16> =
+            self.tile_map.neighbour_coordinates(coordinate).collect();
+        let flagged: u16 = neighbours
+            .iter()
+            .map(|neighbour| match self.covered.contains_key(neighbour) {
+                true => flags(neighbour) as u16,
+                false => self.tile_map[*neighbour].bomb_count() as u16,
+            })
+            .sum();
+        if flagged != self.tile_map.bomb_count_at(coordinate) as u16 {
+            return Vec::new();
+        }
+        neighbours
+            .into_iter()
+            .filter(|neighbour| self.covered.contains_key(neighbour) && flags(neighbour) == 0)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl Board {
+    /// A board of `tile_map` for tests. Every tile is covered and has no entities, every tile is one unit big
+    pub(crate) fn covered(tile_map: TileMap) -> Self {
+        let (width, height, depth) = (*tile_map.width(), *tile_map.height(), *tile_map.depth());
+        let coordinates: Vec<CoordinateU16> = (0..depth)
+            .flat_map(|z| {
+                (0..height).flat_map(move |y| (0..width).map(move |x| CoordinateU16 { x, y, z }))
+            })
+            .collect();
+        Self {
+            bounds: Bounds2 {
+                origin: Vec2::ZERO,
+                size: Vec2::new(width as f32, height as f32),
+            },
+            tile_size: 1.,
+            covered: coordinates
+                .iter()
+                .map(|coordinate| (*coordinate, Entity::PLACEHOLDER))
+                .collect(),
+            flagged: coordinates
+                .iter()
+                .map(|coordinate| (*coordinate, (Entity::PLACEHOLDER, Entity::PLACEHOLDER, 0)))
+                .collect(),
+            flag_count: 0,
+            exploded: 0,
+            layer: 0,
+            tile_map,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::topology::Topology;
+
+    use super::*;
+
+    #[test]
+    fn chording_needs_every_bomb_flagged() {
+        let mut tile_map = TileMap::empty(2, 2, Topology::Square);
+        tile_map.spread_bombs(1, 1);
+        let coordinates: Vec<CoordinateU16> =
+            (0..4).map(|index| tile_map.coordinate(index)).collect();
+        let bomb = *coordinates
+            .iter()
+            .find(|coordinate| tile_map[**coordinate].is_bomb())
+            .unwrap();
+        let number = *coordinates
+            .iter()
+            .find(|coordinate| **coordinate != bomb)
+            .unwrap();
+        let mut board = Board::covered(tile_map);
+        assert!(board.chord_targets(number).is_empty());
+
+        board.try_uncover(&number);
+        assert!(board.chord_targets(number).is_empty());
+
+        board.try_add_flag(&bomb);
+        let targets = board.chord_targets(number);
+        assert_eq!(targets.len(), 2);
+        assert!(!targets.contains(&bomb) && !targets.contains(&number));
+    }
 }
@@ -10,7 +10,7 @@ impl Bounds2 {
     pub fn in_bounds(&self, coords: Vec2) -> bool {
         coords.x >= self.origin.x
             && coords.y >= self.origin.y
-            && coords.x <= self.origin.x + self.size.y
+            && coords.x <= self.origin.x + self.size.x
             && coords.y <= self.origin.y + self.size.y
     }
 }
@@ -0,0 +1,288 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::atlas::TileAtlas;
+
+/// An endless board that is generated in chunks from a seed when they are needed.
+/// Positions can be negative, the first tile uncovered is at 0:0
+#[derive(Debug, Clone, Resource)]
+pub struct EndlessBoard {
+    seed: u64,
+    density: f64,
+    chunk_size: i32,
+    pub tile_size: f32,
+    /// Bombs of every chunk that was generated so far
+    chunks: HashMap<IVec2, Vec<bool>>,
+    /// Chunks that have entities right now
+    pub loaded: HashMap<IVec2, Entity>,
+    /// Tile entities of the loaded chunks
+    pub tiles: HashMap<IVec2, Entity>,
+    pub revealed: HashSet<IVec2>,
+    pub flagged: HashSet<IVec2>,
+    /// Bombs that were hit and survived with a life
+    pub exploded: HashSet<IVec2>,
+    /// Tiles that get uncovered once their chunk is loaded. Keeps uncovering big empty areas from running away
+    pending: HashSet<IVec2>,
+}
+
+/// What happened when a tile was uncovered
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Uncovered {
+    Bomb,
+    /// Every tile that got revealed
+    Tiles(Vec<IVec2>),
+}
+
+impl EndlessBoard {
+    pub fn new(seed: u64, density: f64, chunk_size: u16, tile_size: f32) -> Self {
+        Self {
+            seed,
+            density,
+            chunk_size: chunk_size.max(1) as i32,
+            tile_size,
+            chunks: HashMap::new(),
+            loaded: HashMap::new(),
+            tiles: HashMap::new(),
+            revealed: HashSet::new(),
+            flagged: HashSet::new(),
+            exploded: HashSet::new(),
+            pending: HashSet::new(),
+        }
+    }
+
+    pub fn chunk_size(&self) -> i32 {
+        self.chunk_size
+    }
+
+    /// Chunk that contains a tile
+    pub fn chunk_of(&self, position: IVec2) -> IVec2 {
+        position.div_euclid(IVec2::splat(self.chunk_size))
+    }
+
+    /// Every tile of a chunk
+    pub fn chunk_tiles(&self, chunk: IVec2) -> impl Iterator<Item = IVec2> {
+        let (size, origin) = (self.chunk_size, chunk * self.chunk_size);
+        (0..size).flat_map(move |y| (0..size).map(move |x| origin + IVec2::new(x, y)))
+    }
+
+    pub fn is_bomb(&mut self, position: IVec2) -> bool {
+        let chunk = self.chunk_of(position);
+        let local = position - chunk * self.chunk_size;
+        let index = (local.x + local.y * self.chunk_size) as usize;
+        self.generate(chunk)[index]
+    }
+
+    pub fn bomb_count_at(&mut self, position: IVec2) -> u8 {
+        neighbours(position)
+            .filter(|neighbour| self.is_bomb(*neighbour))
+            .count() as u8
+    }
+
+    /// Uncovers a tile and all tiles around it if it has no bomb neighbours.
+    /// Tiles in chunks that aren't loaded are uncovered once the chunk is loaded
+    pub fn uncover(&mut self, position: IVec2) -> Uncovered {
+        if self.revealed.contains(&position)
+            || self.flagged.contains(&position)
+            || self.exploded.contains(&position)
+        {
+            return Uncovered::Tiles(Vec::new());
+        }
+        if self.is_bomb(position) {
+            self.exploded.insert(position);
+            return Uncovered::Bomb;
+        }
+        Uncovered::Tiles(self.flood(position))
+    }
+
+    /// Marks a chunk as loaded and uncovers the tiles that were waiting for it
+    pub fn load_chunk(&mut self, chunk: IVec2, entity: Entity) -> Vec<IVec2> {
+        self.loaded.insert(chunk, entity);
+        let waiting: Vec<IVec2> = self
+            .pending
+            .iter()
+            .copied()
+            .filter(|position| self.chunk_of(*position) == chunk)
+            .collect();
+
+        let mut revealed = Vec::new();
+        for position in waiting {
+            self.pending.remove(&position);
+            revealed.extend(self.flood(position));
+        }
+        revealed
+    }
+
+    /// Forgets the entities of a chunk. Its bombs are forgotten as well, they are generated again from the seed
+    pub fn unload_chunk(&mut self, chunk: IVec2) -> Option<Entity> {
+        let positions: Vec<IVec2> = self.chunk_tiles(chunk).collect();
+        for position in positions {
+            self.tiles.remove(&position);
+        }
+        self.chunks.remove(&chunk);
+        self.loaded.remove(&chunk)
+    }
+
+    /// Atlas index of the texture that shows the current state of a tile
+    pub fn atlas_index(&mut self, position: IVec2) -> usize {
+        if self.revealed.contains(&position) {
+            return TileAtlas::number(self.bomb_count_at(position));
+        }
+        if self.exploded.contains(&position) {
+            return TileAtlas::BOMB;
+        }
+        match self.flagged.contains(&position) {
+            true => TileAtlas::flags(1),
+            false => TileAtlas::BASE,
+        }
+    }
+
+    /// Tiles that were neither uncovered nor hit. Flagged tiles are covered
+    pub fn is_covered(&self, position: IVec2) -> bool {
+        !self.revealed.contains(&position) && !self.exploded.contains(&position)
+    }
+
+    /// Covered tiles around an uncovered number that has as many flags around it as bombs. Chording on the number
+    /// uncovers them. Hit bombs count as flagged
+    pub fn chord_targets(&mut self, position: IVec2) -> Vec<IVec2> {
+        if !self.revealed.contains(&position) {
+            return Vec::new();
+        }
+        let flagged = neighbours(position)
+            .filter(|neighbour| {
+                self.flagged.contains(neighbour) || self.exploded.contains(neighbour)
+            })
+            .count();
+        if flagged != self.bomb_count_at(position) as usize {
+            return Vec::new();
+        }
+        neighbours(position)
+            .filter(|neighbour| self.is_covered(*neighbour) && !self.flagged.contains(neighbour))
+            .collect()
+    }
+
+    /// Number of uncovered tiles, which is the score
+    pub fn score(&self) -> usize {
+        self.revealed.len()
+    }
+
+    fn flood(&mut self, start: IVec2) -> Vec<IVec2> {
+        let mut revealed = Vec::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(position) = queue.pop_front() {
+            if self.revealed.contains(&position) || self.flagged.contains(&position) {
+                continue;
+            }
+            if !self.loaded.contains_key(&self.chunk_of(position)) {
+                self.pending.insert(position);
+                continue;
+            }
+            self.revealed.insert(position);
+            revealed.push(position);
+            if self.bomb_count_at(position) == 0 {
+                queue.extend(neighbours(position));
+            }
+        }
+        revealed
+    }
+
+    /// Bombs of a chunk, generated the first time they are needed
+    fn generate(&mut self, chunk: IVec2) -> &Vec<bool> {
+        let (seed, density, size) = (self.seed, self.density, self.chunk_size);
+        self.chunks.entry(chunk).or_insert_with(|| {
+            let mut rng = StdRng::seed_from_u64(chunk_seed(seed, chunk));
+            (0..size * size)
+                .map(|index| {
+                    let position = chunk * size + IVec2::new(index % size, index / size);
+                    // The start is always empty, so the first click opens an area
+                    let bomb = rng.gen_bool(density.clamp(0., 1.));
+                    bomb && (position.x.abs() > 1 || position.y.abs() > 1)
+                })
+                .collect()
+        })
+    }
+}
+
+/// The eight tiles around a tile
+pub fn neighbours(position: IVec2) -> impl Iterator<Item = IVec2> {
+    (-1..=1)
+        .flat_map(|y| (-1..=1).map(move |x| IVec2::new(x, y)))
+        .filter(|offset| *offset != IVec2::ZERO)
+        .map(move |offset| position + offset)
+}
+
+/// Mixes the seed with the chunk coordinates, so every chunk gets different bombs
+fn chunk_seed(seed: u64, chunk: IVec2) -> u64 {
+    let mut value = seed
+        ^ (chunk.x as u32 as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (chunk.y as u32 as u64)
+            .wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+            .rotate_left(32);
+    // SplitMix64 finalizer
+    value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    value ^ (value >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_are_the_same_for_a_seed() {
+        let mut first = EndlessBoard::new(7, 0.2, 8, 10.);
+        let mut second = EndlessBoard::new(7, 0.2, 8, 10.);
+        for position in [IVec2::new(-20, 3), IVec2::new(100, -55), IVec2::new(5, 5)] {
+            assert_eq!(first.is_bomb(position), second.is_bomb(position));
+        }
+        assert!(!first.is_bomb(IVec2::new(-1, 1)));
+    }
+
+    #[test]
+    fn uncovering_stops_at_unloaded_chunks() {
+        let mut board = EndlessBoard::new(1, 0., 4, 10.);
+        board.load_chunk(IVec2::ZERO, Entity::PLACEHOLDER);
+
+        let Uncovered::Tiles(revealed) = board.uncover(IVec2::ZERO) else {
+            panic!("There are no bombs");
+        };
+        assert_eq!(revealed.len(), 16);
+        assert_eq!(board.score(), 16);
+
+        let revealed = board.load_chunk(IVec2::new(1, 0), Entity::PLACEHOLDER);
+        assert_eq!(revealed.len(), 16);
+        assert_eq!(board.score(), 32);
+    }
+
+    #[test]
+    fn chording_needs_a_flag_on_every_bomb() {
+        let mut board = EndlessBoard::new(3, 0.3, 8, 10.);
+        for chunk in [
+            IVec2::new(-1, -1),
+            IVec2::new(0, -1),
+            IVec2::new(-1, 0),
+            IVec2::ZERO,
+        ] {
+            board.load_chunk(chunk, Entity::PLACEHOLDER);
+        }
+        let Uncovered::Tiles(revealed) = board.uncover(IVec2::ZERO) else {
+            panic!("The start has no bombs");
+        };
+        let number = *revealed
+            .iter()
+            .find(|position| board.bomb_count_at(**position) > 0)
+            .expect("The opened area is surrounded by numbers");
+        let bombs: Vec<IVec2> = neighbours(number)
+            .filter(|neighbour| board.is_bomb(*neighbour))
+            .collect();
+
+        assert!(board.chord_targets(number).is_empty());
+        board.flagged.extend(bombs.iter().copied());
+        let targets = board.chord_targets(number);
+        assert!(targets
+            .iter()
+            .all(|target| board.is_covered(*target) && !board.is_bomb(*target)));
+    }
+}
@@ -2,4 +2,10 @@ pub mod tile;
 pub mod tile_map;
 pub mod board;
 pub mod bounds;
-pub mod plugin_options;
\ No newline at end of file
+pub mod endless_board;
+pub mod modifier_state;
+pub mod pending_theme;
+pub mod plugin_options;
+pub mod screen_shake;
+pub mod tile_press;
+pub mod tile_shapes;
\ No newline at end of file
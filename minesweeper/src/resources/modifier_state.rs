@@ -0,0 +1,100 @@
+use bevy::prelude::*;
+
+use crate::{
+    options::{BoardOptions, RuleModifiers},
+    win_condition::WinCondition,
+};
+
+/// What is left of the lives and time of the [RuleModifiers] in the current game
+#[derive(Debug, Clone, Resource)]
+pub struct ModifierState {
+    pub lives: u8,
+    pub countdown: Option<Timer>,
+}
+
+impl ModifierState {
+    pub fn new(modifiers: &RuleModifiers) -> Self {
+        Self {
+            lives: modifiers.lives,
+            countdown: modifiers
+                .time_limit
+                .map(|limit| Timer::new(limit, TimerMode::Once)),
+        }
+    }
+
+    /// Uses up a life for a bomb hit. Returns false if there was no life left, which loses the game
+    pub fn survive_hit(&mut self) -> bool {
+        match self.lives {
+            0 => false,
+            _ => {
+                self.lives -= 1;
+                true
+            }
+        }
+    }
+
+    /// Line that shows the remaining time, lives and the score of score attack games. None if nothing of it is used
+    pub fn text(&self, options: &BoardOptions, score: usize) -> Option<String> {
+        let mut parts = Vec::new();
+        if let Some(countdown) = &self.countdown {
+            let seconds = countdown.remaining().as_secs_f32().ceil() as u32;
+            parts.push(format!("Time: {}:{:02}", seconds / 60, seconds % 60));
+        }
+        if options.modifiers.lives > 0 {
+            parts.push(format!("Lives: {}", self.lives));
+        }
+        if let WinCondition::ScoreAttack { target, .. } = options.win_condition() {
+            parts.push(format!("Score: {}/{}", score, target));
+        }
+        match parts.is_empty() {
+            true => None,
+            false => Some(parts.join("   ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn lives_run_out() {
+        let mut state = ModifierState::new(&RuleModifiers {
+            lives: 2,
+            ..Default::default()
+        });
+
+        assert!(state.survive_hit());
+        assert!(state.survive_hit());
+        assert!(!state.survive_hit());
+        assert_eq!(state.lives, 0);
+    }
+
+    #[test]
+    fn text_shows_only_used_modifiers() {
+        let mut options = BoardOptions {
+            modifiers: RuleModifiers {
+                time_limit: Some(Duration::from_secs(90)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let state = ModifierState::new(&options.modifiers);
+        assert_eq!(state.text(&options, 0).as_deref(), Some("Time: 1:30"));
+
+        options.win_condition = WinCondition::ScoreAttack {
+            time_limit: Duration::from_secs(90),
+            target: 20,
+        };
+        assert_eq!(
+            state.text(&options, 3).as_deref(),
+            Some("Time: 1:30   Score: 3/20")
+        );
+        assert_eq!(
+            ModifierState::new(&Default::default()).text(&Default::default(), 0),
+            None
+        );
+    }
+}
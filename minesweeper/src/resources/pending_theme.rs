@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::theme::Theme;
+
+/// A theme that is still loading. It replaces the current theme as soon as it's loaded
+#[derive(Debug, Clone, Resource)]
+pub struct PendingTheme(pub Handle<Theme>);
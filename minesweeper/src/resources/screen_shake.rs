@@ -0,0 +1,64 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+/// Shakes the camera after a bomb hit. The shake gets weaker until its timer runs out
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ScreenShake {
+    /// Farthest the camera moves away from where it would be
+    pub strength: f32,
+    pub timer: Timer,
+    /// How far the camera is moved right now
+    pub offset: Vec2,
+}
+
+impl ScreenShake {
+    /// Starts a new shake. The camera stays where the last shake moved it until the next update
+    pub fn start(&mut self, strength: f32, duration: Duration) {
+        self.strength = strength;
+        self.timer = Timer::new(duration, TimerMode::Once);
+    }
+
+    /// Whether the camera is back where it belongs
+    pub fn is_over(&self) -> bool {
+        self.timer.finished() && self.offset == Vec2::ZERO
+    }
+
+    /// Moves on by `delta` and returns how far the camera has to be moved since the last update
+    pub fn update(&mut self, delta: Duration) -> Vec2 {
+        self.timer.tick(delta);
+        let offset = match self.timer.finished() {
+            true => Vec2::ZERO,
+            false => {
+                let mut rng = rand::thread_rng();
+                Vec2::new(rng.gen_range(-1. ..1.), rng.gen_range(-1. ..1.))
+                    * self.strength
+                    * self.timer.fraction_remaining()
+            }
+        };
+        let movement = offset - self.offset;
+        self.offset = offset;
+        movement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_returns_after_the_shake() {
+        let mut shake = ScreenShake::default();
+        shake.start(10., Duration::from_millis(100));
+
+        let mut moved = Vec2::ZERO;
+        for _ in 0..5 {
+            moved += shake.update(Duration::from_millis(30));
+            assert!(moved.length() <= 10. * 2_f32.sqrt());
+        }
+
+        assert!(shake.is_over());
+        assert!(moved.length() < 1e-4);
+    }
+}
@@ -2,12 +2,27 @@ use bevy::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Resource)]
 pub enum Tile {
-    Bomb,
+    /// A tile with at least one bomb
+    Bomb(u8),
     Empty,
+    /// A hole in the board. There is no tile here
+    Void,
 }
 
 impl Tile {
     pub const fn is_bomb(&self) -> bool {
-        matches!(self, Self::Bomb)
+        matches!(self, Self::Bomb(_))
+    }
+
+    /// Number of bombs on the tile
+    pub const fn bomb_count(&self) -> u8 {
+        match self {
+            Self::Bomb(count) => *count,
+            _ => 0,
+        }
+    }
+
+    pub const fn is_void(&self) -> bool {
+        matches!(self, Self::Void)
     }
 }
@@ -1,80 +1,189 @@
-use bevy::prelude::*;
 use rand::{prelude::SliceRandom, thread_rng};
 
-use crate::components::coordinates::CoordinateU16;
+use crate::{
+    components::coordinates::CoordinateU16, mask::BoardMask, neighbourhood::Neighbourhood,
+    topology::Topology,
+};
 
 use super::tile::Tile;
 
 #[derive(Debug, Clone)]
 pub struct TileMap {
     bomb_count: u16,
+    /// Most bombs a single tile can hold
+    max_bombs_per_tile: u8,
     height: u16,
     width: u16,
+    /// Number of layers. Tiles also touch the tiles in the layers above and below
+    depth: u16,
+    topology: Topology,
+    /// Tiles that count for the numbers
+    neighbourhood: Neighbourhood,
+    /// Tiles on opposite edges are neighbours, the board is a torus
+    wrap: bool,
     map: Vec<Tile>,
 }
 
 impl TileMap {
-    pub fn empty(width: u16, height: u16) -> Self {
-        let map = (0..height * width).map(|_| Tile::Empty).collect();
+    pub fn empty(width: u16, height: u16, topology: Topology) -> Self {
+        let map = vec![Tile::Empty; width as usize * height as usize];
 
         Self {
             bomb_count: 0,
+            max_bombs_per_tile: 1,
             height,
             width,
+            depth: 1,
+            topology,
+            neighbourhood: Neighbourhood::Standard,
+            wrap: false,
             map,
         }
     }
 
-    pub fn spread_bombs(&mut self, bomb_count: u16) {
-        self.bomb_count = bomb_count;
-        let mut map_pointers: Vec<&mut Tile> = self.map.iter_mut().collect();
+    /// Stacks `depth` layers of tiles on top of each other
+    pub fn with_depth(mut self, depth: u16) -> Self {
+        self.depth = depth.max(1);
+        self.map = vec![Tile::Empty; self.layer_size() * self.depth as usize];
+        self
+    }
+
+    /// Changes which tiles count as neighbours
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood) -> Self {
+        self.neighbourhood = neighbourhood;
+        self
+    }
+
+    /// Makes opposite edges neighbours of each other. Layers don't wrap
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Turns every tile outside of the mask into a hole, in every layer
+    pub fn with_mask(mut self, mask: &BoardMask) -> Self {
+        for index in 0..self.map.len() {
+            let coordinate = self.coordinate(index);
+            if !mask.contains(CoordinateU16 { z: 0, ..coordinate }) {
+                self.map[index] = Tile::Void;
+            }
+        }
+        self
+    }
+
+    /// Places bombs on random tiles, up to `max_bombs_per_tile` on a single tile.
+    /// There can't be more bombs than fit on the tiles
+    pub fn spread_bombs(&mut self, bomb_count: u16, max_bombs_per_tile: u8) {
+        self.max_bombs_per_tile = max_bombs_per_tile.max(1);
+        // Every tile has a slot for each bomb it can hold
+        let mut slots: Vec<usize> = (0..self.map.len())
+            .filter(|index| !self.map[*index].is_void())
+            .flat_map(|index| std::iter::repeat_n(index, self.max_bombs_per_tile as usize))
+            .collect();
+        self.bomb_count = bomb_count.min(slots.len().try_into().unwrap_or(u16::MAX));
         let mut rng = thread_rng();
 
-        map_pointers.shuffle(&mut rng);
-        map_pointers
-            .into_iter()
-            .take(self.bomb_count as usize)
-            .for_each(|x| *x = Tile::Bomb)
+        slots.shuffle(&mut rng);
+        for index in slots.into_iter().take(self.bomb_count as usize) {
+            self.map[index] = Tile::Bomb(self.map[index].bomb_count() + 1);
+        }
     }
 
+    /// Sum of the bombs on the neighbours of a tile
     pub fn bomb_count_at(&self, coordinate: CoordinateU16) -> u8 {
-        self.neighbours(coordinate).filter(|x| x.is_bomb()).count() as u8
+        self.neighbours(coordinate).map(|x| x.bomb_count()).sum()
     }
 
     pub fn bomb_count_at_index(&self, index: usize) -> u8 {
-        let coordinate = CoordinateU16 {
-            x: index as u16 % self.width,
-            y: index as u16 / self.width,
-        };
-        self.bomb_count_at(coordinate)
+        self.bomb_count_at(self.coordinate(index))
+    }
+
+    /// Coordinate of the tile at an index of the map
+    pub fn coordinate(&self, index: usize) -> CoordinateU16 {
+        let in_layer = index % self.layer_size();
+        CoordinateU16 {
+            x: (in_layer % self.width as usize) as u16,
+            y: (in_layer / self.width as usize) as u16,
+            z: (index / self.layer_size()) as u16,
+        }
     }
 
+    /// Every tile in the neighbourhood of the tile, in its own layer and in the layers above and below
     pub fn neighbour_coordinates(
         &self,
         coordinate: CoordinateU16,
     ) -> impl Iterator<Item = CoordinateU16> + '_ {
-        SQUARE_NEIGHBOUR_COORDINATES
-            .iter()
-            .copied()
-            .map(move |tuple| coordinate + tuple)
-            .filter(|coordinate| coordinate.x < self.width && coordinate.y < self.height)
+        let offsets = self.neighbourhood.offsets(self.topology, coordinate);
+        self.offset_coordinates(coordinate, offsets, offsets)
     }
 
+    /// Every tile that shares an edge with the tile. In the layers above and below that's only the tile right on top of it
     pub fn true_neighbour_coordinates(
         &self,
         coordinate: CoordinateU16,
     ) -> impl Iterator<Item = CoordinateU16> + '_ {
-        TRUE_SQUARE_NEIGHBOUR_COORDINATES
+        self.offset_coordinates(
+            coordinate,
+            self.topology.edge_neighbour_offsets(coordinate),
+            &[],
+        )
+    }
+
+    /// The tiles at `offsets` in the layer of the tile, and the tiles at `layer_offsets` plus the tile itself in the
+    /// layers above and below
+    fn offset_coordinates(
+        &self,
+        coordinate: CoordinateU16,
+        offsets: &[(i8, i8)],
+        layer_offsets: &[(i8, i8)],
+    ) -> impl Iterator<Item = CoordinateU16> {
+        let size = (self.width, self.height);
+        let layers = [coordinate.z.checked_sub(1), Some(coordinate.z + 1)]
+            .into_iter()
+            .flatten()
+            .filter(|z| *z < self.depth)
+            .map(|z| CoordinateU16 { z, ..coordinate });
+        let mut coordinates: Vec<CoordinateU16> = offsets
             .iter()
-            .copied()
-            .map(move |tuple| coordinate + tuple)
-            .filter(|coordinate| coordinate.x < self.width && coordinate.y < self.height)
+            .filter_map(|offset| coordinate.offset(*offset, size, self.wrap))
+            .chain(layers.flat_map(|layer| {
+                std::iter::once(layer).chain(
+                    layer_offsets
+                        .iter()
+                        .filter_map(move |offset| layer.offset(*offset, size, self.wrap)),
+                )
+            }))
+            .filter(|neighbour| *neighbour != coordinate && !self[*neighbour].is_void())
+            .collect();
+        // Small wrapping boards reach the same tile from more than one side
+        if self.wrap {
+            coordinates.sort_unstable();
+            coordinates.dedup();
+        }
+        coordinates.into_iter()
     }
 
     pub fn neighbours(&self, coordinate: CoordinateU16) -> impl Iterator<Item = Tile> + '_ {
         self.neighbour_coordinates(coordinate).map(|x| self[x])
     }
 
+    /// Most neighbours any tile of the map has
+    pub fn max_neighbour_count(&self) -> usize {
+        (0..self.map.len())
+            .map(|index| self.neighbour_coordinates(self.coordinate(index)).count())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Number of tiles that aren't holes
+    pub fn tile_count(&self) -> usize {
+        self.map.iter().filter(|tile| !tile.is_void()).count()
+    }
+
+    fn layer_size(&self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
     pub fn height(&self) -> &u16 {
         &self.height
     }
@@ -83,16 +192,37 @@ impl TileMap {
         &self.width
     }
 
+    pub fn depth(&self) -> &u16 {
+        &self.depth
+    }
+
+    pub fn max_bombs_per_tile(&self) -> &u8 {
+        &self.max_bombs_per_tile
+    }
+
     pub fn bomb_count(&self) -> &u16 {
         &self.bomb_count
     }
+
+    pub fn topology(&self) -> &Topology {
+        &self.topology
+    }
+
+    pub fn neighbourhood(&self) -> &Neighbourhood {
+        &self.neighbourhood
+    }
+
+    pub fn wrap(&self) -> &bool {
+        &self.wrap
+    }
 }
 
 impl std::ops::Index<(u16, u16)> for TileMap {
     type Output = Tile;
 
+    /// Tile on layer 0
     fn index(&self, (x, y): (u16, u16)) -> &Self::Output {
-        &self.map[x as usize + (y * self.width) as usize]
+        &self.map[x as usize + y as usize * self.width as usize]
     }
 }
 
@@ -100,7 +230,9 @@ impl std::ops::Index<CoordinateU16> for TileMap {
     type Output = Tile;
 
     fn index(&self, coordinate: CoordinateU16) -> &Self::Output {
-        &self.map[coordinate.x as usize + (coordinate.y * self.width) as usize]
+        &self.map[coordinate.x as usize
+            + coordinate.y as usize * self.width as usize
+            + coordinate.z as usize * self.layer_size()]
     }
 }
 
@@ -122,8 +254,15 @@ impl std::ops::DerefMut for TileMap {
 impl std::fmt::Display for TileMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut buffer = format!(
-            "Map ({};{}) with {} bombs:\n",
-            self.width, self.height, self.bomb_count
+            "Map ({};{};{}) with {} bombs{}:\n",
+            self.width,
+            self.height,
+            self.depth,
+            self.bomb_count,
+            match self.wrap {
+                true => ", wrapping around the edges",
+                false => "",
+            }
         );
 
         buffer += "|";
@@ -136,11 +275,17 @@ impl std::fmt::Display for TileMap {
             .enumerate()
             .map(|(index, element)| {
                 let mut buf = String::new();
+                // Layers are separated by a line
+                if index % self.layer_size() == 0 && index != 0 {
+                    buf += "|\n|";
+                    buf += &(0..self.width).map(|_| "-").collect::<String>();
+                }
                 if index % self.width as usize == 0 {
                     buf += "|\n|"
                 }
                 buf += &match element {
-                    Tile::Bomb => "*".to_string(),
+                    Tile::Bomb(_) => "*".to_string(),
+                    Tile::Void => "#".to_string(),
                     Tile::Empty => match self.bomb_count_at_index(index) {
                         0 => " ".to_string(),
                         x => x.to_string(),
@@ -157,32 +302,72 @@ impl std::fmt::Display for TileMap {
     }
 }
 
-const SQUARE_NEIGHBOUR_COORDINATES: [(i8, i8); 8] = [
-    // Bottom left
-    (-1, -1),
-    // Bottom
-    (0, -1),
-    // Bottom right
-    (1, -1),
-    // Left
-    (-1, 0),
-    // Right
-    (1, 0),
-    // Top Left
-    (-1, 1),
-    // Top
-    (0, 1),
-    // Top right
-    (1, 1),
-];
-
-const TRUE_SQUARE_NEIGHBOUR_COORDINATES: [(i8, i8); 4] = [
-    // Bottom
-    (0, -1),
-    // Left
-    (-1, 0),
-    // Right
-    (1, 0),
-    // Top
-    (0, 1),
-];
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapping_corner_has_eight_neighbours() {
+        let corner = CoordinateU16 { x: 0, y: 0, z: 0 };
+        let tile_map = TileMap::empty(5, 5, Topology::Square);
+        assert_eq!(tile_map.neighbour_coordinates(corner).count(), 3);
+
+        let tile_map = tile_map.with_wrap(true);
+        let neighbours: Vec<CoordinateU16> = tile_map.neighbour_coordinates(corner).collect();
+        assert_eq!(neighbours.len(), 8);
+        assert!(neighbours.contains(&CoordinateU16 { x: 4, y: 4, z: 0 }));
+    }
+
+    #[test]
+    fn holes_are_not_neighbours() {
+        let mask = BoardMask::parse("###\n#.#\n###").unwrap();
+        let mut tile_map = TileMap::empty(3, 3, Topology::Square).with_mask(&mask);
+        tile_map.spread_bombs(10, 1);
+
+        assert_eq!(tile_map.tile_count(), 8);
+        assert_eq!(*tile_map.bomb_count(), 8);
+        assert!(tile_map[(1, 1)].is_void());
+        assert_eq!(
+            tile_map
+                .neighbour_coordinates(CoordinateU16 { x: 0, y: 0, z: 0 })
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn layered_center_has_twenty_six_neighbours() {
+        let tile_map = TileMap::empty(3, 3, Topology::Square).with_depth(3);
+        let center = CoordinateU16 { x: 1, y: 1, z: 1 };
+
+        assert_eq!(tile_map.neighbour_coordinates(center).count(), 26);
+        assert_eq!(tile_map.true_neighbour_coordinates(center).count(), 6);
+        assert_eq!(tile_map.coordinate(13), center);
+    }
+
+    #[test]
+    fn numbers_sum_up_bombs() {
+        let mut tile_map = TileMap::empty(2, 1, Topology::Square);
+        tile_map.spread_bombs(3, 3);
+
+        assert_eq!(*tile_map.bomb_count(), 3);
+        assert_eq!(tile_map.iter().map(|tile| tile.bomb_count()).sum::<u8>(), 3);
+        let left = CoordinateU16 { x: 0, y: 0, z: 0 };
+        assert_eq!(tile_map.bomb_count_at(left), tile_map[(1, 0)].bomb_count());
+    }
+
+    #[test]
+    fn neighbourhoods_have_their_size() {
+        let center = CoordinateU16 { x: 2, y: 2, z: 0 };
+        for (neighbourhood, count) in [
+            (Neighbourhood::Standard, 8),
+            (Neighbourhood::Orthogonal, 4),
+            (Neighbourhood::Knight, 8),
+            (Neighbourhood::Radius2, 24),
+        ] {
+            let tile_map = TileMap::empty(5, 5, Topology::Square).with_neighbourhood(neighbourhood);
+
+            assert_eq!(tile_map.neighbour_coordinates(center).count(), count);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::{
+    input::{mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+};
+
+use crate::components::coordinates::CoordinateU16;
+
+use super::{
+    board::Board,
+    endless_board::{self, EndlessBoard},
+};
+
+/// The tile under the mouse and the buttons that are held on it. Endless boards use a `TilePress<IVec2>`
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct TilePress<C = CoordinateU16> {
+    pub hovered: Option<C>,
+    /// The left button is held. The tile is uncovered when it's released
+    pub pressed: bool,
+    /// Both buttons or the middle button are held. The tiles around the hovered one are chorded when one of them
+    /// is released
+    pub chording: bool,
+}
+
+impl<C> Default for TilePress<C> {
+    fn default() -> Self {
+        Self {
+            hovered: None,
+            pressed: false,
+            chording: false,
+        }
+    }
+}
+
+/// What a button does to the hovered tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressAction {
+    Uncover,
+    Flag,
+    Chord,
+}
+
+/// How a covered tile looks because of the mouse
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CoverLook {
+    #[default]
+    Normal,
+    Hovered,
+    Pressed,
+}
+
+impl<C: Copy + PartialEq> TilePress<C> {
+    /// Follows a button. Flags are placed when the right button is pressed, tiles are uncovered or chorded when a
+    /// button is released, so the press can still be moved to another tile
+    pub fn button(&mut self, event: &MouseButtonInput, right_held: bool) -> Option<PressAction> {
+        match (event.button, event.state) {
+            (MouseButton::Left, ButtonState::Pressed) => {
+                self.pressed = true;
+                self.chording = right_held;
+                None
+            }
+            (MouseButton::Middle, ButtonState::Pressed) => {
+                self.chording = true;
+                None
+            }
+            (MouseButton::Right, ButtonState::Pressed) if self.pressed => {
+                self.chording = true;
+                None
+            }
+            (MouseButton::Right, ButtonState::Pressed) => Some(PressAction::Flag),
+            (_, ButtonState::Released) if self.chording => {
+                self.pressed = false;
+                self.chording = false;
+                Some(PressAction::Chord)
+            }
+            (MouseButton::Left, ButtonState::Released) if self.pressed => {
+                self.pressed = false;
+                Some(PressAction::Uncover)
+            }
+            _ => None,
+        }
+    }
+
+    /// Looks of the hovered tile, and of its neighbours while chording, that are covered
+    fn looks_around(
+        &self,
+        neighbours: impl Fn(C) -> Vec<C>,
+        flagged: impl Fn(&C) -> bool,
+        covered: impl Fn(&C) -> bool,
+    ) -> Vec<(C, CoverLook)> {
+        let Some(hovered) = self.hovered else {
+            return Vec::new();
+        };
+        let looks: Vec<(C, CoverLook)> = match (self.chording, self.pressed) {
+            (true, _) => [hovered]
+                .into_iter()
+                .chain(neighbours(hovered))
+                .filter(|coordinate| !flagged(coordinate))
+                .map(|coordinate| (coordinate, CoverLook::Pressed))
+                .collect(),
+            (false, true) if !flagged(&hovered) => vec![(hovered, CoverLook::Pressed)],
+            (false, _) => vec![(hovered, CoverLook::Hovered)],
+        };
+        looks
+            .into_iter()
+            .filter(|(coordinate, _)| covered(coordinate))
+            .collect()
+    }
+}
+
+impl TilePress {
+    /// The covered tiles that don't look normal. Flagged tiles can't be pressed in
+    pub fn looks(&self, board: &Board) -> Vec<(CoordinateU16, CoverLook)> {
+        self.looks_around(
+            |hovered| board.tile_map.neighbour_coordinates(hovered).collect(),
+            |coordinate| {
+                board
+                    .flagged
+                    .get(coordinate)
+                    .is_some_and(|(_, _, flags)| *flags != 0)
+            },
+            |coordinate| board.covered.contains_key(coordinate),
+        )
+    }
+}
+
+impl TilePress<IVec2> {
+    /// The covered tiles of an endless board that don't look normal
+    pub fn endless_looks(&self, board: &EndlessBoard) -> Vec<(IVec2, CoverLook)> {
+        self.looks_around(
+            |hovered| endless_board::neighbours(hovered).collect(),
+            |position| board.flagged.contains(position),
+            |position| board.is_covered(*position),
+        )
+    }
+}
+
+impl CoverLook {
+    /// Tints the colour of a cover. Hovered covers get a little bluer, pressed ones darker
+    pub fn tint(&self, color: Color) -> Color {
+        match self {
+            Self::Normal => color,
+            Self::Hovered => color * [0.85, 0.92, 1., 1.],
+            Self::Pressed => color * [0.65, 0.65, 0.65, 1.],
+        }
+    }
+}
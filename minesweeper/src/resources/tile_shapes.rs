@@ -0,0 +1,352 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_4, FRAC_PI_6, TAU};
+
+use bevy::{
+    math::primitives::{Circle, Ellipse, Rectangle, Triangle2d},
+    prelude::*,
+};
+
+use crate::{
+    atlas::TileAtlas,
+    components::{tile_drawing::TileDrawing, tile_shape::TileShape},
+    palette::TilePalette,
+    resources::tile_press::CoverLook,
+    theme::ThemeStyle,
+    topology::Topology,
+    GHOST_COLOR,
+};
+
+/// Height of the drawings above their tile
+const DRAWING_Z: f32 = 0.1;
+
+/// Meshes and materials shared by the tiles of a [crate::options::BoardRendering::Shapes] board.
+/// The assets are changed in place when the tiles are resized or the theme changes
+#[derive(Debug, Clone, Resource)]
+pub struct TileShapes {
+    pub topology: Topology,
+    /// Size of a tile without its padding, which the meshes are made for
+    pub sprite_size: Vec2,
+    pub tile: Handle<Mesh>,
+    /// Triangles pointing down. The same as `tile` for the other topologies
+    pub tile_upside_down: Handle<Mesh>,
+    pub bomb: Handle<Mesh>,
+    pub spike: Handle<Mesh>,
+    pub pole: Handle<Mesh>,
+    pub cloth: Handle<Mesh>,
+    pub foot: Handle<Mesh>,
+    /// Dots of the patterns around numbers
+    pub dot: Handle<Mesh>,
+    pub tile_material: Handle<ColorMaterial>,
+    pub cover_material: Handle<ColorMaterial>,
+    pub hovered_cover_material: Handle<ColorMaterial>,
+    pub pressed_cover_material: Handle<ColorMaterial>,
+    pub ghost_tile_material: Handle<ColorMaterial>,
+    pub ghost_cover_material: Handle<ColorMaterial>,
+    pub bomb_material: Handle<ColorMaterial>,
+    pub flag_material: Handle<ColorMaterial>,
+    pub pole_material: Handle<ColorMaterial>,
+    /// One for every number colour of the palette
+    pub number_materials: Vec<Handle<ColorMaterial>>,
+}
+
+impl TileShapes {
+    pub fn new(
+        topology: Topology,
+        sprite_size: Vec2,
+        palette: &TilePalette,
+        style: &ThemeStyle,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<ColorMaterial>,
+    ) -> Self {
+        let tile = meshes.reserve_handle();
+        let mut shapes = Self {
+            topology,
+            sprite_size,
+            tile_upside_down: match topology {
+                Topology::Triangle => meshes.reserve_handle(),
+                _ => tile.clone(),
+            },
+            tile,
+            bomb: meshes.reserve_handle(),
+            spike: meshes.reserve_handle(),
+            pole: meshes.reserve_handle(),
+            cloth: meshes.reserve_handle(),
+            foot: meshes.reserve_handle(),
+            dot: meshes.reserve_handle(),
+            tile_material: materials.reserve_handle(),
+            cover_material: materials.reserve_handle(),
+            hovered_cover_material: materials.reserve_handle(),
+            pressed_cover_material: materials.reserve_handle(),
+            ghost_tile_material: materials.reserve_handle(),
+            ghost_cover_material: materials.reserve_handle(),
+            bomb_material: materials.reserve_handle(),
+            flag_material: materials.reserve_handle(),
+            pole_material: materials.reserve_handle(),
+            number_materials: Vec::new(),
+        };
+        shapes.build_meshes(meshes);
+        shapes.set_colors(palette, style, materials);
+        shapes
+    }
+
+    /// Makes the meshes fit tiles of a new size
+    pub fn resize(&mut self, sprite_size: Vec2, meshes: &mut Assets<Mesh>) {
+        self.sprite_size = sprite_size;
+        self.build_meshes(meshes);
+    }
+
+    /// Colours the materials with the palette, tinted by the theme
+    pub fn set_colors(
+        &mut self,
+        palette: &TilePalette,
+        style: &ThemeStyle,
+        materials: &mut Assets<ColorMaterial>,
+    ) {
+        let tile = palette.tile * style.tile.as_rgba_f32();
+        let cover = palette.cover * style.cover.as_rgba_f32();
+        for (handle, color) in [
+            (&self.tile_material, tile),
+            (&self.cover_material, cover),
+            (&self.hovered_cover_material, CoverLook::Hovered.tint(cover)),
+            (&self.pressed_cover_material, CoverLook::Pressed.tint(cover)),
+            (&self.ghost_tile_material, tile * GHOST_COLOR.as_rgba_f32()),
+            (
+                &self.ghost_cover_material,
+                cover * GHOST_COLOR.as_rgba_f32(),
+            ),
+            (&self.bomb_material, palette.bomb),
+            (&self.flag_material, palette.flag),
+            (&self.pole_material, palette.pole),
+        ] {
+            materials.insert(handle, ColorMaterial::from(color));
+        }
+        self.number_materials
+            .resize_with(palette.numbers.len(), || materials.reserve_handle());
+        for (handle, color) in self.number_materials.iter().zip(&palette.numbers) {
+            materials.insert(handle, ColorMaterial::from(*color));
+        }
+    }
+
+    pub fn mesh(&self, shape: &TileShape) -> Handle<Mesh> {
+        match shape.upside_down {
+            true => self.tile_upside_down.clone(),
+            false => self.tile.clone(),
+        }
+    }
+
+    /// Material of a tile that shows the texture at `index` of the [TileAtlas]
+    pub fn material(&self, index: usize, ghost: bool) -> Handle<ColorMaterial> {
+        let covered = index >= TileAtlas::BASE && index != TileAtlas::BOMB;
+        match (covered, ghost) {
+            (false, false) => self.tile_material.clone(),
+            (true, false) => self.cover_material.clone(),
+            (false, true) => self.ghost_tile_material.clone(),
+            (true, true) => self.ghost_cover_material.clone(),
+        }
+    }
+
+    /// Material of a cover that looks hovered or pressed in
+    pub fn cover_material(&self, look: CoverLook) -> Handle<ColorMaterial> {
+        match look {
+            CoverLook::Normal => self.cover_material.clone(),
+            CoverLook::Hovered => self.hovered_cover_material.clone(),
+            CoverLook::Pressed => self.pressed_cover_material.clone(),
+        }
+    }
+
+    /// Material in the colour of the number `count`, like [TilePalette::number]
+    pub fn number_material(&self, count: u8) -> Option<Handle<ColorMaterial>> {
+        match (count, self.number_materials.len()) {
+            (0, _) | (_, 0) => None,
+            (count, len) => Some(self.number_materials[(count as usize - 1) % len].clone()),
+        }
+    }
+
+    /// Spawns the number, bomb or flags of the texture at `index` of the [TileAtlas]. Numbers with `patterns` are
+    /// ringed by as many dots
+    pub fn spawn_drawings(
+        &self,
+        parent: &mut ChildBuilder,
+        shape: &TileShape,
+        index: usize,
+        palette: &TilePalette,
+        patterns: bool,
+        font: &Handle<Font>,
+    ) {
+        let size = self.drawing_size();
+        let center = self.drawing_center(shape);
+        let mesh = |parent: &mut ChildBuilder,
+                    mesh: &Handle<Mesh>,
+                    material: &Handle<ColorMaterial>,
+                    transform: Transform| {
+            parent.spawn((
+                ColorMesh2dBundle {
+                    mesh: mesh.clone().into(),
+                    material: material.clone(),
+                    transform: transform
+                        .with_translation(transform.translation + center.extend(DRAWING_Z)),
+                    ..Default::default()
+                },
+                TileDrawing,
+            ));
+        };
+        let text =
+            |parent: &mut ChildBuilder, value: String, font_size: f32, color, offset: Vec2| {
+                parent.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            value,
+                            TextStyle {
+                                font: font.clone(),
+                                font_size,
+                                color,
+                            },
+                        )
+                        .with_justify(JustifyText::Center),
+                        transform: Transform::from_translation((center + offset).extend(DRAWING_Z)),
+                        ..Default::default()
+                    },
+                    TileDrawing,
+                ));
+            };
+
+        match index {
+            TileAtlas::BOMB => {
+                for angle in [0., FRAC_PI_4, FRAC_PI_2, FRAC_PI_4 * 3.] {
+                    mesh(
+                        parent,
+                        &self.spike,
+                        &self.bomb_material,
+                        Transform::from_rotation(Quat::from_rotation_z(angle)),
+                    );
+                }
+                mesh(
+                    parent,
+                    &self.bomb,
+                    &self.bomb_material,
+                    Transform::default(),
+                );
+            }
+            TileAtlas::BASE | 0 => (),
+            _ if index < TileAtlas::BASE => {
+                let count = index as u8;
+                text(
+                    parent,
+                    count.to_string(),
+                    size * 0.7,
+                    palette.number(count),
+                    Vec2::ZERO,
+                );
+                if let Some(material) = self.number_material(count).filter(|_| patterns) {
+                    for dot in 0..count {
+                        // Clockwise from the top
+                        let angle = FRAC_PI_2 - TAU * dot as f32 / count as f32;
+                        let position = Vec2::from_angle(angle) * size * 0.38;
+                        mesh(
+                            parent,
+                            &self.dot,
+                            &material,
+                            Transform::from_translation(position.extend(0.)),
+                        );
+                    }
+                }
+            }
+            _ => {
+                let flags = index + 1 - TileAtlas::FLAG;
+                // More than one flag has the count next to it
+                let shift = match flags {
+                    1 => Vec3::ZERO,
+                    _ => Vec3::new(-0.12, 0., 0.) * size,
+                };
+                for (part, material, position) in [
+                    (&self.cloth, &self.flag_material, Vec3::ZERO),
+                    (&self.pole, &self.pole_material, Vec3::new(0.08, 0., 0.)),
+                    (&self.foot, &self.pole_material, Vec3::new(0.08, -0.26, 0.)),
+                ] {
+                    mesh(
+                        parent,
+                        part,
+                        material,
+                        Transform::from_translation(position * size + shift),
+                    );
+                }
+                if flags > 1 {
+                    text(
+                        parent,
+                        flags.to_string(),
+                        size * 0.4,
+                        palette.pole,
+                        Vec2::new(0.3, -0.05) * size,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Width of the space for drawings, which is the biggest circle inside a tile
+    fn drawing_size(&self) -> f32 {
+        match self.topology {
+            Topology::Square | Topology::Hexagon => self.sprite_size.x,
+            Topology::Triangle => self.sprite_size.x / 3_f32.sqrt(),
+        }
+    }
+
+    /// Center of the drawings relative to the center of the tile. Triangles have them at their centroid
+    fn drawing_center(&self, shape: &TileShape) -> Vec2 {
+        let offset = match self.topology {
+            Topology::Triangle => Vec2::new(0., -self.sprite_size.y / 6.),
+            _ => Vec2::ZERO,
+        };
+        match shape.upside_down {
+            true => -offset,
+            false => offset,
+        }
+    }
+
+    fn build_meshes(&self, meshes: &mut Assets<Mesh>) {
+        let half = self.sprite_size / 2.;
+        let size = self.drawing_size();
+        let tile = match self.topology {
+            Topology::Square => Rectangle::from_size(self.sprite_size).into(),
+            // Pointy topped, the sides are at the left and right edge
+            Topology::Hexagon => Ellipse::new(half.x / FRAC_PI_6.cos(), half.y)
+                .mesh()
+                .resolution(6)
+                .build(),
+            Topology::Triangle => Triangle2d::new(
+                Vec2::new(-half.x, -half.y),
+                Vec2::new(half.x, -half.y),
+                Vec2::new(0., half.y),
+            )
+            .into(),
+        };
+        let tile_upside_down = Triangle2d::new(
+            Vec2::new(-half.x, half.y),
+            Vec2::new(0., -half.y),
+            Vec2::new(half.x, half.y),
+        )
+        .into();
+        // Points to the left from the top of the pole
+        let cloth = Triangle2d::new(
+            Vec2::new(0.05, 0.27) * size,
+            Vec2::new(-0.25, 0.145) * size,
+            Vec2::new(0.05, 0.02) * size,
+        );
+
+        meshes.insert(&self.tile, tile);
+        if self.topology == Topology::Triangle {
+            meshes.insert(&self.tile_upside_down, tile_upside_down);
+        }
+        meshes.insert(
+            &self.bomb,
+            Circle::new(size * 0.22).mesh().resolution(24).build(),
+        );
+        meshes.insert(&self.spike, Rectangle::new(size * 0.62, size * 0.07).into());
+        meshes.insert(&self.pole, Rectangle::new(size * 0.06, size * 0.55).into());
+        meshes.insert(&self.cloth, cloth.into());
+        meshes.insert(&self.foot, Rectangle::new(size * 0.35, size * 0.06).into());
+        meshes.insert(
+            &self.dot,
+            Circle::new(size * 0.04).mesh().resolution(8).build(),
+        );
+    }
+}
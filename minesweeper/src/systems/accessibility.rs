@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    options::{AccessibilityOptions, BoardOptions},
+    resources::pending_theme::PendingTheme,
+    theme::{Theme, ThemeHandle, HIGH_CONTRAST_THEME},
+};
+
+const UI_SCALE_STEP: f32 = 0.25;
+const UI_SCALE_RANGE: (f32, f32) = (0.5, 3.);
+const MIN_TILE_SIZE_STEP: f32 = 5.;
+const MIN_TILE_SIZE_MAX: f32 = 200.;
+
+/// Changes the accessibility settings with the keyboard. C switches the number colours, P the number patterns,
+/// H the high contrast theme and M reduced motion. + and - scale the interface, ] and [ change the minimum tile size
+pub fn accessibility_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut accessibility: ResMut<AccessibilityOptions>,
+) {
+    let mut next = *accessibility;
+    if keys.just_pressed(KeyCode::KeyC) {
+        next.number_colors = next.number_colors.next();
+    }
+    if keys.just_pressed(KeyCode::KeyP) {
+        next.number_patterns = !next.number_patterns;
+    }
+    if keys.just_pressed(KeyCode::KeyH) {
+        next.high_contrast = !next.high_contrast;
+    }
+    if keys.just_pressed(KeyCode::KeyM) {
+        next.reduced_motion = !next.reduced_motion;
+    }
+    if keys.any_just_pressed([KeyCode::Equal, KeyCode::NumpadAdd]) {
+        next.ui_scale = (next.ui_scale + UI_SCALE_STEP).min(UI_SCALE_RANGE.1);
+    }
+    if keys.any_just_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]) {
+        next.ui_scale = (next.ui_scale - UI_SCALE_STEP).max(UI_SCALE_RANGE.0);
+    }
+    if keys.just_pressed(KeyCode::BracketRight) {
+        next.min_tile_size = (next.min_tile_size + MIN_TILE_SIZE_STEP).min(MIN_TILE_SIZE_MAX);
+    }
+    if keys.just_pressed(KeyCode::BracketLeft) {
+        next.min_tile_size = (next.min_tile_size - MIN_TILE_SIZE_STEP).max(0.);
+    }
+
+    if accessibility.set_if_neq(next) {
+        bevy::log::info!("Accessibility settings: {:?}", next);
+    }
+}
+
+/// Applies the parts of changed accessibility settings that aren't picked up by the board systems, which resize
+/// and redraw the tiles themselves. Switching high contrast on loads its theme, switching it off goes back to the
+/// theme that was used before
+#[allow(clippy::too_many_arguments)]
+pub fn apply_accessibility(
+    mut commands: Commands,
+    accessibility: Res<AccessibilityOptions>,
+    asset_server: Res<AssetServer>,
+    options: Option<Res<BoardOptions>>,
+    current: Option<Res<ThemeHandle>>,
+    pending: Option<Res<PendingTheme>>,
+    ui_scale: Option<ResMut<UiScale>>,
+    // The game starts with the right theme, it only has to be switched when the setting changes
+    mut high_contrast: Local<Option<bool>>,
+    mut theme_before: Local<Option<Handle<Theme>>>,
+) {
+    if let Some(mut ui_scale) = ui_scale.filter(|scale| scale.0 != accessibility.ui_scale) {
+        ui_scale.0 = accessibility.ui_scale;
+    }
+
+    let previous = high_contrast.replace(accessibility.high_contrast);
+    if previous.unwrap_or(accessibility.high_contrast) == accessibility.high_contrast {
+        return;
+    }
+    let theme = match accessibility.high_contrast {
+        true => {
+            // A theme that is still loading was picked last
+            *theme_before = pending
+                .map(|pending| pending.0.clone())
+                .or(current.map(|current| current.0.clone()));
+            asset_server.load(HIGH_CONTRAST_THEME)
+        }
+        false => match theme_before.take() {
+            Some(theme) => theme,
+            // The game started with high contrast, so it goes back to the theme of the options
+            None => {
+                let options = match options {
+                    Some(t) => t.clone(),
+                    None => Default::default(),
+                };
+                asset_server.load(options.theme)
+            }
+        },
+    };
+    bevy::log::info!("Loading theme {:?}", asset_server.get_path(theme.id()));
+    commands.insert_resource(PendingTheme(theme));
+}
@@ -0,0 +1,245 @@
+use std::{
+    f32::consts::{FRAC_PI_2, TAU},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    components::{
+        cover_animation::CoverAnimation, flag_drop::FlagDrop, particle::Particle,
+        tile_cover_marker::TileCoverMarker,
+    },
+    events::BombHitEvent,
+    options::{AccessibilityOptions, Animations, BoardOptions, UncoverAnimation},
+    resources::screen_shake::ScreenShake,
+    MinesweeperPlugin, FOREGROUND_Z,
+};
+
+/// Height of particles, above everything else of the board
+const PARTICLE_Z: f32 = 10.;
+/// How much bigger a flag is when it starts to drop
+const DROP_SCALE: f32 = 0.5;
+const SHAKE_DURATION: Duration = Duration::from_millis(400);
+const EXPLOSION_COLORS: [Color; 3] = [Color::ORANGE_RED, Color::ORANGE, Color::YELLOW];
+const CONFETTI_COUNT: usize = 150;
+
+fn animations(
+    options: Option<Res<BoardOptions>>,
+    accessibility: &AccessibilityOptions,
+) -> Animations {
+    accessibility.animations(
+        options
+            .map(|options| options.animations)
+            .unwrap_or_default(),
+    )
+}
+
+/// Fades or flips the covers of uncovered tiles and despawns them once they are gone
+pub fn animate_covers(
+    mut commands: Commands,
+    time: Res<Time>,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    // Tiles have the animation too until their cover takes it over
+    mut covers: Query<
+        (Entity, &CoverAnimation, &mut Transform, Option<&mut Sprite>),
+        With<TileCoverMarker>,
+    >,
+) {
+    if covers.is_empty() {
+        return;
+    }
+    let animations = animations(options, &accessibility);
+
+    for (entity, animation, mut transform, sprite) in covers.iter_mut() {
+        let progress = animations.progress(animation.start, time.elapsed());
+        if progress >= 1. {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        match (animations.uncover, sprite) {
+            (UncoverAnimation::Fade, Some(mut sprite)) => {
+                sprite.color.set_a(1. - progress);
+            }
+            // Shapes share their materials, so they shrink instead of fading
+            (UncoverAnimation::Fade, None) => {
+                transform.scale = Vec2::splat(1. - progress).extend(1.);
+            }
+            (UncoverAnimation::Flip, _) => {
+                transform.scale.x = (progress * FRAC_PI_2).cos();
+            }
+            // The animations were turned off in the meantime
+            (UncoverAnimation::None, _) => commands.entity(entity).despawn_recursive(),
+        }
+    }
+}
+
+/// Lets flags fall onto their tile, they start bigger and above the other covers
+pub fn drop_flags(
+    mut commands: Commands,
+    time: Res<Time>,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    mut covers: Query<(Entity, &FlagDrop, &mut Transform), Without<CoverAnimation>>,
+) {
+    if covers.is_empty() {
+        return;
+    }
+    let animations = animations(options, &accessibility);
+
+    for (entity, drop, mut transform) in covers.iter_mut() {
+        let progress = animations.progress(drop.start, time.elapsed());
+        // Falls faster the closer it gets
+        let height = 1. - progress * progress;
+        transform.scale = Vec2::splat(1. + DROP_SCALE * height).extend(1.);
+        transform.translation.z = FOREGROUND_Z + height;
+        if progress >= 1. {
+            commands.entity(entity).remove::<FlagDrop>();
+        }
+    }
+}
+
+/// Blows up hit bombs into particles and shakes the screen
+pub(crate) fn explode(
+    mut commands: Commands,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    mut bomb_hit_evr: EventReader<BombHitEvent>,
+    mut shake: ResMut<ScreenShake>,
+) {
+    if bomb_hit_evr.is_empty() {
+        return;
+    }
+    let animations = animations(options, &accessibility);
+    let mut rng = rand::thread_rng();
+
+    for event in bomb_hit_evr.read() {
+        for _ in 0..animations.explosion_particles {
+            let speed = rng.gen_range(1. ..5.) * event.tile_size;
+            let size = rng.gen_range(0.08..0.2) * event.tile_size;
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: EXPLOSION_COLORS[rng.gen_range(0..EXPLOSION_COLORS.len())],
+                        custom_size: Some(Vec2::splat(size)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(event.position.extend(PARTICLE_Z)),
+                    ..Default::default()
+                },
+                Name::new("Explosion Particle"),
+                Particle {
+                    velocity: Vec2::from_angle(rng.gen_range(0. ..TAU)) * speed,
+                    gravity: event.tile_size * 4.,
+                    lifetime: Timer::from_seconds(rng.gen_range(0.4..0.9), TimerMode::Once),
+                },
+            ));
+        }
+        if animations.screen_shake > 0. {
+            shake.start(animations.screen_shake * event.tile_size, SHAKE_DURATION);
+        }
+    }
+}
+
+/// Moves particles and fades them out. They are despawned at the end of their lifetime
+pub fn move_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut sprite) in particles.iter_mut() {
+        if particle.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        particle.velocity.y -= particle.gravity * delta;
+        transform.translation += (particle.velocity * delta).extend(0.);
+        sprite.color.set_a(particle.lifetime.fraction_remaining());
+    }
+}
+
+/// Moves the camera around while the screen shakes
+pub fn shake_camera(
+    time: Res<Time>,
+    mut shake: ResMut<ScreenShake>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    if shake.is_over() {
+        return;
+    }
+    let movement = shake.update(time.delta());
+    for mut transform in cameras.iter_mut() {
+        transform.translation += movement.extend(0.);
+    }
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Lets confetti in the colours of the numbers rain down over the window when the game is won
+    pub(crate) fn celebrate(
+        mut commands: Commands,
+        options: Option<Res<BoardOptions>>,
+        accessibility: Res<AccessibilityOptions>,
+        mut won_evr: EventReader<TyWonEvent>,
+        windows: Query<&Window>,
+        cameras: Query<&Transform, With<Camera>>,
+    ) {
+        if won_evr.read().count() == 0 {
+            return;
+        }
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+        let (Ok(window), Ok(camera)) = (windows.get_single(), cameras.get_single()) else {
+            return;
+        };
+        if !accessibility.animations(options.animations).celebration {
+            return;
+        }
+        let palette = accessibility.palette(&options.palette);
+
+        let size = Vec2::new(window.width(), window.height());
+        let mut rng = rand::thread_rng();
+        for _ in 0..CONFETTI_COUNT {
+            let color = match palette.numbers.len() {
+                0 => Color::WHITE,
+                len => palette.numbers[rng.gen_range(0..len)],
+            };
+            // Starts above the window and falls through it
+            let position = camera.translation.truncate()
+                + Vec2::new(
+                    rng.gen_range(-0.5..0.5) * size.x,
+                    rng.gen_range(0.5..1.) * size.y,
+                );
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(Vec2::new(8., 12.)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(position.extend(PARTICLE_Z))
+                        .with_rotation(Quat::from_rotation_z(rng.gen_range(0. ..TAU))),
+                    ..Default::default()
+                },
+                Name::new("Confetti"),
+                Particle {
+                    velocity: Vec2::new(rng.gen_range(-0.1..0.1), rng.gen_range(-0.4..-0.1))
+                        * size.y,
+                    gravity: size.y * 0.2,
+                    lifetime: Timer::from_seconds(4., TimerMode::Once),
+                },
+            ));
+        }
+    }
+}
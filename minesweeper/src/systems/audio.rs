@@ -0,0 +1,210 @@
+use bevy::{
+    asset::LoadState,
+    audio::{AudioSinkPlayback, Volume},
+    prelude::*,
+};
+
+use crate::{
+    components::music::Music,
+    events::{
+        BombHitEvent, EndlessFlagEvent, EndlessUncoverEvent, TileFlagEvent, TileUncoverEvent,
+    },
+    options::AudioOptions,
+    resources::board::Board,
+    theme::ThemeSounds,
+    MinesweeperPlugin,
+};
+
+/// Windowless apps, like the benchmarks, count as focused
+fn is_focused(windows: &Query<&Window>) -> bool {
+    windows.is_empty() || windows.iter().any(|window| window.focused)
+}
+
+/// Plays a sound once. Nothing is played without the sound or at no volume
+fn play(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    sound: Option<&Handle<AudioSource>>,
+    volume: f32,
+) {
+    let Some(sound) = sound else {
+        return;
+    };
+    // Sounds that couldn't be loaded would wait for their file forever
+    if volume <= 0. || asset_server.get_load_state(sound) == Some(LoadState::Failed) {
+        return;
+    }
+    commands.spawn((
+        AudioBundle {
+            source: sound.clone(),
+            settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(volume)),
+        },
+        Name::new("Sound"),
+    ));
+}
+
+/// Clicks when a covered tile is uncovered, or plays the cascade sound if it opens an empty area.
+/// Runs before the tiles are uncovered, so it can tell which tiles were covered
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn play_uncover_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sounds: Option<Res<ThemeSounds>>,
+    options: Res<AudioOptions>,
+    windows: Query<&Window>,
+    board: Option<Res<Board>>,
+    mut uncover_evr: EventReader<TileUncoverEvent>,
+    mut endless_uncover_evr: EventReader<EndlessUncoverEvent>,
+) {
+    let Some(sounds) = sounds else {
+        return;
+    };
+    let mut sound = None;
+    if let Some(board) = board {
+        for event in uncover_evr.read().filter(|event| !event.cascade) {
+            if !board.covered.contains_key(event) {
+                continue;
+            }
+            // Bombs have their explosion
+            sound = match (
+                board.tile_map[**event].is_bomb(),
+                board.tile_map.bomb_count_at(**event),
+            ) {
+                (true, _) => sound,
+                (false, 0) => sounds.cascade.as_ref().or(sounds.uncover.as_ref()),
+                (false, _) => sound.or(sounds.uncover.as_ref()),
+            };
+        }
+    }
+    if endless_uncover_evr.read().count() != 0 {
+        sound = sound.or(sounds.uncover.as_ref());
+    }
+    play(
+        &mut commands,
+        &asset_server,
+        sound,
+        options.effects_volume(is_focused(&windows)),
+    );
+}
+
+pub(crate) fn play_flag_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sounds: Option<Res<ThemeSounds>>,
+    options: Res<AudioOptions>,
+    windows: Query<&Window>,
+    mut flag_evr: EventReader<TileFlagEvent>,
+    mut endless_flag_evr: EventReader<EndlessFlagEvent>,
+) {
+    let flagged = flag_evr.read().count() + endless_flag_evr.read().count() != 0;
+    let Some(sounds) = sounds.filter(|_| flagged) else {
+        return;
+    };
+    play(
+        &mut commands,
+        &asset_server,
+        sounds.flag.as_ref(),
+        options.effects_volume(is_focused(&windows)),
+    );
+}
+
+pub(crate) fn play_explosion_sound(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    sounds: Option<Res<ThemeSounds>>,
+    options: Res<AudioOptions>,
+    windows: Query<&Window>,
+    mut bomb_hit_evr: EventReader<BombHitEvent>,
+) {
+    let hit = bomb_hit_evr.read().count() != 0;
+    let Some(sounds) = sounds.filter(|_| hit) else {
+        return;
+    };
+    play(
+        &mut commands,
+        &asset_server,
+        sounds.explosion.as_ref(),
+        options.effects_volume(is_focused(&windows)),
+    );
+}
+
+/// Starts the music of the theme, and switches to the new one when the theme changes
+pub fn play_music(
+    mut commands: Commands,
+    sounds: Option<Res<ThemeSounds>>,
+    options: Res<AudioOptions>,
+    windows: Query<&Window>,
+    music: Query<Entity, With<Music>>,
+) {
+    let Some(sounds) = sounds.filter(|sounds| sounds.is_changed()) else {
+        return;
+    };
+    for entity in music.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    let Some(source) = sounds.music.clone() else {
+        return;
+    };
+    commands.spawn((
+        AudioBundle {
+            source,
+            settings: PlaybackSettings::LOOP
+                .with_volume(Volume::new(options.music_volume(is_focused(&windows)))),
+        },
+        Name::new("Music"),
+        Music,
+    ));
+}
+
+/// Keeps everything that plays at the volume of the [AudioOptions], which mute it while the window isn't focused
+pub fn update_volume(
+    options: Res<AudioOptions>,
+    windows: Query<&Window>,
+    sinks: Query<(&AudioSink, Has<Music>)>,
+) {
+    let focused = is_focused(&windows);
+    for (sink, is_music) in sinks.iter() {
+        let volume = match is_music {
+            true => options.music_volume(focused),
+            false => options.effects_volume(focused),
+        };
+        if sink.volume() != volume {
+            sink.set_volume(volume);
+        }
+    }
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub(crate) fn play_game_over_sound(
+        mut commands: Commands,
+        asset_server: Res<AssetServer>,
+        sounds: Option<Res<ThemeSounds>>,
+        options: Res<AudioOptions>,
+        windows: Query<&Window>,
+        mut won_evr: EventReader<TyWonEvent>,
+        mut lost_evr: EventReader<TyLostEvent>,
+    ) {
+        let (won, lost) = (won_evr.read().count() != 0, lost_evr.read().count() != 0);
+        let Some(sounds) = sounds else {
+            return;
+        };
+        let sound = match (won, lost) {
+            (true, _) => sounds.won.as_ref(),
+            (false, true) => sounds.lost.as_ref(),
+            (false, false) => return,
+        };
+        play(
+            &mut commands,
+            &asset_server,
+            sound,
+            options.effects_volume(is_focused(&windows)),
+        );
+    }
+}
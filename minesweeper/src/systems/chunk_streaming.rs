@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+
+use crate::{
+    atlas::TileAtlas,
+    components::{board_marker::BoardMarker, chunk_marker::ChunkMarker, endless_tile::EndlessTile},
+    helpers::refresh_endless_tiles,
+    options::BoardOptions,
+    resources::endless_board::EndlessBoard,
+    theme::ThemeStyle,
+    BACKGROUND_Z,
+};
+
+/// Spawns the chunks of endless boards that come into view and despawns the ones that are far away
+#[allow(clippy::too_many_arguments)]
+pub fn chunk_streaming(
+    mut commands: Commands,
+    mut board: ResMut<EndlessBoard>,
+    options: Option<Res<BoardOptions>>,
+    atlas: Res<TileAtlas>,
+    style: Option<Res<ThemeStyle>>,
+    windows: Query<&Window>,
+    cameras: Query<&Transform, With<Camera>>,
+    roots: Query<Entity, With<BoardMarker>>,
+    mut tiles: Query<&mut TextureAtlas, With<EndlessTile>>,
+) {
+    let (Ok(window), Ok(camera), Ok(root)) = (
+        windows.get_single(),
+        cameras.get_single(),
+        roots.get_single(),
+    ) else {
+        return;
+    };
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    let view_distance = options.endless.unwrap_or_default().view_distance as i32;
+
+    let chunk_world_size = board.chunk_size() as f32 * board.tile_size;
+    let half_window = Vec2::new(window.width(), window.height()) / 2.;
+    let center = camera.translation.xy();
+    let first = ((center - half_window) / chunk_world_size)
+        .floor()
+        .as_ivec2()
+        - view_distance;
+    let last = ((center + half_window) / chunk_world_size)
+        .floor()
+        .as_ivec2()
+        + view_distance;
+
+    // One more chunk stays loaded, so chunks on the border don't flicker in and out
+    let far_away: Vec<IVec2> = board
+        .loaded
+        .keys()
+        .copied()
+        .filter(|chunk| chunk.cmplt(first - 1).any() || chunk.cmpgt(last + 1).any())
+        .collect();
+    for chunk in far_away {
+        if let Some(entity) = board.unload_chunk(chunk) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let mut new_chunks = Vec::new();
+    let mut revealed = Vec::new();
+    for y in first.y..=last.y {
+        for x in first.x..=last.x {
+            let chunk = IVec2::new(x, y);
+            if board.loaded.contains_key(&chunk) {
+                continue;
+            }
+            let entity = commands
+                .spawn((
+                    Name::new(format!("Chunk: ({};{})", x, y)),
+                    SpatialBundle {
+                        transform: Transform::from_translation(
+                            (chunk.as_vec2() * chunk_world_size).extend(0.),
+                        ),
+                        ..Default::default()
+                    },
+                    ChunkMarker(chunk),
+                ))
+                .set_parent(root)
+                .id();
+            revealed.extend(board.load_chunk(chunk, entity));
+            new_chunks.push((chunk, entity));
+        }
+    }
+
+    // Tiles of new chunks are spawned after all of them were loaded, uncovering can spread between them
+    let sprite_size = Vec2::splat(board.tile_size - options.tile_padding);
+    let tile_color = style.map_or(Color::WHITE, |style| style.tile);
+    for (chunk, entity) in new_chunks {
+        let origin = chunk * board.chunk_size();
+        let positions: Vec<IVec2> = board.chunk_tiles(chunk).collect();
+        for position in positions {
+            let index = board.atlas_index(position);
+            let tile = commands
+                .spawn((
+                    SpriteSheetBundle {
+                        sprite: Sprite {
+                            color: tile_color,
+                            custom_size: Some(sprite_size),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(
+                            (((position - origin).as_vec2() + 0.5) * board.tile_size)
+                                .extend(BACKGROUND_Z),
+                        ),
+                        atlas: atlas.sprite(index),
+                        texture: atlas.image.clone(),
+                        ..Default::default()
+                    },
+                    Name::new(format!("Tile: ({};{})", position.x, position.y)),
+                    EndlessTile(position),
+                ))
+                .set_parent(entity)
+                .id();
+            board.tiles.insert(position, tile);
+        }
+    }
+
+    refresh_endless_tiles(&mut board, &mut tiles, revealed);
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{board_marker::BoardMarker, modifier_text::ModifierText, score_text::ScoreText},
+    helpers::start_modifiers,
+    options::{AccessibilityOptions, BoardOptions, TileSize},
+    resources::endless_board::EndlessBoard,
+    states::plugin_state::MinesweeperState,
+    theme::ThemeStyle,
+};
+
+/// Creates an empty endless board. The chunks are spawned by [crate::systems::chunk_streaming::chunk_streaming]
+#[allow(clippy::type_complexity)]
+pub fn create_endless_board(
+    mut commands: Commands,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    style: Option<Res<ThemeStyle>>,
+    old_board: Query<Entity, Or<(With<BoardMarker>, With<ScoreText>, With<ModifierText>)>>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+    mut next_state: ResMut<NextState<MinesweeperState>>,
+) {
+    for entity in old_board.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    let endless = options.endless.clone().unwrap_or_default();
+    let style = match style {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    // There is no board size to fit into the window
+    let tile_size = match accessibility.tile_size(&options.tile_size) {
+        TileSize::Fixed(v) => v,
+        TileSize::Adaptive { max, .. } => max,
+    };
+    let seed = endless.seed.unwrap_or_else(rand::random);
+    bevy::log::info!("Endless board seed: {}", seed);
+
+    let mut board = EndlessBoard::new(seed, endless.density, endless.chunk_size, tile_size);
+    // Opens the start as soon as its chunk is loaded
+    board.uncover(IVec2::ZERO);
+
+    for mut transform in cameras.iter_mut() {
+        transform.translation = Vec3::new(tile_size / 2., tile_size / 2., transform.translation.z);
+    }
+
+    commands.spawn((Name::new("Board"), SpatialBundle::default(), BoardMarker));
+    commands.spawn((
+        TextBundle::from_section(
+            score_text(board.score()),
+            TextStyle {
+                font: style.font.clone(),
+                font_size: 30.,
+                color: style.text,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.),
+            left: Val::Px(10.),
+            ..Default::default()
+        }),
+        Name::new("Score"),
+        ScoreText,
+    ));
+    commands.insert_resource(board);
+    start_modifiers(&mut commands, &options, &style);
+    next_state.set(MinesweeperState::Running);
+}
+
+/// Shows the score whenever the endless board changed
+pub fn update_score(board: Res<EndlessBoard>, mut texts: Query<&mut Text, With<ScoreText>>) {
+    if !board.is_changed() {
+        return;
+    }
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = score_text(board.score());
+    }
+}
+
+fn score_text(score: usize) -> String {
+    format!("Revealed: {}", score)
+}
@@ -0,0 +1,43 @@
+use bevy::{input::mouse::MouseMotion, prelude::*};
+
+use crate::resources::endless_board::EndlessBoard;
+
+/// Tiles per second the camera moves when panning with the keyboard
+const PAN_SPEED: f32 = 20.;
+
+/// Pans the camera over endless boards with WASD, the arrow keys or by dragging with the middle mouse button
+pub fn endless_camera(
+    time: Res<Time>,
+    board: Res<EndlessBoard>,
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    let mut direction = Vec2::ZERO;
+    for (key, arrow, step) in [
+        (KeyCode::KeyW, KeyCode::ArrowUp, Vec2::Y),
+        (KeyCode::KeyS, KeyCode::ArrowDown, Vec2::NEG_Y),
+        (KeyCode::KeyA, KeyCode::ArrowLeft, Vec2::NEG_X),
+        (KeyCode::KeyD, KeyCode::ArrowRight, Vec2::X),
+    ] {
+        if keys.any_pressed([key, arrow]) {
+            direction += step;
+        }
+    }
+    let mut movement =
+        direction.normalize_or_zero() * PAN_SPEED * board.tile_size * time.delta_seconds();
+
+    let dragged: Vec2 = motion_events.read().map(|event| event.delta).sum();
+    if buttons.pressed(MouseButton::Middle) {
+        // The board follows the cursor, screen y points down
+        movement += Vec2::new(-dragged.x, dragged.y);
+    }
+
+    if movement == Vec2::ZERO {
+        return;
+    }
+    for mut transform in cameras.iter_mut() {
+        transform.translation += movement.extend(0.);
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::endless_tile::EndlessTile, events::EndlessFlagEvent,
+    helpers::refresh_endless_tiles, resources::endless_board::EndlessBoard,
+};
+
+/// Puts a flag on covered tiles of endless boards or removes it
+pub fn endless_flag_event_handler(
+    mut board: ResMut<EndlessBoard>,
+    mut flag_evr: EventReader<EndlessFlagEvent>,
+    mut tiles: Query<&mut TextureAtlas, With<EndlessTile>>,
+) {
+    for EndlessFlagEvent(position) in flag_evr.read() {
+        if board.revealed.contains(position) {
+            continue;
+        }
+        if !board.flagged.remove(position) {
+            board.flagged.insert(*position);
+        }
+        refresh_endless_tiles(&mut board, &mut tiles, [*position]);
+    }
+}
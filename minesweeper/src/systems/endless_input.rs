@@ -0,0 +1,61 @@
+use bevy::{input::mouse::MouseButtonInput, prelude::*};
+
+use crate::{
+    events::{EndlessFlagEvent, EndlessUncoverEvent},
+    resources::{
+        endless_board::EndlessBoard,
+        tile_press::{PressAction, TilePress},
+    },
+};
+
+/// Follows the mouse over endless boards like [crate::systems::input::input] does on normal boards. The middle
+/// button pans the camera, so chording takes both buttons
+#[allow(clippy::too_many_arguments)]
+pub fn endless_input(
+    windows: Query<&Window>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut board: ResMut<EndlessBoard>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut press: ResMut<TilePress<IVec2>>,
+    mut button_event_read: EventReader<MouseButtonInput>,
+    mut uncover_ewr: EventWriter<EndlessUncoverEvent>,
+    mut flag_ewr: EventWriter<EndlessFlagEvent>,
+) {
+    let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single())
+    else {
+        return;
+    };
+
+    let hovered = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor))
+        .map(|world_position| (world_position / board.tile_size).floor().as_ivec2());
+    let mut next = TilePress { hovered, ..*press };
+
+    for event in button_event_read.read() {
+        if event.button == MouseButton::Middle {
+            continue;
+        }
+        let action = next.button(event, buttons.pressed(MouseButton::Right));
+        let (Some(action), Some(position)) = (action, hovered) else {
+            continue;
+        };
+        match action {
+            PressAction::Flag => {
+                bevy::log::debug!("Trying to flag tile on {}", position);
+                flag_ewr.send(EndlessFlagEvent(position));
+            }
+            PressAction::Chord => {
+                bevy::log::debug!("Chording on {}", position);
+                for target in board.chord_targets(position) {
+                    uncover_ewr.send(EndlessUncoverEvent(target));
+                }
+            }
+            PressAction::Uncover => {
+                bevy::log::debug!("Trying to uncover tile on {}", position);
+                uncover_ewr.send(EndlessUncoverEvent(position));
+            }
+        }
+    }
+    press.set_if_neq(next);
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::endless_tile::EndlessTile,
+    events::{BombHitEvent, EndlessUncoverEvent},
+    helpers::refresh_endless_tiles,
+    resources::endless_board::{EndlessBoard, Uncovered},
+    MinesweeperPlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Uncovers tiles of endless boards and sends a bomb hit event if it's a bomb. Endless boards can't be won
+    pub(crate) fn endless_uncover_event_handler(
+        mut board: ResMut<EndlessBoard>,
+        mut uncover_evr: EventReader<EndlessUncoverEvent>,
+        mut tiles: Query<&mut TextureAtlas, With<EndlessTile>>,
+        mut bomb_hit_event_writer: EventWriter<BombHitEvent>,
+    ) {
+        for EndlessUncoverEvent(position) in uncover_evr.read() {
+            match board.uncover(*position) {
+                Uncovered::Bomb => {
+                    refresh_endless_tiles(&mut board, &mut tiles, [*position]);
+                    bevy::log::info!("Bomb hit with a score of {}", board.score());
+                    bomb_hit_event_writer.send(BombHitEvent {
+                        position: (position.as_vec2() + 0.5) * board.tile_size,
+                        tile_size: board.tile_size,
+                    });
+                }
+                Uncovered::Tiles(revealed) => {
+                    refresh_endless_tiles(&mut board, &mut tiles, revealed)
+                }
+            }
+        }
+    }
+}
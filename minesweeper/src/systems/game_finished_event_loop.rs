@@ -1,8 +1,7 @@
-use std::ops::Index;
-
 use bevy::prelude::*;
 
 use crate::{
+    options::BoardOptions,
     resources::{board::Board, plugin_options::PluginOptions},
     MinesweeperPlugin, TileFlagEvent, TileUncoverEvent,
 };
@@ -15,44 +14,27 @@ impl<
         TyLostEvent: Event + Clone,
     > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
 {
+    /// Checks the win condition whenever a tile was flagged or uncovered
     pub(crate) fn game_end_loop(
         plugin_options: Res<
             PluginOptions<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>,
         >,
+        options: Option<Res<BoardOptions>>,
         mut tile_flag_event_reader: EventReader<TileFlagEvent>,
         mut tile_uncover_event_reader: EventReader<TileUncoverEvent>,
         mut won_event_writer: EventWriter<TyWonEvent>,
         board: Res<Board>,
     ) {
-        if board.flag_count != *board.tile_map.bomb_count()
-            || (tile_flag_event_reader.read().count() == 0
-                && tile_uncover_event_reader.read().count() == 0)
+        if tile_flag_event_reader.read().count() == 0
+            && tile_uncover_event_reader.read().count() == 0
         {
             return;
         }
 
-        // Check if all flags are placed correctly
-        let mut all_correct_flags = true;
-        for (coord, (_, _, is_flagged)) in board.flagged.iter() {
-            if !is_flagged {
-                continue;
-            }
-            if !board.tile_map.index(*coord).is_bomb() {
-                all_correct_flags = false;
-                break;
-            }
-        }
-
-        // Check if all non bombs tiles are revealed
-        let mut all_correct_covered = true;
-        for (coord, _) in board.covered.iter() {
-            if !board.tile_map.index(*coord).is_bomb() {
-                all_correct_covered = false;
-                break;
-            }
-        }
-
-        if all_correct_covered || all_correct_flags {
+        let win_condition = options
+            .map(|options| options.win_condition())
+            .unwrap_or_default();
+        if win_condition.is_won(&board) {
             won_event_writer.send(plugin_options.won_event.clone());
         }
     }
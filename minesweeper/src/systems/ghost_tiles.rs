@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+use crate::{components::ghost_tile::GhostTile, resources::board::Board};
+
+/// Shows the current state of the source tile on its ghosts whenever the board changed
+pub fn ghost_tiles(board: Res<Board>, mut ghosts: Query<(&GhostTile, &mut TextureAtlas)>) {
+    if !board.is_changed() || ghosts.is_empty() {
+        return;
+    }
+
+    for (ghost, mut atlas) in ghosts.iter_mut() {
+        atlas.index = board.atlas_index(ghost.source);
+    }
+}
@@ -1,16 +1,21 @@
-use bevy::{
-    input::{mouse::MouseButtonInput, ButtonState},
-    prelude::*,
-};
+use bevy::{input::mouse::MouseButtonInput, prelude::*};
 
 use crate::{
     events::{TileFlagEvent, TileUncoverEvent},
-    resources::board::Board,
+    resources::{
+        board::Board,
+        tile_press::{PressAction, TilePress},
+    },
 };
 
+/// Follows the mouse over the board. Flags are placed when the right button is pressed, tiles are uncovered or
+/// chorded when a button is released
+#[allow(clippy::too_many_arguments)]
 pub fn input(
     windows: Query<&Window>,
     board: Res<Board>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut press: ResMut<TilePress>,
     mut button_event_read: EventReader<MouseButtonInput>,
     mut app_exit_events: ResMut<Events<bevy::app::AppExit>>,
     mut tile_trigger_ewr: EventWriter<TileUncoverEvent>,
@@ -36,23 +41,57 @@ pub fn input(
         return;
     };
 
+    let hovered = window
+        .cursor_position()
+        .and_then(|cursor| board.cursor_position(window, cursor));
+    let mut next = TilePress { hovered, ..*press };
+
     for event in button_event_read.read() {
-        if let ButtonState::Pressed = event.state {
-            if let Some(click_position) = window.cursor_position() {
-                if let Some(tile_coordinates) = board.cursor_position(window, click_position) {
-                    match event.button {
-                        MouseButton::Left => {
-                            bevy::log::debug!("Trying to uncover tile on {}", tile_coordinates);
-                            tile_trigger_ewr.send(TileUncoverEvent(tile_coordinates));
-                        }
-                        MouseButton::Right => {
-                            bevy::log::debug!("Trying to flag tile on {}", tile_coordinates);
-                            tile_flag_ewr.send(TileFlagEvent(tile_coordinates));
-                        }
-                        _ => (),
-                    }
+        let action = next.button(event, buttons.pressed(MouseButton::Right));
+        let (Some(action), Some(tile_coordinates)) = (action, hovered) else {
+            continue;
+        };
+        match action {
+            PressAction::Flag => {
+                bevy::log::debug!("Trying to flag tile on {}", tile_coordinates);
+                tile_flag_ewr.send(TileFlagEvent(tile_coordinates));
+            }
+            PressAction::Chord => {
+                bevy::log::debug!("Chording on {}", tile_coordinates);
+                for coordinate in board.chord_targets(tile_coordinates) {
+                    tile_trigger_ewr.send(TileUncoverEvent {
+                        coordinate,
+                        cascade: false,
+                    });
                 }
             }
+            PressAction::Uncover => {
+                bevy::log::debug!("Trying to uncover tile on {}", tile_coordinates);
+                tile_trigger_ewr.send(TileUncoverEvent {
+                    coordinate: tile_coordinates,
+                    cascade: false,
+                });
+            }
+        }
+    }
+    press.set_if_neq(next);
+}
+
+/// Shows a pointer instead of the arrow while the mouse is over a tile of either kind of board
+pub fn board_cursor<C: Send + Sync + 'static>(
+    press: Res<TilePress<C>>,
+    mut windows: Query<&mut Window>,
+) {
+    if !press.is_changed() {
+        return;
+    }
+    let icon = match press.hovered {
+        Some(_) => CursorIcon::Pointer,
+        None => CursorIcon::Default,
+    };
+    for mut window in windows.iter_mut() {
+        if window.cursor.icon != icon {
+            window.cursor.icon = icon;
         }
     }
 }
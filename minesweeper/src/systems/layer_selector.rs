@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        coordinates::CoordinateU16, ghost_tile::GhostTile, layer_label::LayerLabel,
+        tile_chunk::TileChunk, tile_marker::TileMarker,
+    },
+    helpers::{layer_label_text, layer_visibility},
+    resources::board::Board,
+};
+
+/// Switches the shown layer of layered boards with page up and page down
+#[allow(clippy::type_complexity)]
+pub fn layer_selector(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut board: ResMut<Board>,
+    mut tiles: Query<(&CoordinateU16, &mut Visibility), With<TileMarker>>,
+    mut ghosts: Query<(&GhostTile, &mut Visibility), Without<TileMarker>>,
+    mut chunks: Query<(&TileChunk, &mut Visibility), (Without<TileMarker>, Without<GhostTile>)>,
+    mut labels: Query<&mut Text, With<LayerLabel>>,
+) {
+    let depth = *board.tile_map.depth();
+    let layer = if keys.just_pressed(KeyCode::PageUp) {
+        (board.layer + 1).min(depth - 1)
+    } else if keys.just_pressed(KeyCode::PageDown) {
+        board.layer.saturating_sub(1)
+    } else {
+        return;
+    };
+    if layer == board.layer {
+        return;
+    }
+    board.layer = layer;
+
+    for (coordinate, mut visibility) in tiles.iter_mut() {
+        *visibility = layer_visibility(coordinate.z, layer);
+    }
+    for (ghost, mut visibility) in ghosts.iter_mut() {
+        *visibility = layer_visibility(ghost.source.z, layer);
+    }
+    for (chunk, mut visibility) in chunks.iter_mut() {
+        *visibility = layer_visibility(chunk.layer, layer);
+    }
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = layer_label_text(layer, depth);
+    }
+}
@@ -1,6 +1,23 @@
+pub mod accessibility;
+pub mod animations;
+pub mod audio;
+pub mod chunk_streaming;
+pub mod create_endless_board;
+pub mod endless_camera;
+pub mod endless_flag_event_handler;
+pub mod endless_input;
+pub mod endless_uncover_event_handler;
 pub mod game_finished_event_loop;
+pub mod ghost_tiles;
 pub mod input;
+pub mod layer_selector;
+pub mod modifiers;
 pub mod remove_cover;
 pub mod rescale;
+pub mod theme_switcher;
+pub mod tile_atlas;
+pub mod tile_chunks;
+pub mod tile_feedback;
 pub mod tile_flag_event_handler;
+pub mod tile_shapes;
 pub mod tile_uncover_event_handler;
@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::modifier_text::ModifierText,
+    events::BombHitEvent,
+    helpers::current_score,
+    options::BoardOptions,
+    resources::{
+        board::Board, endless_board::EndlessBoard, modifier_state::ModifierState,
+        plugin_options::PluginOptions,
+    },
+    MinesweeperPlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Decides if a bomb hit ends the game. Hits are survived as long as there are lives left
+    pub(crate) fn bomb_hit_event_handler(
+        plugin_options: Res<
+            PluginOptions<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>,
+        >,
+        mut state: ResMut<ModifierState>,
+        mut bomb_hit_evr: EventReader<BombHitEvent>,
+        mut lost_event_writer: EventWriter<TyLostEvent>,
+    ) {
+        for _ in bomb_hit_evr.read() {
+            if !state.survive_hit() {
+                lost_event_writer.send(plugin_options.lost_event.clone());
+                return;
+            }
+            bevy::log::info!("Bomb hit survived, {} lives left", state.lives);
+        }
+    }
+
+    /// Counts down the time limit. When it runs out, the game is lost unless the win condition is met by then
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn countdown(
+        plugin_options: Res<
+            PluginOptions<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>,
+        >,
+        options: Option<Res<BoardOptions>>,
+        time: Res<Time>,
+        board: Option<Res<Board>>,
+        endless_board: Option<Res<EndlessBoard>>,
+        mut state: ResMut<ModifierState>,
+        mut won_event_writer: EventWriter<TyWonEvent>,
+        mut lost_event_writer: EventWriter<TyLostEvent>,
+    ) {
+        // Only changed by a ticking countdown, the text is updated whenever the state changes
+        let Some(countdown) = state.bypass_change_detection().countdown.as_mut() else {
+            return;
+        };
+        let finished = countdown.tick(time.delta()).just_finished();
+        state.set_changed();
+        if !finished {
+            return;
+        }
+
+        let score = current_score(board.as_deref(), endless_board.as_deref());
+        let win_condition = options
+            .map(|options| options.win_condition())
+            .unwrap_or_default();
+        bevy::log::info!("Time is up with a score of {}", score);
+        if win_condition.is_won_on_time_up(score) {
+            won_event_writer.send(plugin_options.won_event.clone());
+        } else {
+            lost_event_writer.send(plugin_options.lost_event.clone());
+        }
+    }
+}
+
+/// Shows the remaining time, lives and score whenever they changed
+pub fn update_modifier_text(
+    state: Res<ModifierState>,
+    options: Option<Res<BoardOptions>>,
+    board: Option<Res<Board>>,
+    endless_board: Option<Res<EndlessBoard>>,
+    mut texts: Query<&mut Text, With<ModifierText>>,
+) {
+    let board_changed = board.as_ref().is_some_and(|board| board.is_changed())
+        || endless_board
+            .as_ref()
+            .is_some_and(|board| board.is_changed());
+    // The score takes a while to count on huge boards
+    if (!state.is_changed() && !board_changed) || texts.is_empty() {
+        return;
+    }
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    let score = current_score(board.as_deref(), endless_board.as_deref());
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = state.text(&options, score).unwrap_or_default();
+    }
+}
@@ -1,29 +1,39 @@
 use bevy::prelude::*;
 
-use crate::components::{tile_cover_marker::TileCoverMarker, uncover_marker::UncoverMarker};
+use crate::components::{
+    cover_animation::CoverAnimation, tile_cover_marker::TileCoverMarker,
+    uncover_marker::UncoverMarker,
+};
 
-/// Removes the cover child of tiles that are marked with the [crate::components::uncover_marker::UncoverMarker] component
+/// Removes the cover child of tiles that are marked with the [crate::components::uncover_marker::UncoverMarker] component.
+/// Covers of tiles with a [CoverAnimation] get it and are removed once it's over
 pub fn remove_cover(
     mut commands: Commands,
-    tiles: Query<(Entity, &Children), With<UncoverMarker>>,
+    tiles: Query<(Entity, &Children, Option<&CoverAnimation>), With<UncoverMarker>>,
     covers: Query<Entity, With<TileCoverMarker>>,
 ) {
     if tiles.is_empty() {
         return;
     }
 
-    for (parent, children) in tiles.iter() {
+    for (parent, children, animation) in tiles.iter() {
         for child in children.iter() {
             let child = match covers.get(*child) {
                 Ok(t) => t,
                 Err(_) => continue,
             };
+            if let Some(animation) = animation {
+                commands.entity(child).insert(*animation);
+                continue;
+            }
             commands.entity(parent).remove_children(&[child]);
             commands.entity(child).despawn_recursive();
         }
     }
 
-    tiles.iter().for_each(|(parent_entity, _)| {
-        commands.entity(parent_entity).remove::<UncoverMarker>();
+    tiles.iter().for_each(|(parent_entity, _, _)| {
+        commands
+            .entity(parent_entity)
+            .remove::<(UncoverMarker, CoverAnimation)>();
     });
 }
@@ -2,14 +2,15 @@ use bevy::{prelude::*, window::WindowResized};
 
 use crate::{
     components::{
-        board_marker::BoardMarker, coordinates::CoordinateU16, tile_cover_marker::TileCoverMarker,
+        board_marker::BoardMarker, coordinates::CoordinateU16, ghost_tile::GhostTile,
+        layer_label::LayerLabel, tile_cover_marker::TileCoverMarker,
     },
-    helpers::adaptive_tile_size,
-    options::{BoardOptions, BoardPosition, TileSize},
+    helpers::{adaptive_tile_size, layer_label_translation, unit_board_size},
+    options::{AccessibilityOptions, BoardOptions, BoardPosition, TileSize},
     resources::{board::Board, bounds::Bounds2},
 };
 
-/// Changes tile size and position if window size changed
+/// Changes tile size and position if window size or the minimum tile size changed
 #[allow(clippy::type_complexity)]
 #[allow(clippy::too_many_arguments)]
 pub fn rescale(
@@ -21,24 +22,50 @@ pub fn rescale(
     mut tiles: Query<
         (
             &mut Transform,
-            &mut Sprite,
+            Option<&mut Sprite>,
             &CoordinateU16,
             Option<&Children>,
         ),
         (Without<BoardMarker>, Without<TileCoverMarker>),
     >,
-    mut covers: Query<(&mut Sprite, &mut Transform), (With<TileCoverMarker>, Without<BoardMarker>)>,
+    mut covers: Query<
+        (Option<&mut Sprite>, &mut Transform),
+        (With<TileCoverMarker>, Without<BoardMarker>),
+    >,
+    mut ghosts: Query<
+        (&GhostTile, &mut Transform, Option<&mut Sprite>),
+        (
+            Without<BoardMarker>,
+            Without<TileCoverMarker>,
+            Without<CoordinateU16>,
+        ),
+    >,
+    mut labels: Query<
+        (&mut Transform, &mut Text),
+        (
+            With<LayerLabel>,
+            Without<BoardMarker>,
+            Without<TileCoverMarker>,
+            Without<CoordinateU16>,
+            Without<GhostTile>,
+        ),
+    >,
     mut board: Option<ResMut<Board>>,
     mut resize_event: EventReader<WindowResized>,
-    windows: Query<Option<&mut Window>>,
+    windows: Query<&Window>,
     options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
 ) {
-    if resize_event.read().count() == 0 {
+    if resize_event.read().count() == 0 && !accessibility.is_changed() {
         return;
     }
 
-    let tile_map = match &board {
-        Some(t) => &t.tile_map,
+    let (topology, map_size, wrap) = match &board {
+        Some(t) => (
+            *t.tile_map.topology(),
+            (*t.tile_map.width(), *t.tile_map.height()),
+            *t.tile_map.wrap(),
+        ),
         None => return,
     };
 
@@ -48,15 +75,16 @@ pub fn rescale(
         if window_count != 1 {
             bevy::log::error!("Can't have more or less than one window.");
             app_exit_events.send(bevy::app::AppExit);
+            return;
         }
     }
 
     #[cfg(not(feature = "debug"))]
-    let window = windows.single().unwrap();
+    let window = windows.single();
 
     // I think the world view thingie counts as a window so we have to do this when debugging
     #[cfg(feature = "debug")]
-    let window = windows.iter().next().unwrap().unwrap();
+    let window = windows.iter().next().unwrap();
 
     let options = match options {
         Some(t) => t.clone(),
@@ -64,18 +92,18 @@ pub fn rescale(
     };
 
     // Compute the tile size
-    let tile_size = match options.tile_size {
+    let tile_size = match accessibility.tile_size(&options.tile_size) {
         TileSize::Fixed(v) => v,
-        TileSize::Adaptive { min, max } => {
-            adaptive_tile_size(window, (min, max), (*tile_map.width(), *tile_map.height()))
-        }
+        TileSize::Adaptive { min, max } => adaptive_tile_size(
+            window,
+            (min, max),
+            unit_board_size(topology, map_size, wrap),
+        ),
     };
 
     // Compute the size of all tiles added together
-    let board_size = Vec2::new(
-        *tile_map.width() as f32 * tile_size,
-        *tile_map.height() as f32 * tile_size,
-    );
+    let board_size = topology.board_size(map_size, tile_size);
+    let sprite_size = topology.tile_sprite_size(tile_size) - Vec2::splat(options.tile_padding);
 
     // Compute board position
     let board_position = match options.position {
@@ -93,31 +121,52 @@ pub fn rescale(
     *board_transform = Transform::from_translation(board_position);
 
     for child_id in children_id.iter() {
-        let (mut transform, mut sprite_parent, coordinate, children) =
-            match tiles.get_mut(*child_id) {
-                Ok(t) => t,
-                Err(_) => continue,
-            };
-        sprite_parent.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
-        *transform = Transform::from_xyz(
-            coordinate.x as f32 * tile_size + tile_size * 0.5,
-            coordinate.y as f32 * tile_size + tile_size * 0.5,
-            crate::BACKGROUND_Z,
+        let (mut transform, sprite_parent, coordinate, children) = match tiles.get_mut(*child_id) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        // Tiles drawn as shapes resize their meshes themselves
+        if let Some(mut sprite_parent) = sprite_parent {
+            sprite_parent.custom_size = Some(sprite_size);
+        }
+        *transform = Transform::from_translation(
+            topology
+                .tile_center(*coordinate, tile_size)
+                .extend(crate::BACKGROUND_Z),
         );
 
         if let Some(children) = children {
             for cover_id in children.iter() {
-                let (mut sprite_cover, mut transform_cover) = match covers.get_mut(*cover_id) {
+                let (sprite_cover, mut transform_cover) = match covers.get_mut(*cover_id) {
                     Ok(t) => t,
                     Err(_) => continue,
                 };
 
-                sprite_cover.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
+                if let Some(mut sprite_cover) = sprite_cover {
+                    sprite_cover.custom_size = Some(sprite_size);
+                }
                 *transform_cover = Transform::from_xyz(0., 0., crate::FOREGROUND_Z)
             }
         }
     }
 
+    let period = topology.wrap_period(map_size, tile_size);
+    for (ghost, mut transform, sprite) in ghosts.iter_mut() {
+        if let Some(mut sprite) = sprite {
+            sprite.custom_size = Some(sprite_size);
+        }
+        *transform = Transform::from_translation(
+            (topology.tile_center(ghost.source, tile_size)
+                + period * Vec2::new(ghost.shift.0 as f32, ghost.shift.1 as f32))
+            .extend(crate::BACKGROUND_Z),
+        );
+    }
+
+    for (mut transform, mut text) in labels.iter_mut() {
+        *transform = Transform::from_translation(layer_label_translation(board_size, tile_size));
+        text.sections[0].style.font_size = tile_size * 0.8;
+    }
+
     // unwrap here is okay because we already return if this is none
     board.as_mut().unwrap().tile_size = tile_size;
     board.as_mut().unwrap().bounds = Bounds2 {
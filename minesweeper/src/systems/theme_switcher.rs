@@ -0,0 +1,91 @@
+use bevy::{asset::LoadState, prelude::*};
+
+use crate::{
+    components::{
+        endless_tile::EndlessTile, layer_label::LayerLabel, modifier_text::ModifierText,
+        score_text::ScoreText, tile_cover_marker::TileCoverMarker, tile_marker::TileMarker,
+    },
+    options::BoardOptions,
+    resources::pending_theme::PendingTheme,
+    theme::{Theme, ThemeHandle, BUNDLED_THEMES},
+};
+
+/// Starts loading the next bundled theme when T is pressed
+pub fn switch_theme(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    current: Option<Res<ThemeHandle>>,
+) {
+    if !keys.just_pressed(KeyCode::KeyT) {
+        return;
+    }
+
+    let current_path = current
+        .and_then(|current| asset_server.get_path(current.0.id()))
+        .map(|path| path.path().to_string_lossy().replace('\\', "/"));
+    // Themes that aren't bundled are followed by the first bundled theme
+    let next = BUNDLED_THEMES
+        .iter()
+        .position(|theme| Some(*theme) == current_path.as_deref())
+        .map_or(0, |index| (index + 1) % BUNDLED_THEMES.len());
+
+    bevy::log::info!("Loading theme {}", BUNDLED_THEMES[next]);
+    commands.insert_resource(PendingTheme(asset_server.load(BUNDLED_THEMES[next])));
+}
+
+/// Replaces the current theme with the pending one once it's loaded and re-tints the board.
+/// The new textures are swapped in by rebuilding the [crate::atlas::TileAtlas]
+#[allow(clippy::type_complexity)]
+pub fn apply_pending_theme(
+    mut commands: Commands,
+    pending: Option<Res<PendingTheme>>,
+    asset_server: Res<AssetServer>,
+    themes: Res<Assets<Theme>>,
+    options: Option<Res<BoardOptions>>,
+    mut sprites: Query<
+        (&mut Sprite, Has<TileCoverMarker>),
+        Or<(With<TileMarker>, With<TileCoverMarker>, With<EndlessTile>)>,
+    >,
+    mut texts: Query<&mut Text, Or<(With<LayerLabel>, With<ScoreText>, With<ModifierText>)>>,
+) {
+    let Some(pending) = pending else {
+        return;
+    };
+    let state = match themes.get(&pending.0) {
+        Some(theme) => theme.load_state(&asset_server),
+        None => asset_server
+            .get_load_state(&pending.0)
+            .unwrap_or(LoadState::Failed),
+    };
+    let theme = match (state, themes.get(&pending.0)) {
+        (LoadState::Loaded, Some(theme)) => theme,
+        (LoadState::Failed, _) => {
+            bevy::log::error!("Couldn't load the theme, keeping the current one.");
+            commands.remove_resource::<PendingTheme>();
+            return;
+        }
+        _ => return,
+    };
+    commands.remove_resource::<PendingTheme>();
+
+    let topology = options
+        .map(|options| options.tile_topology())
+        .unwrap_or_default();
+
+    for (mut sprite, is_cover) in sprites.iter_mut() {
+        sprite.color = match is_cover {
+            true => theme.style.cover,
+            false => theme.style.tile,
+        };
+    }
+    for mut text in texts.iter_mut() {
+        for section in text.sections.iter_mut() {
+            section.style.color = theme.style.text;
+            section.style.font = theme.style.font.clone();
+        }
+    }
+
+    theme.insert_resources(&mut commands, pending.0.clone(), topology);
+    bevy::log::info!("Switched to the {} theme", theme.name);
+}
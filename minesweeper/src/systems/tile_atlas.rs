@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use crate::{atlas::TileAtlas, texture_handles::TextureHandles};
+
+/// Packs the tile textures into a new atlas whenever they change. Sprites and meshes of the old atlas get the
+/// new one, so switching the theme doesn't need to touch single tiles
+pub fn build_tile_atlas(
+    mut commands: Commands,
+    textures: Res<TextureHandles>,
+    old_atlas: Option<Res<TileAtlas>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut sprites: Query<(&mut Handle<Image>, &mut TextureAtlas)>,
+) {
+    let atlas = match TileAtlas::build(&textures, &mut images, &mut layouts) {
+        Ok(t) => t,
+        Err(e) => {
+            bevy::log::error!("Couldn't pack the tile textures: {}", e);
+            return;
+        }
+    };
+
+    if let Some(old_atlas) = old_atlas {
+        for (mut image, mut sprite_atlas) in sprites.iter_mut() {
+            if *image == old_atlas.image {
+                *image = atlas.image.clone();
+                sprite_atlas.layout = atlas.layout.clone();
+            }
+        }
+        // Chunked boards share one material
+        for (_, material) in materials.iter_mut() {
+            if material.texture.as_ref() == Some(&old_atlas.image) {
+                material.texture = Some(atlas.image.clone());
+            }
+        }
+    }
+    commands.insert_resource(atlas);
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use bevy::{prelude::*, render::primitives::Aabb, sprite::Mesh2dHandle};
+
+use crate::{
+    atlas::TileAtlas,
+    components::{
+        coordinates::CoordinateU16, tile_chunk::TileChunk, uncover_marker::UncoverMarker,
+    },
+    options::BoardOptions,
+    resources::{
+        board::Board,
+        tile_press::{CoverLook, TilePress},
+    },
+    theme::ThemeStyle,
+    tile_mesh::chunk_mesh,
+};
+
+/// Rebuilds the meshes of chunked boards whose tiles changed. Chunks take the place of the tile entities,
+/// so they get the uncover markers of their tiles and are marked as changed when a tile gets flagged or the mouse
+/// is over one of their covers
+#[allow(clippy::too_many_arguments)]
+pub fn update_tile_chunks(
+    mut commands: Commands,
+    board: Res<Board>,
+    press: Option<Res<TilePress>>,
+    options: Option<Res<BoardOptions>>,
+    atlas: Res<TileAtlas>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+    style: Option<Res<ThemeStyle>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut chunks: Query<(Entity, &mut TileChunk, &Mesh2dHandle, Has<UncoverMarker>)>,
+) {
+    // A new theme changes every tile. New boards are already built with the current theme
+    let restyled = !board.is_added()
+        && (atlas.is_changed() || style.as_ref().is_some_and(|style| style.is_changed()));
+    let Some(layout) = layouts.get(&atlas.layout) else {
+        return;
+    };
+    let style = match style {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    let padding = options.map_or(0., |options| options.tile_padding);
+    let looks: HashMap<CoordinateU16, CoverLook> = press
+        .as_ref()
+        .map(|press| press.looks(&board).into_iter().collect())
+        .unwrap_or_default();
+    // Chunks with covers under the mouse are marked as changed when the mouse moves
+    let pressed = press.is_some_and(|press| press.is_changed());
+    let topology = *board.tile_map.topology();
+    let sprite_size = topology.tile_sprite_size(board.tile_size) - Vec2::splat(padding);
+
+    for (entity, mut chunk, mesh, uncovered) in chunks.iter_mut() {
+        if uncovered {
+            commands.entity(entity).remove::<UncoverMarker>();
+        }
+        let resized = chunk.tile_size != board.tile_size;
+        let flagged = chunk.is_changed() && !chunk.is_added();
+        if !restyled && !resized && !uncovered && !flagged {
+            continue;
+        }
+        let shown: Vec<usize> = chunk
+            .tiles
+            .iter()
+            .map(|coordinate| board.atlas_index(*coordinate))
+            .collect();
+        if !restyled && !resized && !pressed && shown == chunk.shown {
+            continue;
+        }
+
+        if let Some(mesh) = meshes.get_mut(&mesh.0) {
+            *mesh = chunk_mesh(
+                &chunk.tiles,
+                |coordinate| {
+                    let color = match board.covered.contains_key(&coordinate) {
+                        true => looks
+                            .get(&coordinate)
+                            .copied()
+                            .unwrap_or_default()
+                            .tint(style.cover),
+                        false => style.tile,
+                    };
+                    (board.atlas_index(coordinate), color)
+                },
+                topology,
+                board.tile_size,
+                sprite_size,
+                layout,
+            );
+        }
+        if resized {
+            // The bounds used for culling are calculated again from the new mesh
+            commands.entity(entity).remove::<Aabb>();
+        }
+        chunk.shown = shown;
+        chunk.tile_size = board.tile_size;
+    }
+}
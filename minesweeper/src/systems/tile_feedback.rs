@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        coordinates::CoordinateU16, endless_tile::EndlessTile, tile_chunk::TileChunk,
+        tile_cover_marker::TileCoverMarker,
+    },
+    resources::{
+        board::Board,
+        endless_board::EndlessBoard,
+        tile_press::{CoverLook, TilePress},
+        tile_shapes::TileShapes,
+    },
+    theme::ThemeStyle,
+};
+
+/// Tints the covers under the mouse. Only the covers whose look changed are touched, chunks are marked as changed
+/// so they are drawn again with the new looks
+#[allow(clippy::type_complexity)]
+pub fn show_tile_feedback(
+    press: Res<TilePress>,
+    board: Res<Board>,
+    style: Option<Res<ThemeStyle>>,
+    shapes: Option<Res<TileShapes>>,
+    // Covers that don't look normal right now
+    mut shown: Local<Vec<CoordinateU16>>,
+    mut covers: Query<
+        (Option<&mut Sprite>, Option<&mut Handle<ColorMaterial>>),
+        With<TileCoverMarker>,
+    >,
+    mut chunks: Query<&mut TileChunk>,
+) {
+    if !press.is_changed() && !board.is_changed() {
+        return;
+    }
+    let cover_color = style.map_or(Color::WHITE, |style| style.cover);
+
+    let looks = press.looks(&board);
+    let normal = shown
+        .drain(..)
+        .filter(|coordinate| !looks.iter().any(|(shown, _)| shown == coordinate))
+        .map(|coordinate| (coordinate, CoverLook::Normal))
+        .collect::<Vec<_>>();
+    for (coordinate, look) in normal.into_iter().chain(looks) {
+        // Only covered tiles have a cover
+        let Some((_, cover, _)) = board.flagged.get(&coordinate) else {
+            continue;
+        };
+        if look != CoverLook::Normal {
+            shown.push(coordinate);
+        }
+        if let Ok((sprite, material)) = covers.get_mut(*cover) {
+            if let Some(mut sprite) = sprite {
+                sprite.color = look.tint(cover_color);
+            } else if let (Some(mut material), Some(shapes)) = (material, shapes.as_ref()) {
+                *material = shapes.cover_material(look);
+            }
+        } else if let Ok(mut chunk) = chunks.get_mut(*cover) {
+            chunk.set_changed();
+        }
+    }
+}
+
+/// Tints the tiles of endless boards under the mouse. Their covered tiles have no separate cover, the tile is tinted
+pub fn show_endless_tile_feedback(
+    press: Res<TilePress<IVec2>>,
+    board: Res<EndlessBoard>,
+    style: Option<Res<ThemeStyle>>,
+    // Tiles that don't look normal right now
+    mut shown: Local<Vec<IVec2>>,
+    mut tiles: Query<&mut Sprite, With<EndlessTile>>,
+) {
+    if !press.is_changed() && !board.is_changed() {
+        return;
+    }
+    let tile_color = style.map_or(Color::WHITE, |style| style.tile);
+
+    let looks = press.endless_looks(&board);
+    let normal = shown
+        .drain(..)
+        .filter(|position| !looks.iter().any(|(shown, _)| shown == position))
+        .map(|position| (position, CoverLook::Normal))
+        .collect::<Vec<_>>();
+    for (position, look) in normal.into_iter().chain(looks) {
+        if look != CoverLook::Normal {
+            shown.push(position);
+        }
+        let Some(mut sprite) = board
+            .tiles
+            .get(&position)
+            .and_then(|entity| tiles.get_mut(*entity).ok())
+        else {
+            continue;
+        };
+        sprite.color = look.tint(tile_color);
+    }
+}
@@ -1,33 +1,48 @@
 use bevy::prelude::*;
 
 use crate::{
-    components::tile_cover_marker::TileCoverMarker, events::TileFlagEvent, resources::board::Board,
-    texture_handles::TextureHandles,
+    atlas::TileAtlas,
+    components::{flag_drop::FlagDrop, tile_chunk::TileChunk, tile_cover_marker::TileCoverMarker},
+    events::TileFlagEvent,
+    options::{AccessibilityOptions, BoardOptions},
+    resources::board::Board,
 };
 
+#[allow(clippy::too_many_arguments)]
 pub fn tile_flag_event_handler(
+    mut commands: Commands,
     mut board: ResMut<Board>,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    time: Res<Time>,
     mut tile_flag_evr: EventReader<TileFlagEvent>,
-    mut query: Query<&mut Handle<Image>, With<TileCoverMarker>>,
-    textures: Res<TextureHandles>,
+    mut query: Query<&mut TextureAtlas, With<TileCoverMarker>>,
+    mut chunks: Query<&mut TileChunk>,
 ) {
+    let flag_drop = accessibility
+        .animations(
+            options
+                .map(|options| options.animations)
+                .unwrap_or_default(),
+        )
+        .flag_drop;
     for event in tile_flag_evr.read() {
-        if let Some((_, cover, bool)) = board.try_toggle_flag(event) {
-            let mut texture = match query.get_mut(cover) {
-                Ok(t) => t,
-                Err(_) => continue,
-            };
-
-            match bool {
-                true => {
-                    *texture = textures.tile_flag.clone();
-                    board.flag_count += 1;
-                }
-
-                false => {
-                    *texture = textures.tile_base.clone();
-                    board.flag_count -= 1;
+        if let Some((_, cover, flags)) = board.try_add_flag(event) {
+            if let Ok(mut atlas) = query.get_mut(cover) {
+                atlas.index = TileAtlas::flags(flags);
+                if flag_drop && flags != 0 {
+                    commands.entity(cover).insert(FlagDrop {
+                        start: time.elapsed(),
+                    });
                 }
+            } else if let Ok(mut chunk) = chunks.get_mut(cover) {
+                // Chunked boards have no cover entities, the chunk redraws its tiles
+                chunk.set_changed();
+            }
+            match flags {
+                // All flags were removed
+                0 => board.flag_count -= *board.tile_map.max_bombs_per_tile() as u16,
+                _ => board.flag_count += 1,
             };
         }
     }
@@ -0,0 +1,192 @@
+use bevy::{prelude::*, sprite::Mesh2dHandle};
+
+use crate::{
+    atlas::TileAtlas,
+    components::{
+        coordinates::CoordinateU16, ghost_tile::GhostTile, tile_cover_marker::TileCoverMarker,
+        tile_drawing::TileDrawing, tile_marker::TileMarker, tile_shape::TileShape,
+    },
+    options::{AccessibilityOptions, BoardOptions, BoardRendering},
+    resources::{board::Board, tile_shapes::TileShapes},
+    theme::ThemeStyle,
+    GHOST_COLOR,
+};
+
+/// Draws the numbers, bombs and flags of tiles drawn as shapes whenever they show something else. Resizing the
+/// tiles, switching the theme or changing the accessibility settings draws all of them again
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn draw_tile_shapes(
+    mut commands: Commands,
+    board: Res<Board>,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    style: Option<Res<ThemeStyle>>,
+    mut shapes: ResMut<TileShapes>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut tiles: Query<(
+        Entity,
+        Ref<TextureAtlas>,
+        &TileShape,
+        &mut Handle<ColorMaterial>,
+        Option<&Children>,
+    )>,
+    drawings: Query<(), With<TileDrawing>>,
+) {
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    let palette = accessibility.palette(&options.palette);
+    let restyled =
+        style.as_ref().is_some_and(|style| style.is_changed()) || accessibility.is_changed();
+    let style = match style {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    let sprite_size =
+        shapes.topology.tile_sprite_size(board.tile_size) - Vec2::splat(options.tile_padding);
+    let resized = sprite_size != shapes.sprite_size;
+    if resized {
+        shapes.resize(sprite_size, &mut meshes);
+    }
+    if restyled {
+        shapes.set_colors(&palette, &style, &mut materials);
+    }
+    // Tiles only show something else after the board changed
+    if !board.is_changed() && !resized && !restyled {
+        return;
+    }
+
+    for (entity, atlas, shape, mut material, children) in tiles.iter_mut() {
+        if !atlas.is_changed() && !resized && !restyled {
+            continue;
+        }
+        let new_material = shapes.material(atlas.index, shape.ghost);
+        if *material != new_material {
+            *material = new_material;
+        }
+        for child in children.into_iter().flatten() {
+            if drawings.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        commands.entity(entity).with_children(|parent| {
+            shapes.spawn_drawings(
+                parent,
+                shape,
+                atlas.index,
+                &palette,
+                accessibility.number_patterns,
+                &style.font,
+            )
+        });
+    }
+}
+
+/// Turns the sprites of a board into shapes when the accessibility settings need shapes, see
+/// [AccessibilityOptions::rendering], and back when they don't anymore. The shapes are drawn by [draw_tile_shapes]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub fn switch_tile_rendering(
+    mut commands: Commands,
+    board: Res<Board>,
+    options: Option<Res<BoardOptions>>,
+    accessibility: Res<AccessibilityOptions>,
+    style: Option<Res<ThemeStyle>>,
+    atlas: Res<TileAtlas>,
+    shapes: Option<Res<TileShapes>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    tiles: Query<
+        (
+            Entity,
+            &TextureAtlas,
+            Option<&CoordinateU16>,
+            Option<&GhostTile>,
+            Has<TileCoverMarker>,
+            Option<&Children>,
+        ),
+        Or<(With<TileMarker>, With<TileCoverMarker>, With<GhostTile>)>,
+    >,
+    drawings: Query<(), With<TileDrawing>>,
+) {
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    // Only boards of sprites are switched, the others are drawn the way the options say
+    if options.rendering != BoardRendering::Sprites {
+        return;
+    }
+    let to_shapes = accessibility.rendering(options.rendering) == BoardRendering::Shapes;
+    if to_shapes == shapes.is_some() {
+        return;
+    }
+    let style = match style {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+    let topology = options.topology;
+    let sprite_size =
+        topology.tile_sprite_size(board.tile_size) - Vec2::splat(options.tile_padding);
+    let new_shapes = to_shapes.then(|| {
+        TileShapes::new(
+            topology,
+            sprite_size,
+            &accessibility.palette(&options.palette),
+            &style,
+            &mut meshes,
+            &mut materials,
+        )
+    });
+
+    for (entity, tile_atlas, coordinate, ghost, is_cover, children) in tiles.iter() {
+        let Some(coordinate) = ghost.map(|ghost| ghost.source).or(coordinate.copied()) else {
+            continue;
+        };
+        let mut entity_commands = commands.entity(entity);
+        match &new_shapes {
+            Some(shapes) => {
+                let shape = TileShape {
+                    upside_down: topology.is_upside_down(coordinate),
+                    ghost: ghost.is_some(),
+                };
+                entity_commands.remove::<(Sprite, Handle<Image>)>().insert((
+                    Mesh2dHandle(shapes.mesh(&shape)),
+                    shapes.material(tile_atlas.index, shape.ghost),
+                    shape,
+                ));
+            }
+            None => {
+                entity_commands
+                    .remove::<(Mesh2dHandle, Handle<ColorMaterial>, TileShape)>()
+                    .insert((
+                        Sprite {
+                            color: match (ghost.is_some(), is_cover) {
+                                (true, _) => GHOST_COLOR,
+                                (false, true) => style.cover,
+                                (false, false) => style.tile,
+                            },
+                            custom_size: Some(sprite_size),
+                            rect: topology.texture_rect(coordinate),
+                            ..Default::default()
+                        },
+                        atlas.image.clone(),
+                    ));
+                for child in children.into_iter().flatten() {
+                    if drawings.contains(*child) {
+                        commands.entity(*child).despawn_recursive();
+                    }
+                }
+            }
+        }
+    }
+
+    match new_shapes {
+        Some(shapes) => commands.insert_resource(shapes),
+        None => commands.remove_resource::<TileShapes>(),
+    }
+}
@@ -1,11 +1,14 @@
-use std::ops::Index;
+use std::{collections::HashMap, ops::Index, time::Duration};
 
 use bevy::{ecs::event::ManualEventReader, prelude::*};
 
 use crate::{
-    components::uncover_marker::UncoverMarker,
-    events::TileUncoverEvent,
-    resources::{board::Board, plugin_options::PluginOptions},
+    components::{
+        coordinates::CoordinateU16, cover_animation::CoverAnimation, uncover_marker::UncoverMarker,
+    },
+    events::{BombHitEvent, TileUncoverEvent},
+    options::{AccessibilityOptions, BoardOptions, BoardRendering, UncoverAnimation},
+    resources::board::Board,
     MinesweeperPlugin,
 };
 
@@ -17,36 +20,73 @@ impl<
         TyLostEvent: Event + Clone,
     > MinesweeperPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
 {
-    /// Handles the uncovering of tiles and sends a lost event if it's a bomb
+    /// Handles the uncovering of tiles and sends a bomb hit event if it's a bomb
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn tile_uncover_event_handler(
-        plugin_options: Res<
-            PluginOptions<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>,
-        >,
         mut commands: Commands,
         mut board: ResMut<Board>,
+        options: Option<Res<BoardOptions>>,
+        accessibility: Res<AccessibilityOptions>,
+        time: Res<Time>,
         mut local_event_reader: Local<ManualEventReader<TileUncoverEvent>>,
+        // When the covers of tiles that are uncovered around empty tiles start to disappear
+        mut ripple_starts: Local<HashMap<CoordinateU16, Duration>>,
         mut tile_trigger_events: ResMut<Events<TileUncoverEvent>>,
-        mut lost_event_writer: EventWriter<TyLostEvent>,
+        mut bomb_hit_event_writer: EventWriter<BombHitEvent>,
     ) {
+        let (animations, rendering) = options
+            .map(|options| (options.animations, options.rendering))
+            .unwrap_or_default();
+        let animations = accessibility.animations(animations);
+        // Chunks have no cover entities that could be animated
+        let animated = animations.uncover != UncoverAnimation::None
+            && !matches!(rendering, BoardRendering::Chunks { .. });
         let mut events = Vec::new();
 
         for event in local_event_reader.read(&tile_trigger_events) {
+            let start = ripple_starts
+                .remove(&**event)
+                .unwrap_or_else(|| time.elapsed());
+            let animation = animated.then_some(CoverAnimation { start });
             // Returns true if entity is some
-            if !add_uncover_marker(&mut commands, board.try_uncover(event)) {
+            if !add_uncover_marker(&mut commands, board.try_uncover(event), animation) {
                 continue;
             }
-            if board.tile_map.index(**event).is_bomb() {
-                lost_event_writer.send(plugin_options.lost_event.clone());
+            let tile = *board.tile_map.index(**event);
+            if tile.is_bomb() {
+                // The bomb stays uncovered if the hit is survived, its flags aren't needed anymore
+                if let Some((_, _, flags)) = board.flagged.remove(event) {
+                    board.flag_count -= flags as u16;
+                }
+                board.exploded += tile.bomb_count() as u16;
+                bomb_hit_event_writer.send(BombHitEvent {
+                    position: board.bounds.origin
+                        + board
+                            .tile_map
+                            .topology()
+                            .tile_center(**event, board.tile_size),
+                    tile_size: board.tile_size,
+                });
+                continue;
             }
             if board.tile_map.bomb_count_at(**event) != 0 {
                 continue;
             }
             board.tile_map.neighbour_coordinates(**event).for_each(|x| {
-                events.push(TileUncoverEvent(x));
+                // The first tile that reaches a neighbour is the closest to the clicked tile
+                ripple_starts.entry(x).or_insert(start + animations.ripple);
+                events.push(TileUncoverEvent {
+                    coordinate: x,
+                    cascade: true,
+                });
             });
             board.flagged.remove(event);
         }
 
+        // Starts of tiles that were never uncovered, like after a reset, don't belong to the next click
+        if events.is_empty() {
+            ripple_starts.clear();
+        }
         events.into_iter().for_each(|x| {
             tile_trigger_events.send(x);
         })
@@ -54,9 +94,16 @@ impl<
 }
 
 #[inline(always)]
-fn add_uncover_marker(commands: &mut Commands, entity: Option<Entity>) -> bool {
+fn add_uncover_marker(
+    commands: &mut Commands,
+    entity: Option<Entity>,
+    animation: Option<CoverAnimation>,
+) -> bool {
     if let Some(entity) = entity {
         commands.entity(entity).insert(UncoverMarker);
+        if let Some(animation) = animation {
+            commands.entity(entity).insert(animation);
+        }
         return true;
     }
     false
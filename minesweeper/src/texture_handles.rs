@@ -2,16 +2,55 @@ use bevy::prelude::*;
 
 #[derive(Debug, Clone, Resource)]
 pub struct TextureHandles {
-    pub tile_0: Handle<Image>,
-    pub tile_1: Handle<Image>,
-    pub tile_2: Handle<Image>,
-    pub tile_3: Handle<Image>,
-    pub tile_4: Handle<Image>,
-    pub tile_5: Handle<Image>,
-    pub tile_6: Handle<Image>,
-    pub tile_7: Handle<Image>,
-    pub tile_8: Handle<Image>,
+    /// Uncovered tiles by their number of bomb neighbours, from 0 up to [TextureHandles::MAX_NUMBER]
+    pub tile_numbers: Vec<Handle<Image>>,
     pub tile_base: Handle<Image>,
     pub tile_bomb: Handle<Image>,
     pub tile_flag: Handle<Image>,
+    /// Covers with more than one flag, from 2 up to [TextureHandles::MAX_FLAGS] flags
+    pub tile_multi_flags: Vec<Handle<Image>>,
+}
+
+impl TextureHandles {
+    /// Highest number of bomb neighbours that has a texture. Layered boards with the radius 2 neighbourhood have the
+    /// most neighbours
+    pub const MAX_NUMBER: u8 = 74;
+    /// Highest number of flags on one tile that has a texture
+    pub const MAX_FLAGS: u8 = 5;
+
+    /// File names of the textures. The texture folder of every [crate::topology::Topology] has the same files
+    pub fn files() -> impl Iterator<Item = String> {
+        (0..=Self::MAX_NUMBER)
+            .map(|number| format!("{}.png", number))
+            .chain(["Base.png", "Bomb.png", "Flag.png"].map(String::from))
+            .chain((2..=Self::MAX_FLAGS).map(|flags| format!("Flag{}.png", flags)))
+    }
+
+    /// Gets the handles of the textures in a folder inside the assets folder
+    pub fn load(asset_server: &AssetServer, folder: &str) -> Self {
+        Self::load_with(|file| asset_server.load::<Image>(format!("./{}/{}", folder, file)))
+    }
+
+    /// Gets the handle of every texture file from `load`, which also works for asset loaders
+    pub fn load_with(mut load: impl FnMut(&str) -> Handle<Image>) -> Self {
+        Self {
+            tile_numbers: (0..=Self::MAX_NUMBER)
+                .map(|number| load(&format!("{}.png", number)))
+                .collect(),
+            tile_base: load("Base.png"),
+            tile_bomb: load("Bomb.png"),
+            tile_flag: load("Flag.png"),
+            tile_multi_flags: (2..=Self::MAX_FLAGS)
+                .map(|flags| load(&format!("Flag{}.png", flags)))
+                .collect(),
+        }
+    }
+
+    /// All handles, in the same order as [TextureHandles::files]
+    pub fn handles(&self) -> impl Iterator<Item = &Handle<Image>> {
+        self.tile_numbers
+            .iter()
+            .chain([&self.tile_base, &self.tile_bomb, &self.tile_flag])
+            .chain(self.tile_multi_flags.iter())
+    }
 }
@@ -0,0 +1,408 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState, UntypedAssetId},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{aseprite, texture_handles::TextureHandles, topology::Topology};
+
+/// The bundled theme that is played with while high contrast is switched on
+pub const HIGH_CONTRAST_THEME: &str = "minesweeper/themes/high_contrast.theme.ron";
+
+/// Themes that ship with the game, by their path inside the assets folder. Every one of them is checked in the tests
+pub const BUNDLED_THEMES: [&str; 3] = [
+    "minesweeper/themes/classic.theme.ron",
+    "minesweeper/themes/dark.theme.ron",
+    HIGH_CONTRAST_THEME,
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum ThemeError {
+    #[error("Couldn't read the theme file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Couldn't parse the theme file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("The {name} colour '{value}' isn't a hex colour")]
+    Color { name: &'static str, value: String },
+}
+
+/// The theme file as it's written. Textures, fonts and sounds are paths inside the assets folder
+#[derive(Debug, Clone, Deserialize)]
+pub struct ThemeManifest {
+    pub name: String,
+    /// Folders with the tile textures of every topology. Every folder has all [TextureHandles::files]
+    pub textures: ThemeTextures,
+    pub colors: ThemeColors,
+    #[serde(default)]
+    pub font: Option<String>,
+    #[serde(default)]
+    pub sounds: ThemeSoundPaths,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ThemeTextures {
+    pub square: String,
+    pub hexagon: String,
+    pub triangle: String,
+    #[serde(default)]
+    pub sources: ThemeSources,
+}
+
+/// Aseprite files that are read directly. Their layers are used instead of the PNGs of their textures, the PNGs
+/// are still used if a file can't be read
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeSources {
+    pub square: Option<String>,
+    pub hexagon: Option<String>,
+    pub triangle: Option<String>,
+}
+
+/// Hex colours like `#rrggbb` or `#rrggbbaa`
+#[derive(Debug, Clone, Deserialize)]
+pub struct ThemeColors {
+    pub background: String,
+    /// Tint of uncovered tiles
+    pub tile: String,
+    /// Tint of the covers
+    pub cover: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThemeSoundPaths {
+    pub uncover: Option<String>,
+    /// Played instead of `uncover` when a click uncovers an empty area
+    pub cascade: Option<String>,
+    pub flag: Option<String>,
+    pub explosion: Option<String>,
+    pub won: Option<String>,
+    pub lost: Option<String>,
+    /// Loops in the background
+    pub music: Option<String>,
+}
+
+/// A loaded theme. Loaded with the [ThemeLoader] from `.theme.ron` files
+#[derive(Debug, Clone, Asset, TypePath)]
+pub struct Theme {
+    pub name: String,
+    pub square: TextureHandles,
+    pub hexagon: TextureHandles,
+    pub triangle: TextureHandles,
+    pub style: ThemeStyle,
+    pub sounds: ThemeSounds,
+}
+
+/// Colours and font of the current theme
+#[derive(Debug, Clone, Resource)]
+pub struct ThemeStyle {
+    pub background: Color,
+    pub tile: Color,
+    pub cover: Color,
+    pub text: Color,
+    pub font: Handle<Font>,
+}
+
+/// Sounds of the current theme. Themes don't need to have every sound
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ThemeSounds {
+    pub uncover: Option<Handle<AudioSource>>,
+    pub cascade: Option<Handle<AudioSource>>,
+    pub flag: Option<Handle<AudioSource>>,
+    pub explosion: Option<Handle<AudioSource>>,
+    pub won: Option<Handle<AudioSource>>,
+    pub lost: Option<Handle<AudioSource>>,
+    pub music: Option<Handle<AudioSource>>,
+}
+
+impl ThemeSounds {
+    pub fn ids(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        [
+            &self.uncover,
+            &self.cascade,
+            &self.flag,
+            &self.explosion,
+            &self.won,
+            &self.lost,
+            &self.music,
+        ]
+        .into_iter()
+        .flatten()
+        .map(|handle| handle.id().untyped())
+    }
+}
+
+/// The theme that is used right now
+#[derive(Debug, Clone, Resource)]
+pub struct ThemeHandle(pub Handle<Theme>);
+
+impl Default for ThemeStyle {
+    fn default() -> Self {
+        Self {
+            background: ClearColor::default().0,
+            tile: Color::WHITE,
+            cover: Color::WHITE,
+            text: Color::WHITE,
+            font: Default::default(),
+        }
+    }
+}
+
+impl ThemeColors {
+    /// Checks that every colour is valid
+    pub fn parse(&self) -> Result<(Color, Color, Color, Color), ThemeError> {
+        let parse = |name: &'static str, value: &str| {
+            Color::hex(value).map_err(|_| ThemeError::Color {
+                name,
+                value: value.to_string(),
+            })
+        };
+        Ok((
+            parse("background", &self.background)?,
+            parse("tile", &self.tile)?,
+            parse("cover", &self.cover)?,
+            parse("text", &self.text)?,
+        ))
+    }
+}
+
+impl Theme {
+    pub fn textures(&self, topology: Topology) -> &TextureHandles {
+        match topology {
+            Topology::Square => &self.square,
+            Topology::Hexagon => &self.hexagon,
+            Topology::Triangle => &self.triangle,
+        }
+    }
+
+    /// Every texture, font and sound of the theme, to follow how far they are loaded
+    pub fn dependencies(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        self.required_dependencies().chain(self.sounds.ids())
+    }
+
+    /// The textures and the font. The theme can't be used if one of them can't be loaded
+    pub fn required_dependencies(&self) -> impl Iterator<Item = UntypedAssetId> + '_ {
+        [&self.square, &self.hexagon, &self.triangle]
+            .into_iter()
+            .flat_map(|textures| textures.handles().map(|handle| handle.id().untyped()))
+            .chain([self.style.font.id().untyped()])
+    }
+
+    /// Loaded once every file finished loading and all [Theme::required_dependencies] could be loaded. Sounds that
+    /// can't be loaded are left out, the theme is just quieter without them
+    pub fn load_state(&self, asset_server: &AssetServer) -> LoadState {
+        // Assets without a state, like the default font, have nothing to wait for
+        let state = |id| asset_server.get_load_state(id);
+        if self
+            .dependencies()
+            .any(|id| matches!(state(id), Some(LoadState::NotLoaded | LoadState::Loading)))
+        {
+            return LoadState::Loading;
+        }
+        match self
+            .required_dependencies()
+            .any(|id| state(id) == Some(LoadState::Failed))
+        {
+            true => LoadState::Failed,
+            false => LoadState::Loaded,
+        }
+    }
+
+    /// Makes this the current theme. Boards that already exist have to be re-textured separately
+    pub fn insert_resources(
+        &self,
+        commands: &mut Commands,
+        handle: Handle<Theme>,
+        topology: Topology,
+    ) {
+        commands.insert_resource(self.textures(topology).clone());
+        commands.insert_resource(self.style.clone());
+        commands.insert_resource(self.sounds.clone());
+        commands.insert_resource(ClearColor(self.style.background));
+        commands.insert_resource(ThemeHandle(handle));
+    }
+}
+
+/// Loads `.theme.ron` files and every texture, font and sound they list
+#[derive(Debug, Default)]
+pub struct ThemeLoader;
+
+impl AssetLoader for ThemeLoader {
+    type Asset = Theme;
+    type Settings = ();
+    type Error = ThemeError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let manifest: ThemeManifest = ron::de::from_bytes(&bytes)?;
+            let (background, tile, cover, text) = manifest.colors.parse()?;
+
+            let folders = &manifest.textures;
+            let sources = &folders.sources;
+            let (square, hexagon, triangle) = (
+                load_textures(load_context, &folders.square, &sources.square).await,
+                load_textures(load_context, &folders.hexagon, &sources.hexagon).await,
+                load_textures(load_context, &folders.triangle, &sources.triangle).await,
+            );
+
+            let paths = &manifest.sounds;
+            let mut sound =
+                |path: &Option<String>| path.as_ref().map(|path| load_context.load(path));
+            let sounds = ThemeSounds {
+                uncover: sound(&paths.uncover),
+                cascade: sound(&paths.cascade),
+                flag: sound(&paths.flag),
+                explosion: sound(&paths.explosion),
+                won: sound(&paths.won),
+                lost: sound(&paths.lost),
+                music: sound(&paths.music),
+            };
+
+            Ok(Theme {
+                name: manifest.name,
+                square,
+                hexagon,
+                triangle,
+                style: ThemeStyle {
+                    background,
+                    tile,
+                    cover,
+                    text,
+                    font: manifest
+                        .font
+                        .map(|path| load_context.load(path))
+                        .unwrap_or_default(),
+                },
+                sounds,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
+
+/// Loads the textures in a folder, or takes them from the layers of an Aseprite file
+async fn load_textures(
+    load_context: &mut LoadContext<'_>,
+    folder: &str,
+    source: &Option<String>,
+) -> TextureHandles {
+    let mut layers = HashMap::new();
+    if let Some(source) = source {
+        let parsed = match load_context.read_asset_bytes(source).await {
+            Ok(bytes) => aseprite::parse(&bytes).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        match parsed {
+            Ok(parsed) => layers.extend(
+                parsed
+                    .into_iter()
+                    .map(|layer| (layer.texture_file(), layer.image)),
+            ),
+            Err(e) => bevy::log::warn!("Couldn't read {}, using the PNGs instead: {}", source, e),
+        }
+    }
+
+    TextureHandles::load_with(|file| match layers.remove(file) {
+        Some(image) => load_context.add_labeled_asset(format!("{}/{}", folder, file), image),
+        None => load_context.load(format!("{}/{}", folder, file)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUNDLED_MANIFESTS: [&str; 3] = [
+        include_str!("../../assets/minesweeper/themes/classic.theme.ron"),
+        include_str!("../../assets/minesweeper/themes/dark.theme.ron"),
+        include_str!("../../assets/minesweeper/themes/high_contrast.theme.ron"),
+    ];
+
+    #[test]
+    fn bundled_themes_parse() {
+        for text in BUNDLED_MANIFESTS {
+            let manifest: ThemeManifest = ron::de::from_str(text).unwrap();
+            assert!(manifest.colors.parse().is_ok(), "{}", manifest.name);
+        }
+    }
+
+    #[test]
+    fn bundled_themes_have_all_their_sounds() {
+        let assets = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets");
+        for text in BUNDLED_MANIFESTS {
+            let manifest: ThemeManifest = ron::de::from_str(text).unwrap();
+            let paths = &manifest.sounds;
+            for path in [
+                &paths.uncover,
+                &paths.cascade,
+                &paths.flag,
+                &paths.explosion,
+                &paths.won,
+                &paths.lost,
+                &paths.music,
+            ] {
+                let path = path.as_ref().expect(&manifest.name);
+                assert!(assets.join(path).is_file(), "{}: {path}", manifest.name);
+            }
+        }
+    }
+
+    #[test]
+    fn classic_theme_uses_the_classic_textures() {
+        let manifest: ThemeManifest = ron::de::from_str(BUNDLED_MANIFESTS[0]).unwrap();
+
+        assert_eq!(manifest.textures.square, Topology::Square.texture_folder());
+        assert_eq!(
+            manifest.textures.hexagon,
+            Topology::Hexagon.texture_folder()
+        );
+        assert_eq!(
+            manifest.textures.triangle,
+            Topology::Triangle.texture_folder()
+        );
+    }
+
+    #[test]
+    fn themes_only_list_the_sounds_they_have() {
+        let manifest: ThemeManifest = ron::de::from_str(
+            r##"(
+                name: "Quiet",
+                textures: (square: "a", hexagon: "b", triangle: "c"),
+                colors: (background: "#000000", tile: "#ffffff", cover: "#ffffff", text: "#ffffff"),
+                sounds: (uncover: Some("click.ogg"), music: Some("music.ogg")),
+            )"##,
+        )
+        .unwrap();
+
+        assert_eq!(manifest.sounds.uncover.as_deref(), Some("click.ogg"));
+        assert_eq!(manifest.sounds.music.as_deref(), Some("music.ogg"));
+        assert!(manifest.sounds.cascade.is_none());
+        assert!(manifest.sounds.won.is_none());
+    }
+
+    #[test]
+    fn invalid_colours_are_rejected() {
+        let colors = ThemeColors {
+            background: "#000000".to_string(),
+            tile: "white".to_string(),
+            cover: "#ffffff".to_string(),
+            text: "#ffffff".to_string(),
+        };
+
+        assert!(matches!(
+            colors.parse(),
+            Err(ThemeError::Color { name: "tile", .. })
+        ));
+    }
+}
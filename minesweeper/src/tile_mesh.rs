@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_asset::RenderAssetUsages, render_resource::PrimitiveTopology},
+};
+
+use crate::{
+    components::coordinates::CoordinateU16, resources::tile_map::TileMap, topology::Topology,
+    BACKGROUND_Z,
+};
+
+/// Tiles that aren't void, grouped into chunks of `size` by `size` tiles of one layer.
+/// The keys are the layer and the chunk position
+pub(crate) fn chunk_tiles(
+    tile_map: &TileMap,
+    size: u16,
+) -> BTreeMap<(u16, u16, u16), Vec<CoordinateU16>> {
+    let size = size.max(1);
+    let mut chunks: BTreeMap<(u16, u16, u16), Vec<CoordinateU16>> = BTreeMap::new();
+    for (index, tile) in tile_map.iter().enumerate() {
+        if tile.is_void() {
+            continue;
+        }
+        let coordinate = tile_map.coordinate(index);
+        chunks
+            .entry((coordinate.z, coordinate.x / size, coordinate.y / size))
+            .or_default()
+            .push(coordinate);
+    }
+    chunks
+}
+
+/// Mesh with one quad for every tile. `shown` gives the atlas index and the tint of a tile
+pub(crate) fn chunk_mesh(
+    tiles: &[CoordinateU16],
+    mut shown: impl FnMut(CoordinateU16) -> (usize, Color),
+    topology: Topology,
+    tile_size: f32,
+    sprite_size: Vec2,
+    layout: &TextureAtlasLayout,
+) -> Mesh {
+    let half = sprite_size / 2.;
+    let mut positions = Vec::with_capacity(tiles.len() * 4);
+    let mut uvs = Vec::with_capacity(tiles.len() * 4);
+    let mut colors = Vec::with_capacity(tiles.len() * 4);
+    let mut indices = Vec::with_capacity(tiles.len() * 6);
+
+    for coordinate in tiles {
+        let (index, color) = shown(*coordinate);
+        let center = topology.tile_center(*coordinate, tile_size);
+        let uv = uv_rect(layout, index, topology.texture_rect(*coordinate));
+
+        let first = positions.len() as u32;
+        // Counter clockwise from the bottom left. Texture coordinates grow downwards
+        for (corner, uv) in [
+            (Vec2::new(-half.x, -half.y), Vec2::new(uv.min.x, uv.max.y)),
+            (Vec2::new(half.x, -half.y), uv.max),
+            (Vec2::new(half.x, half.y), Vec2::new(uv.max.x, uv.min.y)),
+            (Vec2::new(-half.x, half.y), uv.min),
+        ] {
+            positions.push((center + corner).extend(BACKGROUND_Z).to_array());
+            uvs.push(uv.to_array());
+            colors.push(color.as_linear_rgba_f32());
+        }
+        indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+    }
+
+    Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    )
+    .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+    .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+    .with_inserted_indices(Indices::U32(indices))
+}
+
+/// Texture coordinates of an atlas texture from 0 to 1. `part` is a rect inside the texture, like
+/// [Topology::texture_rect]
+pub(crate) fn uv_rect(layout: &TextureAtlasLayout, index: usize, part: Option<Rect>) -> Rect {
+    let cell = layout.textures[index];
+    let rect = match part {
+        Some(part) => Rect::from_corners(cell.min + part.min, cell.min + part.max),
+        None => cell,
+    };
+    Rect::from_corners(rect.min / layout.size, rect.max / layout.size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_cover_every_tile_once() {
+        let tile_map = TileMap::empty(5, 3, Topology::Square).with_depth(2);
+        let chunks = chunk_tiles(&tile_map, 2);
+
+        // Three by two chunks on each layer
+        assert_eq!(chunks.len(), 12);
+        assert_eq!(chunks.values().map(Vec::len).sum::<usize>(), 30);
+        assert_eq!(chunks[&(1, 2, 1)], vec![CoordinateU16 { x: 4, y: 2, z: 1 }]);
+    }
+
+    #[test]
+    fn quads_use_their_part_of_the_atlas() {
+        let layout = TextureAtlasLayout::from_grid(Vec2::new(4., 2.), 2, 1, None, None);
+        let tiles = [CoordinateU16 { x: 0, y: 0, z: 0 }];
+        let mesh = chunk_mesh(
+            &tiles,
+            |_| (1, Color::WHITE),
+            Topology::Square,
+            10.,
+            Vec2::splat(10.),
+            &layout,
+        );
+
+        assert_eq!(mesh.count_vertices(), 4);
+        assert_eq!(mesh.indices().map(Indices::len), Some(6));
+        assert_eq!(
+            uv_rect(&layout, 1, Some(Rect::new(0., 0., 2., 2.))),
+            Rect::new(0.5, 0., 0.75, 1.)
+        );
+    }
+}
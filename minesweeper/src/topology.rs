@@ -0,0 +1,431 @@
+use bevy::prelude::*;
+
+use crate::components::coordinates::CoordinateU16;
+
+/// The shape of the tiles and how they are arranged on the board.
+/// Everything that depends on the grid (neighbours, layout and hit testing) goes through here
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Topology {
+    #[default]
+    Square,
+    /// Pointy topped hexagons in offset coordinates. Odd rows are shifted right by half a tile
+    Hexagon,
+    /// Alternating upwards and downwards pointing triangles. The tile at 0:0 points upwards
+    Triangle,
+}
+
+impl Topology {
+    /// Offsets to every tile that touches the tile at `coordinate`, even if only at a corner
+    pub fn neighbour_offsets(&self, coordinate: CoordinateU16) -> &'static [(i8, i8)] {
+        match self {
+            Self::Square => &SQUARE_NEIGHBOUR_COORDINATES,
+            Self::Hexagon => hexagon_neighbour_offsets(coordinate),
+            Self::Triangle => match points_up(coordinate) {
+                true => &UP_TRIANGLE_NEIGHBOUR_COORDINATES,
+                false => &DOWN_TRIANGLE_NEIGHBOUR_COORDINATES,
+            },
+        }
+    }
+
+    /// Offsets to every tile that shares an edge with the tile at `coordinate`
+    pub fn edge_neighbour_offsets(&self, coordinate: CoordinateU16) -> &'static [(i8, i8)] {
+        match self {
+            Self::Square => &TRUE_SQUARE_NEIGHBOUR_COORDINATES,
+            // Hexagons only touch at edges
+            Self::Hexagon => hexagon_neighbour_offsets(coordinate),
+            Self::Triangle => match points_up(coordinate) {
+                true => &UP_TRIANGLE_EDGE_NEIGHBOUR_COORDINATES,
+                false => &DOWN_TRIANGLE_EDGE_NEIGHBOUR_COORDINATES,
+            },
+        }
+    }
+
+    /// World size of a tile sprite. `tile_size` is the width of a tile, hexagons are a bit higher than wide
+    /// and triangles a bit lower
+    pub fn tile_sprite_size(&self, tile_size: f32) -> Vec2 {
+        match self {
+            Self::Square => Vec2::splat(tile_size),
+            Self::Hexagon => Vec2::new(tile_size, tile_size * HEXAGON_HEIGHT),
+            Self::Triangle => Vec2::new(tile_size, tile_size * TRIANGLE_HEIGHT),
+        }
+    }
+
+    /// Center of a tile relative to the bottom left corner of the board
+    pub fn tile_center(&self, coordinate: CoordinateU16, tile_size: f32) -> Vec2 {
+        match self {
+            Self::Square => Vec2::new(
+                coordinate.x as f32 * tile_size + tile_size * 0.5,
+                coordinate.y as f32 * tile_size + tile_size * 0.5,
+            ),
+            Self::Hexagon => {
+                let height = tile_size * HEXAGON_HEIGHT;
+                let shift = (coordinate.y % 2) as f32 * tile_size * 0.5;
+                Vec2::new(
+                    coordinate.x as f32 * tile_size + tile_size * 0.5 + shift,
+                    coordinate.y as f32 * height * 0.75 + height * 0.5,
+                )
+            }
+            // The center of the sprite, not the centroid of the triangle
+            Self::Triangle => Vec2::new(
+                (coordinate.x + 1) as f32 * tile_size * 0.5,
+                (coordinate.y as f32 + 0.5) * tile_size * TRIANGLE_HEIGHT,
+            ),
+        }
+    }
+
+    /// World size of the whole board
+    pub fn board_size(&self, (width, height): (u16, u16), tile_size: f32) -> Vec2 {
+        match self {
+            Self::Square => Vec2::new(width as f32 * tile_size, height as f32 * tile_size),
+            Self::Hexagon => {
+                let tile_height = tile_size * HEXAGON_HEIGHT;
+                let shift = match height > 1 {
+                    true => tile_size * 0.5,
+                    false => 0.,
+                };
+                Vec2::new(
+                    width as f32 * tile_size + shift,
+                    (height.max(1) - 1) as f32 * tile_height * 0.75 + tile_height,
+                )
+            }
+            Self::Triangle => Vec2::new(
+                (width + 1) as f32 * tile_size * 0.5,
+                height as f32 * tile_size * TRIANGLE_HEIGHT,
+            ),
+        }
+    }
+
+    /// Finds the tile at a position relative to the bottom left corner of the board. The tile is on layer 0
+    pub fn tile_at(
+        &self,
+        position: Vec2,
+        (width, height): (u16, u16),
+        tile_size: f32,
+    ) -> Option<CoordinateU16> {
+        let (x, y) = match self {
+            Self::Square => (
+                (position.x / tile_size).floor(),
+                (position.y / tile_size).floor(),
+            ),
+            Self::Hexagon => {
+                // Axial coordinates relative to the center of the tile 0:0, rounded to the nearest hexagon
+                let radius = tile_size * HEXAGON_HEIGHT * 0.5;
+                let local = position - Vec2::new(tile_size * 0.5, radius);
+                let q = (3f32.sqrt() / 3. * local.x - local.y / 3.) / radius;
+                let r = (2. / 3. * local.y) / radius;
+                let (q, r) = cube_round(q, r);
+                // Axial to offset coordinates
+                ((q + (r - (r & 1)) / 2) as f32, r as f32)
+            }
+            Self::Triangle => {
+                // Tiles overlap half a tile with each neighbour in the row, so two tiles can contain the position
+                let y = (position.y / (tile_size * TRIANGLE_HEIGHT)).floor();
+                let column = (position.x / (tile_size * 0.5)).floor();
+                let x = [column - 1., column]
+                    .into_iter()
+                    .find(|x| {
+                        let coordinate = CoordinateU16 {
+                            x: *x as u16,
+                            y: y as u16,
+                            z: 0,
+                        };
+                        *x >= 0.
+                            && y >= 0.
+                            && triangle_contains(
+                                self.triangle_vertices(coordinate, tile_size),
+                                position,
+                            )
+                    })
+                    .unwrap_or(-1.);
+                (x, y)
+            }
+        };
+
+        if x < 0. || y < 0. || x >= width as f32 || y >= height as f32 {
+            return None;
+        }
+        Some(CoordinateU16 {
+            x: x as u16,
+            y: y as u16,
+            z: 0,
+        })
+    }
+
+    /// Distance between a tile and its copy on the other side of a wrapping board
+    pub fn wrap_period(&self, (width, height): (u16, u16), tile_size: f32) -> Vec2 {
+        match self {
+            Self::Square => Vec2::new(width as f32 * tile_size, height as f32 * tile_size),
+            Self::Hexagon => Vec2::new(
+                width as f32 * tile_size,
+                height as f32 * tile_size * HEXAGON_HEIGHT * 0.75,
+            ),
+            Self::Triangle => Vec2::new(
+                width as f32 * tile_size * 0.5,
+                height as f32 * tile_size * TRIANGLE_HEIGHT,
+            ),
+        }
+    }
+
+    /// Whether a board of this size lines up with itself when wrapping around the edges
+    pub fn can_wrap(&self, (width, height): (u16, u16)) -> bool {
+        match self {
+            Self::Square => true,
+            Self::Hexagon => height % 2 == 0,
+            Self::Triangle => width % 2 == 0 && height % 2 == 0,
+        }
+    }
+
+    /// Folder of the tile textures inside the assets folder
+    pub fn texture_folder(&self) -> &'static str {
+        match self {
+            Self::Square => "minesweeper",
+            Self::Hexagon => "minesweeper/hex",
+            Self::Triangle => "minesweeper/triangle",
+        }
+    }
+
+    /// Part of the texture used for the tile at `coordinate`, in pixels.
+    /// Triangle textures have the upwards tile on the left and the downwards tile on the right
+    pub fn texture_rect(&self, coordinate: CoordinateU16) -> Option<Rect> {
+        match self {
+            Self::Square | Self::Hexagon => None,
+            Self::Triangle => match points_up(coordinate) {
+                true => Some(Rect::new(0., 0., TRIANGLE_TEXTURE.x, TRIANGLE_TEXTURE.y)),
+                false => Some(Rect::new(
+                    TRIANGLE_TEXTURE.x,
+                    0.,
+                    TRIANGLE_TEXTURE.x * 2.,
+                    TRIANGLE_TEXTURE.y,
+                )),
+            },
+        }
+    }
+
+    /// Triangles that point downwards. Tiles of the other topologies are never upside down
+    pub fn is_upside_down(&self, coordinate: CoordinateU16) -> bool {
+        *self == Self::Triangle && !points_up(coordinate)
+    }
+
+    /// Corners of a triangle tile relative to the bottom left corner of the board
+    fn triangle_vertices(&self, coordinate: CoordinateU16, tile_size: f32) -> [Vec2; 3] {
+        let center = self.tile_center(coordinate, tile_size);
+        let half = self.tile_sprite_size(tile_size) * 0.5;
+        match points_up(coordinate) {
+            true => [
+                center + Vec2::new(-half.x, -half.y),
+                center + Vec2::new(half.x, -half.y),
+                center + Vec2::new(0., half.y),
+            ],
+            false => [
+                center + Vec2::new(-half.x, half.y),
+                center + Vec2::new(half.x, half.y),
+                center + Vec2::new(0., -half.y),
+            ],
+        }
+    }
+}
+
+fn points_up(coordinate: CoordinateU16) -> bool {
+    (coordinate.x + coordinate.y).is_multiple_of(2)
+}
+
+/// Checks on which side of every edge the point is. It's inside if it isn't on different sides of two edges
+fn triangle_contains([a, b, c]: [Vec2; 3], point: Vec2) -> bool {
+    let side = |from: Vec2, to: Vec2| (to - from).perp_dot(point - from);
+    let sides = [side(a, b), side(b, c), side(c, a)];
+    !(sides.iter().any(|s| *s < 0.) && sides.iter().any(|s| *s > 0.))
+}
+
+/// Odd rows are shifted right, so the tiles above and below are at different x offsets depending on the row
+fn hexagon_neighbour_offsets(coordinate: CoordinateU16) -> &'static [(i8, i8)] {
+    match coordinate.y % 2 {
+        0 => &EVEN_ROW_HEXAGON_NEIGHBOUR_COORDINATES,
+        _ => &ODD_ROW_HEXAGON_NEIGHBOUR_COORDINATES,
+    }
+}
+
+/// Rounds fractional axial coordinates to the hexagon that contains them
+fn cube_round(q: f32, r: f32) -> (i32, i32) {
+    let s = -q - r;
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+    (rq as i32, rr as i32)
+}
+
+/// Height of a pointy topped hexagon compared to its width
+const HEXAGON_HEIGHT: f32 = 1.154_700_5;
+/// Height of a triangle compared to the length of its sides
+const TRIANGLE_HEIGHT: f32 = 0.866_025_4;
+/// Size of one triangle in the triangle textures
+pub(crate) const TRIANGLE_TEXTURE: Vec2 = Vec2::new(64., 56.);
+
+const SQUARE_NEIGHBOUR_COORDINATES: [(i8, i8); 8] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top Left
+    (-1, 1),
+    // Top
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+const TRUE_SQUARE_NEIGHBOUR_COORDINATES: [(i8, i8); 4] = [
+    // Bottom
+    (0, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top
+    (0, 1),
+];
+
+const EVEN_ROW_HEXAGON_NEIGHBOUR_COORDINATES: [(i8, i8); 6] = [
+    // Bottom left
+    (-1, -1),
+    // Bottom right
+    (0, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top left
+    (-1, 1),
+    // Top right
+    (0, 1),
+];
+
+const ODD_ROW_HEXAGON_NEIGHBOUR_COORDINATES: [(i8, i8); 6] = [
+    // Bottom left
+    (0, -1),
+    // Bottom right
+    (1, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top left
+    (0, 1),
+    // Top right
+    (1, 1),
+];
+
+const UP_TRIANGLE_NEIGHBOUR_COORDINATES: [(i8, i8); 12] = [
+    // The row below shares the bottom edge and both bottom corners
+    (-2, -1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (2, -1),
+    // Left
+    (-2, 0),
+    (-1, 0),
+    // Right
+    (1, 0),
+    (2, 0),
+    // The row above only touches the top corner
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+const DOWN_TRIANGLE_NEIGHBOUR_COORDINATES: [(i8, i8); 12] = [
+    // The row below only touches the bottom corner
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    // Left
+    (-2, 0),
+    (-1, 0),
+    // Right
+    (1, 0),
+    (2, 0),
+    // The row above shares the top edge and both top corners
+    (-2, 1),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (2, 1),
+];
+
+const UP_TRIANGLE_EDGE_NEIGHBOUR_COORDINATES: [(i8, i8); 3] = [
+    // Bottom
+    (0, -1),
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+];
+
+const DOWN_TRIANGLE_EDGE_NEIGHBOUR_COORDINATES: [(i8, i8); 3] = [
+    // Left
+    (-1, 0),
+    // Right
+    (1, 0),
+    // Top
+    (0, 1),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hexagon_hit_test_finds_tile_centers() {
+        let topology = Topology::Hexagon;
+        for y in 0..5 {
+            for x in 0..5 {
+                let coordinate = CoordinateU16 { x, y, z: 0 };
+                let center = topology.tile_center(coordinate, 10.);
+
+                assert_eq!(topology.tile_at(center, (5, 5), 10.), Some(coordinate));
+            }
+        }
+        assert_eq!(topology.tile_at(Vec2::new(-1., 5.), (5, 5), 10.), None);
+    }
+
+    #[test]
+    fn hexagon_neighbours_are_adjacent() {
+        let topology = Topology::Hexagon;
+        for coordinate in [
+            CoordinateU16 { x: 2, y: 2, z: 0 },
+            CoordinateU16 { x: 2, y: 3, z: 0 },
+        ] {
+            let center = topology.tile_center(coordinate, 10.);
+            for offset in topology.neighbour_offsets(coordinate) {
+                let neighbour = topology.tile_center(coordinate + *offset, 10.);
+
+                assert!((center.distance(neighbour) - 10.).abs() < 0.01);
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_hit_test_finds_tile_centroids() {
+        let topology = Topology::Triangle;
+        for y in 0..4 {
+            for x in 0..6 {
+                let coordinate = CoordinateU16 { x, y, z: 0 };
+                let [a, b, c] = topology.triangle_vertices(coordinate, 10.);
+                let centroid = (a + b + c) / 3.;
+
+                assert_eq!(topology.tile_at(centroid, (6, 4), 10.), Some(coordinate));
+            }
+        }
+        assert_eq!(topology.tile_at(Vec2::new(1., 8.), (6, 4), 10.), None);
+    }
+}
@@ -0,0 +1,128 @@
+use std::{ops::Index, time::Duration};
+
+use crate::{options::RuleModifiers, resources::board::Board};
+
+/// What has to be done to win a game
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum WinCondition {
+    /// Every tile without a bomb is uncovered
+    #[default]
+    Classic,
+    /// Every bomb has the right number of flags and no other tile has flags
+    FlagAllMines,
+    /// Uncover as many tiles as possible before the time runs out. The game is won if the score reaches the target
+    ScoreAttack { time_limit: Duration, target: usize },
+}
+
+impl WinCondition {
+    /// Whether the board is won. Score attack games are only decided when the time runs out
+    pub(crate) fn is_won(&self, board: &Board) -> bool {
+        match self {
+            Self::Classic => board
+                .covered
+                .keys()
+                .all(|coordinate| board.tile_map.index(*coordinate).is_bomb()),
+            Self::FlagAllMines => {
+                board.flag_count + board.exploded == *board.tile_map.bomb_count()
+                    && board.flagged.iter().all(|(coordinate, (_, _, flags))| {
+                        *flags == 0 || board.tile_map.index(*coordinate).bomb_count() == *flags
+                    })
+            }
+            Self::ScoreAttack { .. } => false,
+        }
+    }
+
+    /// Whether games with these rule modifiers can be won. Flagging all mines needs flags
+    pub fn supports(&self, modifiers: &RuleModifiers) -> bool {
+        !(*self == Self::FlagAllMines && modifiers.no_flags)
+    }
+
+    /// Time limit of the game. Replaces the time limit of the rule modifiers
+    pub fn time_limit(&self) -> Option<Duration> {
+        match self {
+            Self::ScoreAttack { time_limit, .. } => Some(*time_limit),
+            _ => None,
+        }
+    }
+
+    /// Whether a game that ran out of time with this score is won
+    pub fn is_won_on_time_up(&self, score: usize) -> bool {
+        match self {
+            Self::ScoreAttack { target, .. } => score >= *target,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        components::coordinates::CoordinateU16, options::BoardOptions,
+        resources::tile_map::TileMap, topology::Topology,
+    };
+
+    /// A board with one bomb and one safe tile, both covered
+    fn board() -> (Board, CoordinateU16, CoordinateU16) {
+        let mut tile_map = TileMap::empty(2, 1, Topology::Square);
+        tile_map.spread_bombs(1, 1);
+        let (bomb, safe) = match tile_map[(0, 0)].is_bomb() {
+            true => (0, 1),
+            false => (1, 0),
+        };
+        let coordinate = |x| CoordinateU16 { x, y: 0, z: 0 };
+        (Board::covered(tile_map), coordinate(bomb), coordinate(safe))
+    }
+
+    #[test]
+    fn flags_alone_dont_win_classic_games() {
+        let (mut board, bomb, safe) = board();
+        board.flagged.get_mut(&bomb).unwrap().2 = 1;
+        board.flag_count = 1;
+
+        assert!(WinCondition::FlagAllMines.is_won(&board));
+        assert!(!WinCondition::Classic.is_won(&board));
+
+        board.covered.remove(&safe);
+        assert!(WinCondition::Classic.is_won(&board));
+    }
+
+    #[test]
+    fn wrong_flags_dont_win() {
+        let (mut board, _, safe) = board();
+        board.flagged.get_mut(&safe).unwrap().2 = 1;
+        board.flag_count = 1;
+
+        assert!(!WinCondition::FlagAllMines.is_won(&board));
+    }
+
+    #[test]
+    fn flag_all_mines_falls_back_without_flags() {
+        let no_flags = RuleModifiers {
+            no_flags: true,
+            ..Default::default()
+        };
+        assert!(WinCondition::FlagAllMines.supports(&Default::default()));
+        assert!(!WinCondition::FlagAllMines.supports(&no_flags));
+        assert!(WinCondition::Classic.supports(&no_flags));
+
+        let options = BoardOptions {
+            win_condition: WinCondition::FlagAllMines,
+            modifiers: no_flags,
+            ..Default::default()
+        };
+        assert_eq!(options.win_condition(), WinCondition::Classic);
+    }
+
+    #[test]
+    fn score_attack_is_decided_by_the_score() {
+        let condition = WinCondition::ScoreAttack {
+            time_limit: Duration::from_secs(60),
+            target: 10,
+        };
+
+        assert!(!condition.is_won(&board().0));
+        assert!(!condition.is_won_on_time_up(9));
+        assert!(condition.is_won_on_time_up(10));
+    }
+}
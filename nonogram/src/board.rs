@@ -0,0 +1,183 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use rand::Rng;
+
+use crate::{
+    components::{
+        board_marker::BoardMarker, cell::Cell, clue::Clue, cross_marker::CrossMarker,
+        tile_marker::TileMarker,
+    },
+    helpers::board_layout,
+    options::NonogramOptions,
+    puzzle::Nonogram,
+    resources::game::Game,
+    solver::has_unique_solution,
+    states::plugin_state::NonogramState,
+    NonogramPlugin, BACKGROUND_Z, CLUE_COLOR, CROSS_COLOR, EMPTY_COLOR, FOREGROUND_Z,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > NonogramPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn create_board(
+        mut commands: Commands,
+        windows: Query<&Window, With<PrimaryWindow>>,
+        options: Option<Res<NonogramOptions>>,
+        old_board: Query<Entity, With<BoardMarker>>,
+        mut next_state: ResMut<NextState<NonogramState>>,
+    ) {
+        let window = match windows.get_single() {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+
+        // Despawn old board if it exists
+        if let Ok(t) = old_board.get_single() {
+            commands.entity(t).despawn_recursive();
+        }
+
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+
+        let mut puzzles = Nonogram::bundled();
+        let index = match options.puzzle {
+            Some(t) => t.min(puzzles.len() - 1),
+            None => rand::thread_rng().gen_range(0..puzzles.len()),
+        };
+        let nonogram = puzzles.swap_remove(index);
+        bevy::log::info!("Starting puzzle '{}'", nonogram.title());
+        if !has_unique_solution(&nonogram) {
+            bevy::log::warn!(
+                "Puzzle '{}' can't be solved without guessing",
+                nonogram.title()
+            );
+        }
+
+        let mut game = Game::new(nonogram);
+        let (tile_size, board_position) = board_layout(window, &options, game.size_in_tiles());
+        game.tile_size = tile_size;
+        game.origin = board_position.xy();
+
+        commands
+            .spawn((
+                Name::new("Board"),
+                SpatialBundle {
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                },
+                BoardMarker,
+            ))
+            .with_children(|parent| {
+                for y in 0..*game.nonogram.height() {
+                    for x in 0..*game.nonogram.width() {
+                        let coordinate = Cell { x, y };
+                        parent
+                            .spawn((
+                                SpriteBundle {
+                                    sprite: Sprite {
+                                        color: EMPTY_COLOR,
+                                        custom_size: Some(Vec2::splat(
+                                            tile_size - options.tile_padding,
+                                        )),
+                                        ..Default::default()
+                                    },
+                                    transform: Transform::from_translation(
+                                        game.translation(coordinate, BACKGROUND_Z),
+                                    ),
+                                    ..Default::default()
+                                },
+                                Name::new(format!("Tile: {}", coordinate)),
+                                coordinate,
+                                TileMarker,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text2dBundle {
+                                        text: Text::from_section(
+                                            "X",
+                                            TextStyle {
+                                                font_size: tile_size * 0.7,
+                                                color: CROSS_COLOR,
+                                                ..Default::default()
+                                            },
+                                        ),
+                                        transform: Transform::from_xyz(0., 0., FOREGROUND_Z),
+                                        visibility: Visibility::Hidden,
+                                        ..Default::default()
+                                    },
+                                    CrossMarker,
+                                ));
+                            });
+                    }
+                }
+
+                let rows = (0..*game.nonogram.height()).flat_map(|line| {
+                    let clues = game.nonogram.row_clues(line);
+                    let count = clues.len() as u16;
+                    clues.into_iter().enumerate().map(move |(index, value)| {
+                        (
+                            Clue::Row {
+                                line,
+                                index: index as u16,
+                                count,
+                            },
+                            value,
+                        )
+                    })
+                });
+                let columns = (0..*game.nonogram.width()).flat_map(|line| {
+                    let clues = game.nonogram.column_clues(line);
+                    let count = clues.len() as u16;
+                    clues.into_iter().enumerate().map(move |(index, value)| {
+                        (
+                            Clue::Column {
+                                line,
+                                index: index as u16,
+                                count,
+                            },
+                            value,
+                        )
+                    })
+                });
+                for (clue, value) in rows.chain(columns) {
+                    parent.spawn((
+                        Text2dBundle {
+                            text: Text::from_section(
+                                value.to_string(),
+                                TextStyle {
+                                    font_size: tile_size * 0.6,
+                                    color: CLUE_COLOR,
+                                    ..Default::default()
+                                },
+                            ),
+                            transform: Transform::from_translation(
+                                game.clue_translation(clue, FOREGROUND_Z),
+                            ),
+                            ..Default::default()
+                        },
+                        Name::new("Clue"),
+                        clue,
+                    ));
+                }
+            });
+
+        commands.insert_resource(game);
+        next_state.set(NonogramState::Running);
+    }
+
+    pub fn reset_event_handler(
+        mut state: ResMut<NextState<NonogramState>>,
+        mut events: EventReader<TyResetEvent>,
+    ) {
+        if events.read().count() == 0 {
+            return;
+        }
+        state.set(NonogramState::NewGame);
+    }
+}
@@ -0,0 +1,14 @@
+use bevy::prelude::*;
+
+/// A cell of the picture, counted from the top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component)]
+pub struct Cell {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({};{})", self.x, self.y)
+    }
+}
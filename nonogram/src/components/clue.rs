@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// A single clue number next to the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+pub enum Clue {
+    /// The clue at `index` (counted from the left) of row `line`
+    Row { line: u16, index: u16, count: u16 },
+    /// The clue at `index` (counted from the top) of column `line`
+    Column { line: u16, index: u16, count: u16 },
+}
@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Marks the text that shows a cross on a tile
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct CrossMarker;
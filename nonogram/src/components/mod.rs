@@ -0,0 +1,5 @@
+pub mod board_marker;
+pub mod cell;
+pub mod clue;
+pub mod cross_marker;
+pub mod tile_marker;
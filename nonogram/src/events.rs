@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+use crate::{components::cell::Cell, resources::game::Mark};
+
+/// Event that is sent for every tile the player paints over while dragging
+#[derive(Debug, Clone, Copy, Event)]
+pub(crate) struct PaintEvent {
+    pub coordinate: Cell,
+    pub mark: Mark,
+}
@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::options::{NonogramOptions, TileSize};
+
+/// Computes the tile size and the position of the bottom left corner of the board, which has `(width, height)` tiles including the clues
+pub(crate) fn board_layout(
+    window: &Window,
+    options: &NonogramOptions,
+    (width, height): (u16, u16),
+) -> (f32, Vec3) {
+    let tile_size = match options.tile_size {
+        TileSize::Fixed(v) => v,
+        TileSize::Adaptive { min, max } => (window.width() / width as f32)
+            .min(window.height() / height as f32)
+            .clamp(min, max),
+    };
+    let board_size = Vec2::new(width as f32 * tile_size, height as f32 * tile_size);
+    (
+        tile_size,
+        Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.),
+    )
+}
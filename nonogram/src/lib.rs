@@ -0,0 +1,101 @@
+pub(crate) mod board;
+pub(crate) mod components;
+pub(crate) mod events;
+pub(crate) mod helpers;
+pub mod options;
+pub mod puzzle;
+pub(crate) mod resources;
+pub mod solver;
+pub(crate) mod states;
+pub(crate) mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    events::PaintEvent, resources::plugin_options::PluginOptions,
+    states::plugin_state::NonogramState,
+};
+
+pub(crate) const BACKGROUND_Z: f32 = 0.;
+pub(crate) const FOREGROUND_Z: f32 = 1.;
+
+pub(crate) const EMPTY_COLOR: Color = Color::rgb(0.9, 0.9, 0.9);
+pub(crate) const FILLED_COLOR: Color = Color::rgb(0.15, 0.15, 0.2);
+pub(crate) const CROSS_COLOR: Color = Color::rgb(0.67, 0., 0.);
+pub(crate) const CLUE_COLOR: Color = Color::WHITE;
+
+pub struct NonogramPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+where
+    TyRunState: States + PartialEq,
+    TyPauseState: States + PartialEq,
+    TyResetEvent: Event + Clone,
+    TyWonEvent: Event + Clone,
+    TyLostEvent: Event + Clone,
+{
+    pub run_state: TyRunState,
+    pub pause_state: TyPauseState,
+    pub reset_event: TyResetEvent,
+    pub won_event: TyWonEvent,
+    pub lost_event: TyLostEvent,
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > Plugin for NonogramPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Events need to be already added to the engine!
+    fn build(&self, app: &mut App) {
+        app.init_state::<NonogramState>()
+            .add_event::<PaintEvent>()
+            .insert_resource(PluginOptions {
+                won_event: self.won_event.clone(),
+            })
+            .add_systems(
+                Update,
+                (
+                    Self::create_board
+                        .run_if(in_state(NonogramState::NewGame))
+                        .run_if(in_state(self.run_state.clone())),
+                    crate::systems::rescale::rescale,
+                    crate::systems::input::input.run_if(
+                        in_state(NonogramState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    Self::paint_event_handler.run_if(in_state(NonogramState::Running)),
+                    crate::systems::refresh::refresh.run_if(in_state(NonogramState::Running)),
+                    Self::reset_event_handler,
+                ),
+            );
+        bevy::log::info!("Plugin loaded.");
+    }
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > NonogramPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn new(
+        run_state: TyRunState,
+        pause_state: TyPauseState,
+        reset_event: TyResetEvent,
+        won_event: TyWonEvent,
+        lost_event: TyLostEvent,
+    ) -> Self {
+        Self {
+            run_state,
+            pause_state,
+            reset_event,
+            won_event,
+            lost_event,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+
+/// Determines wether the tiles should be fixed (don't change with window scaling) or adaptive (change with window resizing but only above a minimum and below a maximum)
+#[derive(Debug, Clone, Resource)]
+pub enum TileSize {
+    Fixed(f32),
+    Adaptive { min: f32, max: f32 },
+}
+
+/// Vairous options relating to the nonogram
+#[derive(Debug, Clone, Resource)]
+pub struct NonogramOptions {
+    /// Index into the bundled puzzles. A random one is picked if this is none
+    pub puzzle: Option<usize>,
+    /// Tile world size
+    pub tile_size: TileSize,
+    /// Padding between tiles
+    pub tile_padding: f32,
+}
+
+impl Default for TileSize {
+    fn default() -> Self {
+        Self::Adaptive {
+            min: 10.0,
+            max: 60.0,
+        }
+    }
+}
+
+impl Default for NonogramOptions {
+    fn default() -> Self {
+        Self {
+            puzzle: None,
+            tile_size: Default::default(),
+            tile_padding: 2.,
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use crate::components::cell::Cell;
+
+/// Puzzles that ship with the game. Every one of them is checked for a unique solution in the tests
+pub const BUNDLED_PUZZLES: [&str; 4] = [
+    include_str!("../puzzles/heart.txt"),
+    include_str!("../puzzles/house.txt"),
+    include_str!("../puzzles/cat.txt"),
+    include_str!("../puzzles/tree.txt"),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("The puzzle has no rows")]
+    Empty,
+    #[error("Row {row} has {found} cells but the first row has {expected}")]
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Unknown character '{character}' in row {row}")]
+    UnknownCharacter { row: usize, character: char },
+}
+
+/// A nonogram solution. Row 0 is the top row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nonogram {
+    title: String,
+    width: u16,
+    height: u16,
+    solution: Vec<bool>,
+}
+
+impl Nonogram {
+    /// Parses the puzzle text format. Every row of the picture is one line where `#` is a filled and `.` an empty cell.
+    /// Lines starting with `;` are comments and a line starting with `title:` names the puzzle.
+    ///
+    /// ```text
+    /// ; A tiny arrow
+    /// title: Arrow
+    /// ..#..
+    /// .###.
+    /// #####
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut title = String::new();
+        let mut rows: Vec<Vec<bool>> = Vec::new();
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            if let Some(t) = line.strip_prefix("title:") {
+                title = t.trim().to_string();
+                continue;
+            }
+
+            let row = rows.len();
+            let cells = line
+                .chars()
+                .map(|character| match character {
+                    '#' => Ok(true),
+                    '.' => Ok(false),
+                    _ => Err(ParseError::UnknownCharacter { row, character }),
+                })
+                .collect::<Result<Vec<bool>, ParseError>>()?;
+
+            if let Some(first) = rows.first() {
+                if first.len() != cells.len() {
+                    return Err(ParseError::RowLength {
+                        row,
+                        expected: first.len(),
+                        found: cells.len(),
+                    });
+                }
+            }
+            rows.push(cells);
+        }
+
+        if rows.is_empty() || rows[0].is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        Ok(Self {
+            title,
+            width: rows[0].len() as u16,
+            height: rows.len() as u16,
+            solution: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// All bundled puzzles. Panics if one of them doesn't parse, which the tests make sure never happens
+    pub fn bundled() -> Vec<Self> {
+        BUNDLED_PUZZLES
+            .iter()
+            .map(|text| Self::parse(text).expect("Bundled puzzle is invalid"))
+            .collect()
+    }
+
+    pub fn is_filled(&self, coordinate: Cell) -> bool {
+        self.solution[coordinate.x as usize + coordinate.y as usize * self.width as usize]
+    }
+
+    pub fn row_clues(&self, y: u16) -> Vec<u16> {
+        clues((0..self.width).map(|x| self.is_filled(Cell { x, y })))
+    }
+
+    pub fn column_clues(&self, x: u16) -> Vec<u16> {
+        clues((0..self.height).map(|y| self.is_filled(Cell { x, y })))
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn width(&self) -> &u16 {
+        &self.width
+    }
+
+    pub fn height(&self) -> &u16 {
+        &self.height
+    }
+}
+
+/// Lengths of the runs of filled cells in a line
+pub fn clues(line: impl Iterator<Item = bool>) -> Vec<u16> {
+    let mut clues = Vec::new();
+    let mut run = 0;
+    for filled in line {
+        if filled {
+            run += 1;
+        } else if run != 0 {
+            clues.push(run);
+            run = 0;
+        }
+    }
+    if run != 0 {
+        clues.push(run);
+    }
+    clues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_title_and_clues() {
+        let nonogram = Nonogram::parse("; comment\ntitle: Arrow\n..#..\n.###.\n#####\n").unwrap();
+
+        assert_eq!(nonogram.title(), "Arrow");
+        assert_eq!((*nonogram.width(), *nonogram.height()), (5, 3));
+        assert_eq!(nonogram.row_clues(1), vec![3]);
+        assert_eq!(nonogram.column_clues(0), vec![1]);
+        assert_eq!(nonogram.column_clues(2), vec![3]);
+    }
+
+    #[test]
+    fn rejects_invalid_puzzles() {
+        assert_eq!(Nonogram::parse("; nothing"), Err(ParseError::Empty));
+        assert_eq!(
+            Nonogram::parse("##\n#"),
+            Err(ParseError::RowLength {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Nonogram::parse("#x"),
+            Err(ParseError::UnknownCharacter {
+                row: 0,
+                character: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn clues_of_split_line() {
+        assert_eq!(clues([true, true, false, true].into_iter()), vec![2, 1]);
+        assert!(clues([false, false].into_iter()).is_empty());
+    }
+}
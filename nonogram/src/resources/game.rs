@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{cell::Cell, clue::Clue},
+    puzzle::Nonogram,
+};
+
+/// What the player put on a tile
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Mark {
+    #[default]
+    Empty,
+    Filled,
+    /// The player is sure the tile stays empty. Counts as empty for the win check
+    Crossed,
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct Game {
+    pub nonogram: Nonogram,
+    pub marks: Vec<Mark>,
+    /// Number of clue columns left of the grid, the length of the longest row clue
+    pub clue_columns: u16,
+    /// Number of clue rows above the grid, the length of the longest column clue
+    pub clue_rows: u16,
+    /// World position of the bottom left corner of the board, including the clues
+    pub origin: Vec2,
+    pub tile_size: f32,
+    /// The won event is only sent once per game
+    pub won: bool,
+}
+
+impl Game {
+    pub fn new(nonogram: Nonogram) -> Self {
+        let clue_columns = (0..*nonogram.height())
+            .map(|y| nonogram.row_clues(y).len())
+            .max()
+            .unwrap_or(0)
+            .max(1) as u16;
+        let clue_rows = (0..*nonogram.width())
+            .map(|x| nonogram.column_clues(x).len())
+            .max()
+            .unwrap_or(0)
+            .max(1) as u16;
+        let tiles = *nonogram.width() as usize * *nonogram.height() as usize;
+
+        Self {
+            nonogram,
+            marks: vec![Mark::Empty; tiles],
+            clue_columns,
+            clue_rows,
+            origin: Vec2::ZERO,
+            tile_size: 0.,
+            won: false,
+        }
+    }
+
+    /// Size of the board in tiles, including the clues
+    pub fn size_in_tiles(&self) -> (u16, u16) {
+        (
+            self.clue_columns + self.nonogram.width(),
+            self.clue_rows + self.nonogram.height(),
+        )
+    }
+
+    pub fn mark(&self, coordinate: Cell) -> Mark {
+        self.marks[self.index(coordinate)]
+    }
+
+    pub fn set_mark(&mut self, coordinate: Cell, mark: Mark) {
+        let index = self.index(coordinate);
+        self.marks[index] = mark;
+    }
+
+    /// The puzzle is solved when exactly the tiles of the picture are filled. Crosses don't matter
+    pub fn is_solved(&self) -> bool {
+        (0..*self.nonogram.height()).all(|y| {
+            (0..*self.nonogram.width()).all(|x| {
+                let coordinate = Cell { x, y };
+                (self.mark(coordinate) == Mark::Filled) == self.nonogram.is_filled(coordinate)
+            })
+        })
+    }
+
+    /// Local translation of the center of a tile on the board. Row 0 is the top row
+    pub fn translation(&self, coordinate: Cell, z: f32) -> Vec3 {
+        Vec3::new(
+            (self.clue_columns + coordinate.x) as f32 * self.tile_size + self.tile_size * 0.5,
+            (self.nonogram.height() - coordinate.y - 1) as f32 * self.tile_size
+                + self.tile_size * 0.5,
+            z,
+        )
+    }
+
+    /// Local translation of a clue number. Row clues are right aligned to the grid, column clues bottom aligned
+    pub fn clue_translation(&self, clue: Clue, z: f32) -> Vec3 {
+        match clue {
+            Clue::Row { line, index, count } => Vec3::new(
+                (self.clue_columns as f32 - (count - index) as f32 + 0.5) * self.tile_size,
+                (self.nonogram.height() - line - 1) as f32 * self.tile_size + self.tile_size * 0.5,
+                z,
+            ),
+            Clue::Column { line, index, count } => Vec3::new(
+                (self.clue_columns + line) as f32 * self.tile_size + self.tile_size * 0.5,
+                (*self.nonogram.height() as f32 + (count - index) as f32 - 0.5) * self.tile_size,
+                z,
+            ),
+        }
+    }
+
+    /// Converts a world position to the coordinates of the tile below it
+    pub fn cursor_position(&self, position: Vec2) -> Option<Cell> {
+        let local = (position - self.origin) / self.tile_size;
+        if local.x < 0. || local.y < 0. {
+            return None;
+        }
+        let x = (local.x as u16).checked_sub(self.clue_columns)?;
+        let y_from_bottom = local.y as u16;
+        if x >= *self.nonogram.width() || y_from_bottom >= *self.nonogram.height() {
+            return None;
+        }
+        // The position 0:0 is top left but the world origin is bottom left
+        Some(Cell {
+            x,
+            y: self.nonogram.height() - y_from_bottom - 1,
+        })
+    }
+
+    fn index(&self, coordinate: Cell) -> usize {
+        coordinate.x as usize + coordinate.y as usize * *self.nonogram.width() as usize
+    }
+}
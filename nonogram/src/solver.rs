@@ -0,0 +1,181 @@
+use crate::{components::cell::Cell, puzzle::Nonogram};
+
+/// What the solver knows about a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Knowledge {
+    Unknown,
+    Filled,
+    Empty,
+}
+
+/// Deduces everything that follows from the clues of a single line and the cells that are already known.
+/// A cell becomes known if it is filled (or empty) in every arrangement of the clues that fits the line.
+/// Returns `None` if no arrangement fits
+pub fn solve_line(line: &[Knowledge], clues: &[u16]) -> Option<Vec<Knowledge>> {
+    let length = line.len();
+    let clue_count = clues.len();
+    let can_empty = |i: usize| line[i] != Knowledge::Filled;
+    let can_fill = |i: usize| line[i] != Knowledge::Empty;
+
+    // fits[i][j]: the cells from i onwards can hold the clues from j onwards
+    let mut fits = vec![vec![false; clue_count + 1]; length + 1];
+    fits[length][clue_count] = true;
+
+    let block_fits = |fits: &Vec<Vec<bool>>, i: usize, j: usize| -> bool {
+        let end = i + clues[j] as usize;
+        if end > length || !(i..end).all(can_fill) {
+            return false;
+        }
+        match end == length {
+            true => fits[length][j + 1],
+            false => can_empty(end) && fits[end + 1][j + 1],
+        }
+    };
+
+    for i in (0..length).rev() {
+        for j in (0..=clue_count).rev() {
+            fits[i][j] =
+                (can_empty(i) && fits[i + 1][j]) || (j < clue_count && block_fits(&fits, i, j));
+        }
+    }
+
+    if !fits[0][0] {
+        return None;
+    }
+
+    // Walk all arrangements from the start and remember what every cell can be
+    let mut filled = vec![false; length];
+    let mut empty = vec![false; length];
+    let mut reachable = vec![vec![false; clue_count + 1]; length + 1];
+    reachable[0][0] = true;
+
+    for i in 0..length {
+        for j in 0..=clue_count {
+            if !reachable[i][j] {
+                continue;
+            }
+            if can_empty(i) && fits[i + 1][j] {
+                empty[i] = true;
+                reachable[i + 1][j] = true;
+            }
+            if j < clue_count && block_fits(&fits, i, j) {
+                let end = i + clues[j] as usize;
+                filled[i..end].iter_mut().for_each(|cell| *cell = true);
+                if end < length {
+                    empty[end] = true;
+                    reachable[end + 1][j + 1] = true;
+                } else {
+                    reachable[length][j + 1] = true;
+                }
+            }
+        }
+    }
+
+    (0..length)
+        .map(|i| match (filled[i], empty[i]) {
+            (true, true) => Some(Knowledge::Unknown),
+            (true, false) => Some(Knowledge::Filled),
+            (false, true) => Some(Knowledge::Empty),
+            (false, false) => None,
+        })
+        .collect()
+}
+
+/// Applies the line solver to all rows and columns until nothing changes anymore.
+/// Returns the grid row by row, or `None` if the clues contradict each other
+pub fn solve(nonogram: &Nonogram) -> Option<Vec<Knowledge>> {
+    let width = *nonogram.width() as usize;
+    let height = *nonogram.height() as usize;
+    let mut grid = vec![Knowledge::Unknown; width * height];
+
+    let row_clues: Vec<Vec<u16>> = (0..height as u16).map(|y| nonogram.row_clues(y)).collect();
+    let column_clues: Vec<Vec<u16>> = (0..width as u16)
+        .map(|x| nonogram.column_clues(x))
+        .collect();
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for (y, clues) in row_clues.iter().enumerate() {
+            let line: Vec<Knowledge> = (0..width).map(|x| grid[x + y * width]).collect();
+            let solved = solve_line(&line, clues)?;
+            for x in 0..width {
+                changed |= grid[x + y * width] != solved[x];
+                grid[x + y * width] = solved[x];
+            }
+        }
+
+        for (x, clues) in column_clues.iter().enumerate() {
+            let line: Vec<Knowledge> = (0..height).map(|y| grid[x + y * width]).collect();
+            let solved = solve_line(&line, clues)?;
+            for y in 0..height {
+                changed |= grid[x + y * width] != solved[y];
+                grid[x + y * width] = solved[y];
+            }
+        }
+    }
+
+    Some(grid)
+}
+
+/// A puzzle the line solver can finish on its own has exactly one solution, and that solution has to be the picture.
+/// Puzzles that need guessing are rejected even if they might be unique
+pub fn has_unique_solution(nonogram: &Nonogram) -> bool {
+    let grid = match solve(nonogram) {
+        Some(t) => t,
+        None => return false,
+    };
+    let width = *nonogram.width() as usize;
+    grid.iter().enumerate().all(|(index, knowledge)| {
+        let coordinate = Cell {
+            x: (index % width) as u16,
+            y: (index / width) as u16,
+        };
+        match knowledge {
+            Knowledge::Filled => nonogram.is_filled(coordinate),
+            Knowledge::Empty => !nonogram.is_filled(coordinate),
+            Knowledge::Unknown => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use Knowledge::{Empty as E, Filled as F, Unknown as U};
+
+    #[test]
+    fn overlapping_block_is_filled() {
+        assert_eq!(solve_line(&[U; 5], &[4]), Some(vec![U, F, F, F, U]));
+        assert_eq!(solve_line(&[U; 5], &[2, 2]), Some(vec![F, F, E, F, F]));
+        assert_eq!(solve_line(&[U; 3], &[]), Some(vec![E, E, E]));
+    }
+
+    #[test]
+    fn known_cells_are_used() {
+        assert_eq!(
+            solve_line(&[U, U, U, U, F], &[2]),
+            Some(vec![E, E, E, F, F])
+        );
+        assert_eq!(solve_line(&[F, U, F], &[1]), None);
+    }
+
+    #[test]
+    fn bundled_puzzles_are_unique() {
+        for nonogram in Nonogram::bundled() {
+            assert!(
+                has_unique_solution(&nonogram),
+                "'{}' needs guessing or has more than one solution",
+                nonogram.title()
+            );
+        }
+    }
+
+    #[test]
+    fn ambiguous_puzzle_is_rejected() {
+        let nonogram = Nonogram::parse("#.\n.#").unwrap();
+        assert!(!has_unique_solution(&nonogram));
+    }
+}
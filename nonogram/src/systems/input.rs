@@ -0,0 +1,84 @@
+use bevy::{
+    input::{mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    components::cell::Cell,
+    events::PaintEvent,
+    resources::game::{Game, Mark},
+};
+
+/// The mark that gets painted while a mouse button is held and the last tile it was painted on
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Drag {
+    paint: Option<(MouseButton, Mark)>,
+    last: Option<Cell>,
+}
+
+/// Left click fills a tile and right click crosses it. Clicking a tile that already has the mark clears it.
+/// Holding the button and dragging paints the same mark on every tile the cursor passes
+pub fn input(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    game: Res<Game>,
+    mut button_event_read: EventReader<MouseButtonInput>,
+    mut drag: Local<Drag>,
+    mut paint_ewr: EventWriter<PaintEvent>,
+) {
+    let window = match windows.get_single() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    // Window to world space. Window y grows downwards
+    let tile = window.cursor_position().and_then(|cursor| {
+        game.cursor_position(Vec2::new(
+            cursor.x - window.width() / 2.,
+            window.height() / 2. - cursor.y,
+        ))
+    });
+
+    for event in button_event_read.read() {
+        match event.state {
+            ButtonState::Pressed => {
+                let tile = match tile {
+                    Some(t) => t,
+                    None => continue,
+                };
+                let mark = match event.button {
+                    MouseButton::Left => Mark::Filled,
+                    MouseButton::Right => Mark::Crossed,
+                    _ => continue,
+                };
+                // Starting on a tile that already has the mark erases instead
+                let paint = match game.mark(tile) == mark {
+                    true => Mark::Empty,
+                    false => mark,
+                };
+                drag.paint = Some((event.button, paint));
+                drag.last = None;
+            }
+            ButtonState::Released => {
+                if drag.paint.map(|(button, _)| button) == Some(event.button) {
+                    drag.paint = None;
+                }
+            }
+        }
+    }
+
+    let (_, paint) = match drag.paint {
+        Some(t) => t,
+        None => return,
+    };
+    if let Some(tile) = tile {
+        if drag.last != Some(tile) {
+            bevy::log::debug!("Painting {:?} on {}", paint, tile);
+            paint_ewr.send(PaintEvent {
+                coordinate: tile,
+                mark: paint,
+            });
+            drag.last = Some(tile);
+        }
+    }
+}
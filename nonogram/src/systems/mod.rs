@@ -0,0 +1,4 @@
+pub mod input;
+pub mod paint_event_handler;
+pub mod refresh;
+pub mod rescale;
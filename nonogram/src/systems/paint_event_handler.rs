@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::PaintEvent,
+    resources::{game::Game, plugin_options::PluginOptions},
+    NonogramPlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > NonogramPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Applies painted marks and sends the won event once the picture is complete
+    pub(crate) fn paint_event_handler(
+        plugin_options: Res<PluginOptions<TyWonEvent>>,
+        mut game: ResMut<Game>,
+        mut paint_evr: EventReader<PaintEvent>,
+        mut won_event_writer: EventWriter<TyWonEvent>,
+    ) {
+        let mut painted = false;
+        for event in paint_evr.read() {
+            if game.mark(event.coordinate) != event.mark {
+                game.set_mark(event.coordinate, event.mark);
+                painted = true;
+            }
+        }
+
+        if painted && !game.won && game.is_solved() {
+            game.won = true;
+            won_event_writer.send(plugin_options.won_event.clone());
+        }
+    }
+}
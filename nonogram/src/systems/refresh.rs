@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{cell::Cell, cross_marker::CrossMarker},
+    resources::game::{Game, Mark},
+    EMPTY_COLOR, FILLED_COLOR,
+};
+
+/// Updates tile colours and crosses whenever the marks changed
+pub fn refresh(
+    game: Res<Game>,
+    mut tiles: Query<(&Cell, &mut Sprite, &Children)>,
+    mut crosses: Query<&mut Visibility, With<CrossMarker>>,
+) {
+    if !game.is_changed() {
+        return;
+    }
+
+    for (coordinate, mut sprite, children) in tiles.iter_mut() {
+        let mark = game.mark(*coordinate);
+        sprite.color = match mark {
+            Mark::Filled => FILLED_COLOR,
+            Mark::Empty | Mark::Crossed => EMPTY_COLOR,
+        };
+        for child in children.iter() {
+            if let Ok(mut visibility) = crosses.get_mut(*child) {
+                *visibility = match mark {
+                    Mark::Crossed => Visibility::Inherited,
+                    _ => Visibility::Hidden,
+                };
+            }
+        }
+    }
+}
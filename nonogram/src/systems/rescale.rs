@@ -0,0 +1,68 @@
+use bevy::{prelude::*, window::PrimaryWindow, window::WindowResized};
+
+use crate::{
+    components::{board_marker::BoardMarker, cell::Cell, clue::Clue, cross_marker::CrossMarker},
+    helpers::board_layout,
+    options::NonogramOptions,
+    resources::game::Game,
+    BACKGROUND_Z, FOREGROUND_Z,
+};
+
+/// Changes tile size and position if window size changed
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+pub fn rescale(
+    mut board_component: Query<&mut Transform, With<BoardMarker>>,
+    mut tiles: Query<(&mut Transform, &mut Sprite, &Cell), Without<BoardMarker>>,
+    mut clues: Query<(&mut Transform, &mut Text, &Clue), (Without<BoardMarker>, Without<Cell>)>,
+    mut crosses: Query<&mut Text, (With<CrossMarker>, Without<Clue>)>,
+    mut game: Option<ResMut<Game>>,
+    mut resize_event: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    options: Option<Res<NonogramOptions>>,
+) {
+    if resize_event.read().count() == 0 {
+        return;
+    }
+
+    let game = match &mut game {
+        Some(t) => t,
+        None => return,
+    };
+
+    let window = match windows.get_single() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    let (tile_size, board_position) = board_layout(window, &options, game.size_in_tiles());
+    game.tile_size = tile_size;
+    game.origin = board_position.xy();
+
+    for mut transform in board_component.iter_mut() {
+        *transform = Transform::from_translation(board_position);
+    }
+
+    for (mut transform, mut sprite, coordinate) in tiles.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
+        transform.translation = game.translation(*coordinate, BACKGROUND_Z);
+    }
+
+    for (mut transform, mut text, clue) in clues.iter_mut() {
+        transform.translation = game.clue_translation(*clue, FOREGROUND_Z);
+        for section in text.sections.iter_mut() {
+            section.style.font_size = tile_size * 0.6;
+        }
+    }
+
+    for mut text in crosses.iter_mut() {
+        for section in text.sections.iter_mut() {
+            section.style.font_size = tile_size * 0.7;
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    card::Card,
+    components::{
+        board_marker::BoardMarker, card_marker::CardMarker, card_text_marker::CardTextMarker,
+        pile_marker::PileMarker,
+    },
+    klondike::{Klondike, Pile},
+    options::SolitaireOptions,
+    resources::{drag::Drag, game::Game, layout::Layout},
+    states::plugin_state::SolitaireState,
+    SolitairePlugin, BACKGROUND_Z, BLACK_SUIT_COLOR, CARD_BACK_COLOR, FOREGROUND_Z, PILE_COLOR,
+    RED_SUIT_COLOR,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > SolitairePlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn create_board(
+        mut commands: Commands,
+        windows: Query<&Window, With<PrimaryWindow>>,
+        options: Option<Res<SolitaireOptions>>,
+        old_board: Query<Entity, With<BoardMarker>>,
+        mut drag: ResMut<Drag>,
+        mut next_state: ResMut<NextState<SolitaireState>>,
+    ) {
+        let window = match windows.get_single() {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+
+        // Despawn old board if it exists
+        if let Ok(t) = old_board.get_single() {
+            commands.entity(t).despawn_recursive();
+        }
+
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+
+        let seed = options.seed.unwrap_or_else(rand::random);
+        bevy::log::info!("Dealing game {} ({:?})", seed, options.draw_mode);
+
+        let layout = Layout::new(Vec2::new(window.width(), window.height()));
+        let game = Game::new(Klondike::deal(seed, options.draw_mode), layout);
+        drag.0 = None;
+
+        commands
+            .spawn((Name::new("Board"), SpatialBundle::default(), BoardMarker))
+            .with_children(|parent| {
+                for pile in Pile::all() {
+                    parent.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: PILE_COLOR,
+                                custom_size: Some(layout.card_size),
+                                ..Default::default()
+                            },
+                            transform: Transform::from_translation(
+                                layout.pile_position(pile).extend(BACKGROUND_Z),
+                            ),
+                            ..Default::default()
+                        },
+                        Name::new(format!("Pile: {:?}", pile)),
+                        PileMarker(pile),
+                    ));
+                }
+
+                // The refresh system moves the cards to their places
+                for card in Card::deck() {
+                    parent
+                        .spawn((
+                            SpriteBundle {
+                                sprite: Sprite {
+                                    color: CARD_BACK_COLOR,
+                                    custom_size: Some(card_sprite_size(&layout)),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            Name::new(format!("Card: {}", card)),
+                            CardMarker(card),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text2dBundle {
+                                    text: Text::from_section(
+                                        card.to_string(),
+                                        TextStyle {
+                                            font_size: font_size(&layout),
+                                            color: match card.suit.is_red() {
+                                                true => RED_SUIT_COLOR,
+                                                false => BLACK_SUIT_COLOR,
+                                            },
+                                            ..Default::default()
+                                        },
+                                    )
+                                    .with_justify(JustifyText::Center),
+                                    transform: Transform::from_translation(text_translation(
+                                        &layout,
+                                    )),
+                                    visibility: Visibility::Hidden,
+                                    ..Default::default()
+                                },
+                                CardTextMarker,
+                            ));
+                        });
+                }
+            });
+
+        commands.insert_resource(game);
+        next_state.set(SolitaireState::Running);
+    }
+
+    pub fn reset_event_handler(
+        mut state: ResMut<NextState<SolitaireState>>,
+        mut events: EventReader<TyResetEvent>,
+    ) {
+        if events.read().count() == 0 {
+            return;
+        }
+        state.set(SolitaireState::NewGame);
+    }
+}
+
+/// Cards are a bit smaller than the piles so the pile outline stays visible
+pub(crate) fn card_sprite_size(layout: &Layout) -> Vec2 {
+    layout.card_size - Vec2::splat(layout.spacing * 0.2)
+}
+
+pub(crate) fn font_size(layout: &Layout) -> f32 {
+    layout.card_size.x * 0.3
+}
+
+/// Rank and suit are shown at the top of the card so they can still be read in a fanned tableau
+pub(crate) fn text_translation(layout: &Layout) -> Vec3 {
+    Vec3::new(0., layout.card_size.y * 0.36, FOREGROUND_Z)
+}
@@ -0,0 +1,98 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+    pub const fn is_red(&self) -> bool {
+        matches!(self, Self::Hearts | Self::Diamonds)
+    }
+
+    /// Short name shown on the card
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Self::Spades => "S",
+            Self::Hearts => "H",
+            Self::Diamonds => "D",
+            Self::Clubs => "C",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    Ace,
+    /// 2 to 10
+    Number(u8),
+    Jack,
+    Queen,
+    King,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Ace,
+        Rank::Number(2),
+        Rank::Number(3),
+        Rank::Number(4),
+        Rank::Number(5),
+        Rank::Number(6),
+        Rank::Number(7),
+        Rank::Number(8),
+        Rank::Number(9),
+        Rank::Number(10),
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+    ];
+
+    /// The rank one higher, none for kings
+    pub const fn next(&self) -> Option<Rank> {
+        match self {
+            Self::Ace => Some(Self::Number(2)),
+            Self::Number(10) => Some(Self::Jack),
+            Self::Number(n) => Some(Self::Number(*n + 1)),
+            Self::Jack => Some(Self::Queen),
+            Self::Queen => Some(Self::King),
+            Self::King => None,
+        }
+    }
+
+    /// Short name shown on the card
+    pub fn label(&self) -> String {
+        match self {
+            Self::Ace => "A".to_string(),
+            Self::Number(n) => n.to_string(),
+            Self::Jack => "J".to_string(),
+            Self::Queen => "Q".to_string(),
+            Self::King => "K".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl Card {
+    /// All 52 cards sorted by suit and rank
+    pub fn deck() -> Vec<Card> {
+        Suit::ALL
+            .into_iter()
+            .flat_map(|suit| Rank::ALL.into_iter().map(move |rank| Card { rank, suit }))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank.label(), self.suit.label())
+    }
+}
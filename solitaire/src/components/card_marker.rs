@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::card::Card;
+
+/// A card on the table. Where it lies is only stored in the [crate::klondike::Klondike]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub struct CardMarker(pub Card);
+
+impl std::ops::Deref for CardMarker {
+    type Target = Card;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
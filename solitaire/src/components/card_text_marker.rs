@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Marks the text showing rank and suit of a card
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct CardTextMarker;
@@ -0,0 +1,4 @@
+pub mod board_marker;
+pub mod card_marker;
+pub mod card_text_marker;
+pub mod pile_marker;
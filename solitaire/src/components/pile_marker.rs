@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+use crate::klondike::Pile;
+
+/// The outline of a pile that shows where cards can go once it is empty
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub struct PileMarker(pub Pile);
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::klondike::Pile;
+
+/// Everything the player can do. Sent by the input system and applied to the [crate::resources::game::Game]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub(crate) enum SolitaireActionEvent {
+    /// Turn cards from the stock or recycle the waste
+    Draw,
+    /// Move the cards from `index` to the top of `from` onto `to`
+    Move {
+        from: Pile,
+        index: usize,
+        to: Pile,
+    },
+    /// Move the top card of a pile to a fitting foundation
+    ToFoundation(Pile),
+    Undo,
+    /// Start moving cards to the foundations until nothing fits anymore
+    AutoComplete,
+    /// A single step of the auto complete, sent on a timer
+    AutoCompleteStep,
+}
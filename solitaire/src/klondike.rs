@@ -0,0 +1,444 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use crate::card::{Card, Rank};
+
+/// Every place a card can lie
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pile {
+    Stock,
+    Waste,
+    Foundation(u8),
+    Tableau(u8),
+}
+
+impl Pile {
+    pub const FOUNDATION_COUNT: u8 = 4;
+    pub const TABLEAU_COUNT: u8 = 7;
+
+    /// All piles in the order they are laid out
+    pub fn all() -> impl Iterator<Item = Pile> {
+        [Pile::Stock, Pile::Waste]
+            .into_iter()
+            .chain((0..Self::FOUNDATION_COUNT).map(Pile::Foundation))
+            .chain((0..Self::TABLEAU_COUNT).map(Pile::Tableau))
+    }
+}
+
+/// How many cards are turned over from the stock at once
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DrawMode {
+    #[default]
+    One,
+    Three,
+}
+
+impl DrawMode {
+    pub const fn count(&self) -> usize {
+        match self {
+            Self::One => 1,
+            Self::Three => 3,
+        }
+    }
+}
+
+/// A card and wether it is turned face up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlacedCard {
+    pub card: Card,
+    pub face_up: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MoveError {
+    #[error("There is no card at that position")]
+    NoCard,
+    #[error("Face down cards can't be moved")]
+    FaceDown,
+    #[error("Only the top card can be moved from this pile")]
+    NotTopCard,
+    #[error("Cards can't be put onto this pile")]
+    InvalidTarget,
+    #[error("The card doesn't fit onto this pile")]
+    DoesNotFit,
+}
+
+/// The state of a game of klondike. All rules live here so they can be tested without bevy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Klondike {
+    pub draw_mode: DrawMode,
+    stock: Vec<PlacedCard>,
+    waste: Vec<PlacedCard>,
+    foundations: [Vec<PlacedCard>; Pile::FOUNDATION_COUNT as usize],
+    tableaus: [Vec<PlacedCard>; Pile::TABLEAU_COUNT as usize],
+    history: Vec<Snapshot>,
+}
+
+/// Everything that is needed to undo a move
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Snapshot {
+    stock: Vec<PlacedCard>,
+    waste: Vec<PlacedCard>,
+    foundations: [Vec<PlacedCard>; Pile::FOUNDATION_COUNT as usize],
+    tableaus: [Vec<PlacedCard>; Pile::TABLEAU_COUNT as usize],
+}
+
+impl Klondike {
+    /// Shuffles a deck with the seed and deals it. The same seed always deals the same game
+    pub fn deal(seed: u64, draw_mode: DrawMode) -> Self {
+        let mut deck = Card::deck();
+        deck.shuffle(&mut StdRng::seed_from_u64(seed));
+        Self::deal_deck(deck, draw_mode)
+    }
+
+    /// Deals the cards in order. Tableau `n` gets `n + 1` cards with only the last one face up, the rest goes to the stock
+    pub fn deal_deck(deck: Vec<Card>, draw_mode: DrawMode) -> Self {
+        let mut cards = deck.into_iter();
+        let mut tableaus: [Vec<PlacedCard>; Pile::TABLEAU_COUNT as usize] = Default::default();
+        for (index, tableau) in tableaus.iter_mut().enumerate() {
+            for position in 0..=index {
+                if let Some(card) = cards.next() {
+                    tableau.push(PlacedCard {
+                        card,
+                        face_up: position == index,
+                    });
+                }
+            }
+        }
+
+        Self {
+            draw_mode,
+            stock: cards
+                .rev()
+                .map(|card| PlacedCard {
+                    card,
+                    face_up: false,
+                })
+                .collect(),
+            waste: Vec::new(),
+            foundations: Default::default(),
+            tableaus,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn pile(&self, pile: Pile) -> &[PlacedCard] {
+        match pile {
+            Pile::Stock => &self.stock,
+            Pile::Waste => &self.waste,
+            Pile::Foundation(i) => &self.foundations[i as usize],
+            Pile::Tableau(i) => &self.tableaus[i as usize],
+        }
+    }
+
+    fn pile_mut(&mut self, pile: Pile) -> &mut Vec<PlacedCard> {
+        match pile {
+            Pile::Stock => &mut self.stock,
+            Pile::Waste => &mut self.waste,
+            Pile::Foundation(i) => &mut self.foundations[i as usize],
+            Pile::Tableau(i) => &mut self.tableaus[i as usize],
+        }
+    }
+
+    /// Turns cards from the stock to the waste, or puts the waste back onto the stock if the stock is empty.
+    /// Returns false if both are empty
+    pub fn draw(&mut self) -> bool {
+        if self.stock.is_empty() && self.waste.is_empty() {
+            return false;
+        }
+        self.push_history();
+
+        if self.stock.is_empty() {
+            self.stock = self
+                .waste
+                .drain(..)
+                .rev()
+                .map(|card| PlacedCard {
+                    face_up: false,
+                    ..card
+                })
+                .collect();
+            return true;
+        }
+
+        for _ in 0..self.draw_mode.count() {
+            match self.stock.pop() {
+                Some(card) => self.waste.push(PlacedCard {
+                    face_up: true,
+                    ..card
+                }),
+                None => break,
+            }
+        }
+        true
+    }
+
+    /// Checks if the cards from `index` to the top of `from` can be put onto `to`
+    pub fn check_move(&self, from: Pile, index: usize, to: Pile) -> Result<(), MoveError> {
+        let source = self.pile(from);
+        let card = source.get(index).ok_or(MoveError::NoCard)?;
+        if !card.face_up {
+            return Err(MoveError::FaceDown);
+        }
+        let moved = source.len() - index;
+        if from == to {
+            return Err(MoveError::InvalidTarget);
+        }
+        if moved > 1 && !matches!(from, Pile::Tableau(_)) {
+            return Err(MoveError::NotTopCard);
+        }
+
+        let target = self.pile(to).last();
+        match to {
+            Pile::Stock | Pile::Waste => Err(MoveError::InvalidTarget),
+            Pile::Foundation(_) => {
+                if moved > 1 {
+                    return Err(MoveError::NotTopCard);
+                }
+                let fits = match target {
+                    None => card.card.rank == Rank::Ace,
+                    Some(top) => {
+                        top.card.suit == card.card.suit
+                            && top.card.rank.next() == Some(card.card.rank)
+                    }
+                };
+                fits.then_some(()).ok_or(MoveError::DoesNotFit)
+            }
+            Pile::Tableau(_) => {
+                let fits = match target {
+                    None => card.card.rank == Rank::King,
+                    Some(top) => {
+                        top.face_up
+                            && top.card.suit.is_red() != card.card.suit.is_red()
+                            && card.card.rank.next() == Some(top.card.rank)
+                    }
+                };
+                fits.then_some(()).ok_or(MoveError::DoesNotFit)
+            }
+        }
+    }
+
+    /// Moves the cards from `index` to the top of `from` onto `to` and turns over the new top card of a tableau
+    pub fn apply_move(&mut self, from: Pile, index: usize, to: Pile) -> Result<(), MoveError> {
+        self.check_move(from, index, to)?;
+        self.push_history();
+
+        let cards = self.pile_mut(from).split_off(index);
+        self.pile_mut(to).extend(cards);
+        if let Pile::Tableau(_) = from {
+            if let Some(top) = self.pile_mut(from).last_mut() {
+                top.face_up = true;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finds a foundation the top card of `from` can go to
+    pub fn foundation_for(&self, from: Pile) -> Option<Pile> {
+        let index = self.pile(from).len().checked_sub(1)?;
+        (0..Pile::FOUNDATION_COUNT)
+            .map(Pile::Foundation)
+            .find(|to| self.check_move(from, index, *to).is_ok())
+    }
+
+    /// Moves a single card from the waste or a tableau to a foundation. Returns false if no card can be moved
+    pub fn auto_complete_step(&mut self) -> bool {
+        let from = [Pile::Waste]
+            .into_iter()
+            .chain((0..Pile::TABLEAU_COUNT).map(Pile::Tableau))
+            .find_map(|from| self.foundation_for(from).map(|to| (from, to)));
+        match from {
+            Some((from, to)) => {
+                let index = self.pile(from).len() - 1;
+                self.apply_move(from, index, to).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /// Auto complete is possible once every card is turned over and the stock and waste are empty
+    pub fn can_auto_complete(&self) -> bool {
+        self.stock.is_empty()
+            && self.waste.is_empty()
+            && self.tableaus.iter().flatten().all(|card| card.face_up)
+            && !self.is_won()
+    }
+
+    pub fn is_won(&self) -> bool {
+        self.foundations
+            .iter()
+            .all(|foundation| foundation.len() == 13)
+    }
+
+    /// Reverts the last draw or move. Returns false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.stock = snapshot.stock;
+                self.waste = snapshot.waste;
+                self.foundations = snapshot.foundations;
+                self.tableaus = snapshot.tableaus;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn push_history(&mut self) {
+        self.history.push(Snapshot {
+            stock: self.stock.clone(),
+            waste: self.waste.clone(),
+            foundations: self.foundations.clone(),
+            tableaus: self.tableaus.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::Suit;
+
+    fn card(rank: Rank, suit: Suit) -> PlacedCard {
+        PlacedCard {
+            card: Card { rank, suit },
+            face_up: true,
+        }
+    }
+
+    fn empty() -> Klondike {
+        Klondike::deal_deck(Vec::new(), DrawMode::One)
+    }
+
+    #[test]
+    fn deal_is_deterministic() {
+        let game = Klondike::deal(42, DrawMode::One);
+
+        assert_eq!(game, Klondike::deal(42, DrawMode::One));
+        assert_ne!(game, Klondike::deal(43, DrawMode::One));
+        assert_eq!(game.pile(Pile::Tableau(6)).len(), 7);
+        assert_eq!(game.pile(Pile::Stock).len(), 52 - 28);
+        assert!(game.pile(Pile::Tableau(3)).last().unwrap().face_up);
+        assert!(!game.pile(Pile::Tableau(3))[0].face_up);
+    }
+
+    #[test]
+    fn tableau_needs_alternating_colours_and_descending_ranks() {
+        let mut game = empty();
+        game.tableaus[0].push(card(Rank::Number(8), Suit::Spades));
+        game.tableaus[1].push(card(Rank::Number(7), Suit::Hearts));
+        game.tableaus[2].push(card(Rank::Number(7), Suit::Clubs));
+        game.tableaus[3].push(card(Rank::Number(6), Suit::Diamonds));
+
+        assert_eq!(
+            game.check_move(Pile::Tableau(1), 0, Pile::Tableau(0)),
+            Ok(())
+        );
+        assert_eq!(
+            game.check_move(Pile::Tableau(2), 0, Pile::Tableau(0)),
+            Err(MoveError::DoesNotFit)
+        );
+        assert_eq!(
+            game.check_move(Pile::Tableau(3), 0, Pile::Tableau(0)),
+            Err(MoveError::DoesNotFit)
+        );
+    }
+
+    #[test]
+    fn only_kings_go_to_empty_tableaus() {
+        let mut game = empty();
+        game.waste.push(card(Rank::Queen, Suit::Hearts));
+        game.tableaus[1].push(card(Rank::King, Suit::Clubs));
+
+        assert_eq!(
+            game.check_move(Pile::Waste, 0, Pile::Tableau(0)),
+            Err(MoveError::DoesNotFit)
+        );
+        assert_eq!(
+            game.check_move(Pile::Tableau(1), 0, Pile::Tableau(0)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn foundations_build_up_by_suit() {
+        let mut game = empty();
+        game.waste.push(card(Rank::Number(2), Suit::Hearts));
+        game.tableaus[0].push(card(Rank::Ace, Suit::Hearts));
+
+        assert_eq!(
+            game.check_move(Pile::Waste, 0, Pile::Foundation(0)),
+            Err(MoveError::DoesNotFit)
+        );
+        game.apply_move(Pile::Tableau(0), 0, Pile::Foundation(0))
+            .unwrap();
+        assert_eq!(game.foundation_for(Pile::Waste), Some(Pile::Foundation(0)));
+    }
+
+    #[test]
+    fn stacks_move_together_and_reveal_card() {
+        let mut game = empty();
+        game.tableaus[0].push(PlacedCard {
+            face_up: false,
+            ..card(Rank::Ace, Suit::Spades)
+        });
+        game.tableaus[0].push(card(Rank::Number(9), Suit::Hearts));
+        game.tableaus[0].push(card(Rank::Number(8), Suit::Clubs));
+        game.tableaus[1].push(card(Rank::Number(10), Suit::Spades));
+
+        assert_eq!(
+            game.check_move(Pile::Tableau(0), 0, Pile::Tableau(1)),
+            Err(MoveError::FaceDown)
+        );
+        game.apply_move(Pile::Tableau(0), 1, Pile::Tableau(1))
+            .unwrap();
+
+        assert_eq!(game.pile(Pile::Tableau(1)).len(), 3);
+        assert!(game.pile(Pile::Tableau(0))[0].face_up);
+        assert_eq!(
+            game.check_move(Pile::Tableau(1), 1, Pile::Foundation(0)),
+            Err(MoveError::NotTopCard)
+        );
+    }
+
+    #[test]
+    fn draw_three_and_recycle() {
+        let mut game = Klondike::deal(7, DrawMode::Three);
+        game.draw();
+        assert_eq!(game.pile(Pile::Waste).len(), 3);
+        assert!(game.pile(Pile::Waste).iter().all(|card| card.face_up));
+
+        while !game.pile(Pile::Stock).is_empty() {
+            game.draw();
+        }
+        let waste = game.pile(Pile::Waste).to_vec();
+        game.draw();
+        assert_eq!(game.pile(Pile::Stock).len(), waste.len());
+        assert_eq!(game.pile(Pile::Stock).last().unwrap().card, waste[0].card);
+    }
+
+    #[test]
+    fn undo_restores_previous_state() {
+        let mut game = Klondike::deal(1, DrawMode::One);
+        let before = game.clone();
+        game.draw();
+
+        assert!(game.undo());
+        assert_eq!(game.pile(Pile::Stock), before.pile(Pile::Stock));
+        assert_eq!(game.pile(Pile::Waste), before.pile(Pile::Waste));
+        assert!(!game.undo());
+    }
+
+    #[test]
+    fn auto_complete_finishes_the_game() {
+        let mut game = empty();
+        for (index, suit) in Suit::ALL.into_iter().enumerate() {
+            for rank in Rank::ALL.into_iter().rev() {
+                game.tableaus[index].push(card(rank, suit));
+            }
+        }
+
+        assert!(game.can_auto_complete());
+        while game.auto_complete_step() {}
+        assert!(game.is_won());
+    }
+}
@@ -0,0 +1,111 @@
+pub(crate) mod board;
+pub mod card;
+pub(crate) mod components;
+pub(crate) mod events;
+pub mod klondike;
+pub mod options;
+pub(crate) mod resources;
+pub(crate) mod states;
+pub(crate) mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    events::SolitaireActionEvent,
+    resources::{drag::Drag, plugin_options::PluginOptions},
+    states::plugin_state::SolitaireState,
+};
+
+pub(crate) const BACKGROUND_Z: f32 = 0.;
+pub(crate) const FOREGROUND_Z: f32 = 1.;
+/// Dragged cards are drawn above everything else
+pub(crate) const DRAG_Z: f32 = 10.;
+/// Added per card so cards higher up a pile are drawn on top
+pub(crate) const CARD_Z_STEP: f32 = 0.01;
+
+pub(crate) const PILE_COLOR: Color = Color::rgb(0.1, 0.4, 0.15);
+pub(crate) const CARD_FACE_COLOR: Color = Color::WHITE;
+pub(crate) const CARD_BACK_COLOR: Color = Color::rgb(0.15, 0.25, 0.6);
+pub(crate) const RED_SUIT_COLOR: Color = Color::rgb(0.75, 0., 0.);
+pub(crate) const BLACK_SUIT_COLOR: Color = Color::BLACK;
+
+pub struct SolitairePlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+where
+    TyRunState: States + PartialEq,
+    TyPauseState: States + PartialEq,
+    TyResetEvent: Event + Clone,
+    TyWonEvent: Event + Clone,
+    TyLostEvent: Event + Clone,
+{
+    pub run_state: TyRunState,
+    pub pause_state: TyPauseState,
+    pub reset_event: TyResetEvent,
+    pub won_event: TyWonEvent,
+    pub lost_event: TyLostEvent,
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > Plugin for SolitairePlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Events need to be already added to the engine!
+    fn build(&self, app: &mut App) {
+        app.init_state::<SolitaireState>()
+            .add_event::<SolitaireActionEvent>()
+            .init_resource::<Drag>()
+            .insert_resource(PluginOptions {
+                won_event: self.won_event.clone(),
+            })
+            .add_systems(
+                Update,
+                (
+                    Self::create_board
+                        .run_if(in_state(SolitaireState::NewGame))
+                        .run_if(in_state(self.run_state.clone())),
+                    crate::systems::rescale::rescale,
+                    crate::systems::input::input.run_if(
+                        in_state(SolitaireState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    crate::systems::auto_complete::auto_complete.run_if(
+                        in_state(SolitaireState::Running)
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    Self::action_event_handler.run_if(in_state(SolitaireState::Running)),
+                    crate::systems::refresh::refresh.run_if(in_state(SolitaireState::Running)),
+                    Self::reset_event_handler,
+                ),
+            );
+        bevy::log::info!("Plugin loaded.");
+    }
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > SolitairePlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn new(
+        run_state: TyRunState,
+        pause_state: TyPauseState,
+        reset_event: TyResetEvent,
+        won_event: TyWonEvent,
+        lost_event: TyLostEvent,
+    ) -> Self {
+        Self {
+            run_state,
+            pause_state,
+            reset_event,
+            won_event,
+            lost_event,
+        }
+    }
+}
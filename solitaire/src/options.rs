@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::klondike::DrawMode;
+
+/// Vairous options relating to the solitaire
+#[derive(Debug, Clone, Resource)]
+pub struct SolitaireOptions {
+    /// How many cards are turned over from the stock at once
+    pub draw_mode: DrawMode,
+    /// Seed of the shuffle. A random game is dealt if this is none
+    pub seed: Option<u64>,
+    /// Start the auto complete on its own once every card is turned over
+    pub auto_complete: bool,
+    /// Seconds between two cards moved by the auto complete
+    pub auto_complete_interval: f32,
+}
+
+impl Default for SolitaireOptions {
+    fn default() -> Self {
+        Self {
+            draw_mode: Default::default(),
+            seed: None,
+            auto_complete: true,
+            auto_complete_interval: 0.1,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::klondike::Pile;
+
+/// Cards the player is currently holding
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DraggedCards {
+    pub from: Pile,
+    /// Index of the bottom most dragged card. Every card above it is dragged as well
+    pub index: usize,
+    /// Offset from the center of the grabbed card to the cursor
+    pub grab_offset: Vec2,
+    /// World position of the cursor
+    pub cursor: Vec2,
+}
+
+#[derive(Debug, Clone, Default, Resource)]
+pub struct Drag(pub Option<DraggedCards>);
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+
+use crate::{
+    card::Card,
+    klondike::{Klondike, Pile},
+    resources::layout::Layout,
+};
+
+#[derive(Debug, Clone, Resource)]
+pub struct Game {
+    pub klondike: Klondike,
+    pub layout: Layout,
+    /// Cards are moved to the foundations one by one until nothing fits anymore
+    pub auto_completing: bool,
+    /// Undo keeps the auto complete from starting on its own until the next move
+    pub auto_complete_held: bool,
+    /// The won event is only sent once per game
+    pub won: bool,
+}
+
+impl Game {
+    pub fn new(klondike: Klondike, layout: Layout) -> Self {
+        Self {
+            klondike,
+            layout,
+            auto_completing: false,
+            auto_complete_held: false,
+            won: false,
+        }
+    }
+
+    /// Finds the pile, index and side of every card
+    pub fn card_places(&self) -> bevy::utils::HashMap<Card, (Pile, usize, bool)> {
+        Pile::all()
+            .flat_map(|pile| {
+                self.klondike
+                    .pile(pile)
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, placed)| (placed.card, (pile, index, placed.face_up)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::klondike::{Klondike, Pile};
+
+/// Where piles and cards are on the table. All positions are world positions of card centers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub card_size: Vec2,
+    pub spacing: f32,
+    /// Top left corner of the stock
+    pub top_left: Vec2,
+}
+
+impl Layout {
+    /// Fits seven columns of cards into the window
+    pub fn new(window_size: Vec2) -> Self {
+        let card_width = (window_size.x * 0.95 / (7. + 6. * SPACING))
+            .min(window_size.y * 0.95 / (CARD_RATIO * 4.));
+        let card_size = Vec2::new(card_width, card_width * CARD_RATIO);
+        let spacing = card_width * SPACING;
+        let table_width = 7. * card_size.x + 6. * spacing;
+
+        Self {
+            card_size,
+            spacing,
+            top_left: Vec2::new(-table_width / 2., window_size.y / 2. - spacing),
+        }
+    }
+
+    /// Center of the bottom card of a pile
+    pub fn pile_position(&self, pile: Pile) -> Vec2 {
+        let (column, row) = match pile {
+            Pile::Stock => (0, 0),
+            Pile::Waste => (1, 0),
+            Pile::Foundation(i) => (3 + i as usize, 0),
+            Pile::Tableau(i) => (i as usize, 1),
+        };
+        Vec2::new(
+            self.top_left.x
+                + column as f32 * (self.card_size.x + self.spacing)
+                + self.card_size.x / 2.,
+            self.top_left.y
+                - row as f32 * (self.card_size.y + self.spacing)
+                - self.card_size.y / 2.,
+        )
+    }
+
+    /// Center of the card at `index` of a pile. Tableaus fan downwards and the waste shows the last cards drawn
+    pub fn card_position(&self, klondike: &Klondike, pile: Pile, index: usize) -> Vec2 {
+        let base = self.pile_position(pile);
+        let cards = klondike.pile(pile);
+        match pile {
+            Pile::Tableau(_) => {
+                let offset: f32 = cards
+                    .iter()
+                    .take(index)
+                    .map(|card| match card.face_up {
+                        true => self.card_size.y * FACE_UP_FAN,
+                        false => self.card_size.y * FACE_DOWN_FAN,
+                    })
+                    .sum();
+                base - Vec2::new(0., offset)
+            }
+            Pile::Waste => {
+                let first_shown = cards.len().saturating_sub(klondike.draw_mode.count());
+                let position = index.saturating_sub(first_shown);
+                base + Vec2::new(position as f32 * self.card_size.x * WASTE_FAN, 0.)
+            }
+            _ => base,
+        }
+    }
+
+    /// Offset between two face up cards of a tableau
+    pub fn fan_offset(&self) -> Vec2 {
+        Vec2::new(0., -self.card_size.y * FACE_UP_FAN)
+    }
+
+    pub fn card_rect(&self, center: Vec2) -> Rect {
+        Rect::from_center_size(center, self.card_size)
+    }
+
+    /// Finds the topmost card below the cursor. Returns the pile and the index of the card, or no index for an empty pile
+    pub fn hit_test(&self, klondike: &Klondike, position: Vec2) -> Option<(Pile, Option<usize>)> {
+        for pile in Pile::all() {
+            let cards = klondike.pile(pile);
+            for index in (0..cards.len()).rev() {
+                if self
+                    .card_rect(self.card_position(klondike, pile, index))
+                    .contains(position)
+                {
+                    return Some((pile, Some(index)));
+                }
+            }
+            if cards.is_empty() && self.card_rect(self.pile_position(pile)).contains(position) {
+                return Some((pile, None));
+            }
+        }
+        None
+    }
+
+    /// Finds the pile the cursor is over while dropping cards. The whole fanned tableau counts
+    pub fn drop_target(&self, klondike: &Klondike, position: Vec2) -> Option<Pile> {
+        Pile::all().find(|pile| {
+            let top = match klondike.pile(*pile).len() {
+                0 => self.pile_position(*pile),
+                len => self.card_position(klondike, *pile, len - 1),
+            };
+            self.card_rect(self.pile_position(*pile))
+                .union(self.card_rect(top))
+                .contains(position)
+        })
+    }
+}
+
+/// Height of a card compared to its width
+const CARD_RATIO: f32 = 1.4;
+/// Space between columns compared to the card width
+const SPACING: f32 = 0.15;
+/// Visible part of a face up card below another card in a tableau, compared to the card height
+const FACE_UP_FAN: f32 = 0.25;
+const FACE_DOWN_FAN: f32 = 0.1;
+/// Horizontal offset of the shown waste cards, compared to the card width
+const WASTE_FAN: f32 = 0.25;
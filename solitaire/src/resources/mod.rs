@@ -0,0 +1,4 @@
+pub mod drag;
+pub mod game;
+pub mod layout;
+pub mod plugin_options;
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::SolitaireActionEvent,
+    resources::{game::Game, plugin_options::PluginOptions},
+    SolitairePlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > SolitairePlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Applies the player actions to the game and sends the won event once every card is on a foundation
+    pub(crate) fn action_event_handler(
+        plugin_options: Res<PluginOptions<TyWonEvent>>,
+        mut game: ResMut<Game>,
+        mut action_evr: EventReader<SolitaireActionEvent>,
+        mut won_event_writer: EventWriter<TyWonEvent>,
+    ) {
+        for event in action_evr.read() {
+            match *event {
+                SolitaireActionEvent::Draw => {
+                    game.auto_complete_held = false;
+                    game.klondike.draw();
+                }
+                SolitaireActionEvent::Move { from, index, to } => {
+                    game.auto_complete_held = false;
+                    if let Err(e) = game.klondike.apply_move(from, index, to) {
+                        bevy::log::debug!("Can't move {:?} to {:?}: {}", from, to, e);
+                    }
+                }
+                SolitaireActionEvent::ToFoundation(from) => {
+                    game.auto_complete_held = false;
+                    if let Some(to) = game.klondike.foundation_for(from) {
+                        let index = game.klondike.pile(from).len() - 1;
+                        let _ = game.klondike.apply_move(from, index, to);
+                    }
+                }
+                SolitaireActionEvent::Undo => {
+                    game.auto_completing = false;
+                    game.auto_complete_held = true;
+                    game.klondike.undo();
+                }
+                SolitaireActionEvent::AutoComplete => game.auto_completing = true,
+                SolitaireActionEvent::AutoCompleteStep => {
+                    if !game.klondike.auto_complete_step() {
+                        game.auto_completing = false;
+                    }
+                }
+            }
+        }
+
+        if !game.won && game.klondike.is_won() {
+            game.won = true;
+            game.auto_completing = false;
+            won_event_writer.send(plugin_options.won_event.clone());
+        }
+    }
+}
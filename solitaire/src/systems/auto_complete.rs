@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+
+use crate::{events::SolitaireActionEvent, options::SolitaireOptions, resources::game::Game};
+
+/// Moves one card to the foundations every interval while the auto complete runs.
+/// Starts on its own once the game can't be lost anymore if the options allow it, but not right after an undo
+pub fn auto_complete(
+    time: Res<Time>,
+    mut timer: Local<Timer>,
+    mut game: ResMut<Game>,
+    options: Option<Res<SolitaireOptions>>,
+    mut action_ewr: EventWriter<SolitaireActionEvent>,
+) {
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    if options.auto_complete
+        && !game.auto_completing
+        && !game.auto_complete_held
+        && game.klondike.can_auto_complete()
+    {
+        game.auto_completing = true;
+    }
+    if !game.auto_completing {
+        return;
+    }
+
+    timer.set_mode(TimerMode::Repeating);
+    timer.set_duration(std::time::Duration::from_secs_f32(
+        options.auto_complete_interval,
+    ));
+    if timer.tick(time.delta()).just_finished() {
+        action_ewr.send(SolitaireActionEvent::AutoCompleteStep);
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{
+    events::SolitaireActionEvent,
+    klondike::Pile,
+    resources::{
+        drag::{Drag, DraggedCards},
+        game::Game,
+    },
+};
+
+/// Turns clicks and key presses into [SolitaireActionEvent]s.
+/// Face up cards are dragged with the left mouse button and dropped onto another pile, clicking the stock draws cards
+/// and right clicking a card sends it to a foundation. Ctrl+Z or U undoes the last move, A starts the auto complete
+#[allow(clippy::too_many_arguments)]
+pub fn input(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    game: Res<Game>,
+    mut drag: ResMut<Drag>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut keyboard_event_read: EventReader<KeyboardInput>,
+    mut button_event_read: EventReader<MouseButtonInput>,
+    mut action_ewr: EventWriter<SolitaireActionEvent>,
+) {
+    if let Ok(window) = windows.get_single() {
+        // Window to world space. Window y grows downwards
+        let cursor = window.cursor_position().map(|cursor| {
+            Vec2::new(
+                cursor.x - window.width() / 2.,
+                window.height() / 2. - cursor.y,
+            )
+        });
+
+        if let (Some(dragged), Some(position)) = (&mut drag.0, cursor) {
+            dragged.cursor = position;
+        }
+
+        for event in button_event_read.read() {
+            let position = match cursor {
+                Some(t) => t,
+                None => continue,
+            };
+            match (event.button, event.state) {
+                (MouseButton::Left, ButtonState::Pressed) => {
+                    match game.layout.hit_test(&game.klondike, position) {
+                        Some((Pile::Stock, _)) => {
+                            action_ewr.send(SolitaireActionEvent::Draw);
+                        }
+                        Some((from, Some(index))) if game.klondike.pile(from)[index].face_up => {
+                            let center = game.layout.card_position(&game.klondike, from, index);
+                            drag.0 = Some(DraggedCards {
+                                from,
+                                index,
+                                grab_offset: position - center,
+                                cursor: position,
+                            });
+                        }
+                        _ => (),
+                    }
+                }
+                (MouseButton::Left, ButtonState::Released) => {
+                    // Cards that aren't dropped onto a pile simply snap back
+                    let dragged = match drag.0.take() {
+                        Some(t) => t,
+                        None => continue,
+                    };
+                    if let Some(to) = game.layout.drop_target(&game.klondike, position) {
+                        if to != dragged.from {
+                            action_ewr.send(SolitaireActionEvent::Move {
+                                from: dragged.from,
+                                index: dragged.index,
+                                to,
+                            });
+                        }
+                    }
+                }
+                (MouseButton::Right, ButtonState::Pressed) => {
+                    if let Some((from, Some(_))) = game.layout.hit_test(&game.klondike, position) {
+                        action_ewr.send(SolitaireActionEvent::ToFoundation(from));
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    for event in keyboard_event_read.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let action = match event.key_code {
+            KeyCode::KeyU => SolitaireActionEvent::Undo,
+            KeyCode::KeyZ if control => SolitaireActionEvent::Undo,
+            KeyCode::KeyA => SolitaireActionEvent::AutoComplete,
+            KeyCode::Space => SolitaireActionEvent::Draw,
+            _ => continue,
+        };
+        action_ewr.send(action);
+    }
+}
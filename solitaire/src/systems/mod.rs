@@ -0,0 +1,5 @@
+pub mod action_event_handler;
+pub mod auto_complete;
+pub mod input;
+pub mod refresh;
+pub mod rescale;
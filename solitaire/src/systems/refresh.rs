@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{card_marker::CardMarker, card_text_marker::CardTextMarker},
+    klondike::Pile,
+    resources::{drag::Drag, game::Game},
+    CARD_BACK_COLOR, CARD_FACE_COLOR, CARD_Z_STEP, DRAG_Z, FOREGROUND_Z,
+};
+
+/// Moves every card to its place in the [crate::klondike::Klondike] and turns it face up or down.
+/// Dragged cards follow the cursor instead
+pub fn refresh(
+    game: Res<Game>,
+    drag: Res<Drag>,
+    mut cards: Query<(&CardMarker, &mut Transform, &mut Sprite)>,
+    mut texts: Query<(&Parent, &mut Visibility), With<CardTextMarker>>,
+) {
+    let places = game.card_places();
+    let mut face_up = bevy::utils::HashMap::new();
+
+    for (card, mut transform, mut sprite) in cards.iter_mut() {
+        let (pile, index, up) = match places.get(&**card) {
+            Some(t) => *t,
+            None => continue,
+        };
+
+        let (position, z) = match drag.0 {
+            Some(dragged) if dragged.from == pile && index >= dragged.index => {
+                let above = (index - dragged.index) as f32;
+                let fan = match pile {
+                    Pile::Tableau(_) => game.layout.fan_offset() * above,
+                    _ => Vec2::ZERO,
+                };
+                (
+                    dragged.cursor - dragged.grab_offset + fan,
+                    DRAG_Z + above * CARD_Z_STEP,
+                )
+            }
+            _ => (
+                game.layout.card_position(&game.klondike, pile, index),
+                FOREGROUND_Z + index as f32 * CARD_Z_STEP,
+            ),
+        };
+
+        transform.translation = position.extend(z);
+        sprite.color = match up {
+            true => CARD_FACE_COLOR,
+            false => CARD_BACK_COLOR,
+        };
+        face_up.insert(card.0, up);
+    }
+
+    for (parent, mut visibility) in texts.iter_mut() {
+        let up = cards
+            .get(parent.get())
+            .ok()
+            .and_then(|(card, _, _)| face_up.get(&card.0))
+            .copied()
+            .unwrap_or(false);
+        *visibility = match up {
+            true => Visibility::Inherited,
+            false => Visibility::Hidden,
+        };
+    }
+}
@@ -0,0 +1,54 @@
+use bevy::{prelude::*, window::PrimaryWindow, window::WindowResized};
+
+use crate::{
+    board::{card_sprite_size, font_size, text_translation},
+    components::{
+        card_marker::CardMarker, card_text_marker::CardTextMarker, pile_marker::PileMarker,
+    },
+    resources::{game::Game, layout::Layout},
+    BACKGROUND_Z,
+};
+
+/// Changes card size and pile positions if window size changed. The cards themselves are moved by the refresh system
+#[allow(clippy::type_complexity)]
+pub fn rescale(
+    mut piles: Query<(&mut Transform, &mut Sprite, &PileMarker), Without<CardMarker>>,
+    mut cards: Query<&mut Sprite, (With<CardMarker>, Without<PileMarker>)>,
+    mut texts: Query<(&mut Transform, &mut Text), (With<CardTextMarker>, Without<PileMarker>)>,
+    mut game: Option<ResMut<Game>>,
+    mut resize_event: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    if resize_event.read().count() == 0 {
+        return;
+    }
+
+    let game = match &mut game {
+        Some(t) => t,
+        None => return,
+    };
+
+    let window = match windows.get_single() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    let layout = Layout::new(Vec2::new(window.width(), window.height()));
+    game.layout = layout;
+
+    for (mut transform, mut sprite, pile) in piles.iter_mut() {
+        sprite.custom_size = Some(layout.card_size);
+        transform.translation = layout.pile_position(pile.0).extend(BACKGROUND_Z);
+    }
+
+    for mut sprite in cards.iter_mut() {
+        sprite.custom_size = Some(card_sprite_size(&layout));
+    }
+
+    for (mut transform, mut text) in texts.iter_mut() {
+        transform.translation = text_translation(&layout);
+        for section in text.sections.iter_mut() {
+            section.style.font_size = font_size(&layout);
+        }
+    }
+}
@@ -4,6 +4,8 @@ use bevy::ecs::schedule::States;
 pub enum AppState {
     #[default]
     Loading,
+    /// Some assets couldn't be loaded, the game waits until the error is read
+    LoadFailed,
     Running,
 }
 
@@ -2,6 +2,8 @@
 pub enum Error {
     #[error("Generic error '{0}'")]
     Generic(String),
+    #[error("Couldn't load '{0}'")]
+    Asset(String),
     #[error("3rd party error '{0}'")]
     Other(Box<dyn std::error::Error>),
 }
@@ -0,0 +1,46 @@
+/// The game that gets started. Selected with the first command line argument, defaults to minesweeper
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Game {
+    #[default]
+    Minesweeper,
+    TwentyFortyEight,
+    Sudoku,
+    Nonogram,
+    Solitaire,
+    LightsOut,
+}
+
+impl Game {
+    pub fn from_args() -> Self {
+        match std::env::args().nth(1) {
+            Some(arg) => Self::from_name(&arg).unwrap_or_else(|| {
+                bevy::log::warn!("Unknown game '{}', starting minesweeper.", arg);
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "minesweeper" => Some(Self::Minesweeper),
+            "2048" | "twenty_forty_eight" => Some(Self::TwentyFortyEight),
+            "sudoku" => Some(Self::Sudoku),
+            "nonogram" => Some(Self::Nonogram),
+            "solitaire" | "klondike" => Some(Self::Solitaire),
+            "lights_out" | "lightsout" => Some(Self::LightsOut),
+            _ => None,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Minesweeper => "Mine Sweeper!",
+            Self::TwentyFortyEight => "2048!",
+            Self::Sudoku => "Sudoku!",
+            Self::Nonogram => "Nonogram!",
+            Self::Solitaire => "Solitaire!",
+            Self::LightsOut => "Lights Out!",
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+};
+
+use crate::{app_state::AppState, error::Error};
+
+/// Root of the loading screen
+#[derive(Debug, Component)]
+pub struct LoadingScreen;
+
+/// Filled part of the progress bar
+#[derive(Debug, Component)]
+pub struct ProgressBar;
+
+/// Root of the dialog that lists the assets that couldn't be loaded
+#[derive(Debug, Component)]
+pub struct ErrorDialog;
+
+pub fn spawn_loading_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    row_gap: Val::Px(20.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Name::new("Loading Screen"),
+            LoadingScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Loading",
+                TextStyle {
+                    font_size: 30.,
+                    color: Color::WHITE,
+                    ..Default::default()
+                },
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(300.),
+                        height: Val::Px(20.),
+                        border: UiRect::all(Val::Px(2.)),
+                        ..Default::default()
+                    },
+                    border_color: Color::WHITE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                width: Val::Percent(0.),
+                                height: Val::Percent(100.),
+                                ..Default::default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..Default::default()
+                        },
+                        ProgressBar,
+                    ));
+                });
+        });
+}
+
+/// Fills the progress bar to the share of `loaded` assets
+pub fn show_progress(bars: &mut Query<&mut Style, With<ProgressBar>>, loaded: usize, total: usize) {
+    let progress = loaded as f32 / total.max(1) as f32;
+    for mut style in bars.iter_mut() {
+        style.width = Val::Percent(progress * 100.);
+    }
+}
+
+pub fn despawn_loading_screen(mut commands: Commands, screens: Query<Entity, With<LoadingScreen>>) {
+    for entity in screens.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Shows what couldn't be loaded until a key or mouse button is pressed
+pub fn spawn_error_dialog(commands: &mut Commands, errors: &[Error]) {
+    let mut text: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    text.push(String::new());
+    text.push("Press any key to continue without them".to_string());
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            Name::new("Error Dialog"),
+            ErrorDialog,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        max_width: Val::Percent(80.),
+                        padding: UiRect::all(Val::Px(20.)),
+                        border: UiRect::all(Val::Px(2.)),
+                        ..Default::default()
+                    },
+                    background_color: Color::rgb(0.25, 0.08, 0.08).into(),
+                    border_color: Color::WHITE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        text.join("\n"),
+                        TextStyle {
+                            font_size: 20.,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                    ));
+                });
+        });
+}
+
+pub fn close_error_dialog(
+    mut commands: Commands,
+    mut keys: EventReader<KeyboardInput>,
+    mut buttons: EventReader<MouseButtonInput>,
+    dialogs: Query<Entity, With<ErrorDialog>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let pressed = keys.read().any(|event| event.state == ButtonState::Pressed)
+        | buttons
+            .read()
+            .any(|event| event.state == ButtonState::Pressed);
+    if !pressed {
+        return;
+    }
+    for entity in dialogs.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    next_state.set(AppState::Running);
+}
@@ -2,24 +2,37 @@ pub(crate) mod app_state;
 pub(crate) mod asset_handles;
 pub(crate) mod error;
 pub(crate) mod events;
+pub(crate) mod game;
+pub(crate) mod loading;
 pub(crate) mod prelude;
 
 use app_state::{AppState, PauseState};
 use asset_handles::AssetHandles;
 use bevy::{
-    asset,
+    asset::{LoadState, UntypedAssetId},
     input::{keyboard::KeyboardInput, ButtonState},
     log::LogPlugin,
     prelude::*,
     utils::petgraph::matrix_graph::Zero,
     window::WindowResolution,
 };
+use error::Error;
 use events::{GameLostEvent, GameResetEvent, GameWonEvent};
+use game::Game;
+use loading::ProgressBar;
+use minesweeper::{
+    fallback_theme::insert_fallback_theme,
+    mask::BoardMask,
+    options::{AccessibilityOptions, BoardOptions, EndlessOptions, SettingsError},
+    theme::Theme,
+};
+use std::path::PathBuf;
 
 fn main() {
+    let game = Game::from_args();
     let primary_window = Window {
         resolution: WindowResolution::new(850., 850.),
-        title: "Mine Sweeper!".to_string(),
+        title: game.title().to_string(),
         ..Default::default()
     };
     let mut app = App::new();
@@ -41,37 +54,86 @@ fn main() {
     #[cfg(feature = "debug")]
     app.add_plugins(bevy_inspector_egui::quick::WorldInspectorPlugin::new());
 
-    app.insert_resource(minesweeper::options::BoardOptions {
-        map_size: (20, 20),
-        bomb_count: 40,
-        ..Default::default()
-    })
-    .insert_resource(AssetHandles::default())
-    .add_plugins(minesweeper::MinesweeperPlugin::new(
-        AppState::Running,
-        PauseState::Paused,
-        GameLostEvent,
-        GameResetEvent,
-        GameWonEvent,
-    ))
-    .init_state::<AppState>()
-    .init_state::<PauseState>()
-    .add_event::<GameLostEvent>()
-    .add_event::<GameWonEvent>()
-    .add_event::<GameResetEvent>()
-    .add_systems(
-        Startup,
-        (spawn_camera, load_assets).run_if(in_state(AppState::Loading)),
-    )
-    .add_systems(
-        Update,
-        (
-            is_loaded.run_if(in_state(AppState::Loading)),
-            handle_input.run_if(in_state(AppState::Running)),
-            game_over_handler,
-        ),
-    )
-    .run();
+    match game {
+        Game::Minesweeper => app
+            .insert_resource(BoardOptions {
+                map_size: (20, 20),
+                bomb_count: 40,
+                mask: board_mask(),
+                endless: endless_options(),
+                ..Default::default()
+            })
+            .insert_resource(accessibility_options())
+            .add_systems(
+                Update,
+                save_accessibility_options.run_if(resource_changed::<AccessibilityOptions>),
+            )
+            .add_plugins(minesweeper::MinesweeperPlugin::new(
+                AppState::Running,
+                PauseState::Paused,
+                GameResetEvent,
+                GameWonEvent,
+                GameLostEvent,
+            )),
+        Game::TwentyFortyEight => app.add_plugins(twenty_forty_eight::TwentyFortyEightPlugin::new(
+            AppState::Running,
+            PauseState::Paused,
+            GameResetEvent,
+            GameWonEvent,
+            GameLostEvent,
+        )),
+        Game::Sudoku => app.add_plugins(sudoku::SudokuPlugin::new(
+            AppState::Running,
+            PauseState::Paused,
+            GameResetEvent,
+            GameWonEvent,
+            GameLostEvent,
+        )),
+        Game::Nonogram => app.add_plugins(nonogram::NonogramPlugin::new(
+            AppState::Running,
+            PauseState::Paused,
+            GameResetEvent,
+            GameWonEvent,
+            GameLostEvent,
+        )),
+        Game::Solitaire => app.add_plugins(solitaire::SolitairePlugin::new(
+            AppState::Running,
+            PauseState::Paused,
+            GameResetEvent,
+            GameWonEvent,
+            GameLostEvent,
+        )),
+        Game::LightsOut => app.add_plugins(lights_out::LightsOutPlugin::new(
+            AppState::Running,
+            PauseState::Paused,
+            GameResetEvent,
+            GameWonEvent,
+            GameLostEvent,
+        )),
+    };
+
+    app.insert_resource(AssetHandles::default())
+        .init_state::<AppState>()
+        .init_state::<PauseState>()
+        .add_event::<GameLostEvent>()
+        .add_event::<GameWonEvent>()
+        .add_event::<GameResetEvent>()
+        .add_systems(
+            Startup,
+            (spawn_camera, loading::spawn_loading_screen, load_assets)
+                .run_if(in_state(AppState::Loading)),
+        )
+        .add_systems(OnExit(AppState::Loading), loading::despawn_loading_screen)
+        .add_systems(
+            Update,
+            (
+                is_loaded.run_if(in_state(AppState::Loading)),
+                loading::close_error_dialog.run_if(in_state(AppState::LoadFailed)),
+                handle_input.run_if(in_state(AppState::Running)),
+                game_over_handler,
+            ),
+        )
+        .run();
 }
 
 pub fn spawn_camera(mut commands: Commands) {
@@ -104,59 +166,177 @@ pub fn game_over_handler(
     evw_game_reset_event.send(GameResetEvent);
 }
 
-pub fn load_assets(asset_server: Res<AssetServer>, mut handles: ResMut<AssetHandles>) {
-    handles.push(asset_server.load::<Image>("./minesweeper/0.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/1.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/2.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/3.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/4.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/5.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/6.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/7.png").untyped());
-    handles.push(asset_server.load::<Image>("./minesweeper/8.png").untyped());
-    handles.push(
-        asset_server
-            .load::<Image>("./minesweeper/Base.png")
-            .untyped(),
-    );
-    handles.push(
-        asset_server
-            .load::<Image>("./minesweeper/Bomb.png")
-            .untyped(),
-    );
-    handles.push(
-        asset_server
-            .load::<Image>("./minesweeper/Flag.png")
-            .untyped(),
-    );
+pub fn load_assets(
+    asset_server: Res<AssetServer>,
+    mut handles: ResMut<AssetHandles>,
+    board_options: Option<Res<BoardOptions>>,
+    accessibility: Option<Res<AccessibilityOptions>>,
+) {
+    // Only minesweeper has board options, the theme loader doesn't exist for other games
+    if let Some(board_options) = board_options {
+        let theme = match accessibility {
+            Some(accessibility) => accessibility.theme(&board_options.theme).to_string(),
+            None => board_options.theme.clone(),
+        };
+        handles.push(asset_server.load::<Theme>(theme).untyped());
+    }
 }
 
+/// Moves on once every asset is loaded or failed to load. The files that failed are shown, minesweeper gets its
+/// built-in theme if its theme can't be used. Sounds that failed are only logged
+#[allow(clippy::too_many_arguments)]
 pub fn is_loaded(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     handles: Res<AssetHandles>,
+    mut themes: Option<ResMut<Assets<Theme>>>,
+    mut images: ResMut<Assets<Image>>,
+    board_options: Option<Res<BoardOptions>>,
+    mut progress_bars: Query<&mut Style, With<ProgressBar>>,
     mut next_state: ResMut<NextState<AppState>>,
 ) {
-    if handles
+    let theme_handle = handles
+        .iter()
+        .find_map(|handle| handle.clone().try_typed::<Theme>().ok());
+    let theme = theme_handle
+        .as_ref()
+        .zip(themes.as_deref())
+        .and_then(|(handle, themes)| themes.get(handle));
+    // The files of a theme are only known once the theme file itself is loaded
+    let theme_files: Vec<UntypedAssetId> = theme
+        .map(|theme| theme.dependencies().collect())
+        .unwrap_or_default();
+    // The game works without sounds, they don't need to be reported
+    let sounds: Vec<UntypedAssetId> = theme
+        .map(|theme| theme.sounds.ids().collect())
+        .unwrap_or_default();
+    let ids: Vec<UntypedAssetId> = handles
+        .iter()
+        .map(|handle| handle.id())
+        .chain(theme_files)
+        .collect();
+
+    // Assets without a state, like the default font, have nothing to wait for
+    let states: Vec<Option<LoadState>> = ids
+        .iter()
+        .map(|id| asset_server.get_load_state(*id))
+        .collect();
+    let finished = |state: &&Option<LoadState>| {
+        !matches!(state, Some(LoadState::NotLoaded | LoadState::Loading))
+    };
+    let finished_count = states.iter().filter(finished).count();
+    loading::show_progress(&mut progress_bars, finished_count, ids.len());
+    if finished_count < ids.len() {
+        return;
+    }
+
+    let path = |id: &UntypedAssetId| {
+        asset_server
+            .get_path(*id)
+            .map_or_else(|| id.to_string(), |path| path.to_string())
+    };
+    let (missing_sounds, failed): (Vec<&UntypedAssetId>, Vec<&UntypedAssetId>) = ids
         .iter()
-        .map(|h| asset_server.get_load_state(h))
-        .all(|state| state == Some(asset::LoadState::Loaded))
+        .zip(&states)
+        .filter(|(_, state)| **state == Some(LoadState::Failed))
+        .map(|(id, _)| id)
+        .partition(|id| sounds.contains(id));
+    for id in missing_sounds {
+        bevy::log::warn!(
+            "Couldn't load the sound '{}', playing without it.",
+            path(id)
+        );
+    }
+    let errors: Vec<Error> = failed
+        .into_iter()
+        .map(|id| Error::Asset(path(id)))
+        .collect();
+    for error in &errors {
+        bevy::log::error!("{}", error);
+    }
+
+    if let (Some(handle), Some(themes), Some(board_options)) =
+        (theme_handle, themes.as_deref_mut(), board_options)
     {
-        commands.insert_resource(minesweeper::texture_handles::TextureHandles {
-            tile_0: asset_server.load::<Image>("./minesweeper/0.png"),
-            tile_1: asset_server.load::<Image>("./minesweeper/1.png"),
-            tile_2: asset_server.load::<Image>("./minesweeper/2.png"),
-            tile_3: asset_server.load::<Image>("./minesweeper/3.png"),
-            tile_4: asset_server.load::<Image>("./minesweeper/4.png"),
-            tile_5: asset_server.load::<Image>("./minesweeper/5.png"),
-            tile_6: asset_server.load::<Image>("./minesweeper/6.png"),
-            tile_7: asset_server.load::<Image>("./minesweeper/7.png"),
-            tile_8: asset_server.load::<Image>("./minesweeper/8.png"),
-            tile_base: asset_server.load::<Image>("./minesweeper/Base.png"),
-            tile_bomb: asset_server.load::<Image>("./minesweeper/Bomb.png"),
-            tile_flag: asset_server.load::<Image>("./minesweeper/Flag.png"),
-        });
-        next_state.set(AppState::Running)
+        let topology = board_options.tile_topology();
+        match themes.get(&handle) {
+            Some(theme) if theme.load_state(&asset_server) == LoadState::Loaded => {
+                theme.insert_resources(&mut commands, handle, topology)
+            }
+            _ => {
+                bevy::log::warn!("Couldn't load the theme, using the built-in one.");
+                insert_fallback_theme(&mut commands, &mut images, themes, topology);
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        next_state.set(AppState::Running);
+    } else {
+        loading::spawn_error_dialog(&mut commands, &errors);
+        next_state.set(AppState::LoadFailed);
+    }
+}
+
+/// The second command line argument is an optional mask file that shapes the minesweeper board
+fn board_mask() -> Option<BoardMask> {
+    let path = std::env::args().nth(2).filter(|arg| arg != ENDLESS_ARG)?;
+    BoardMask::load(&path)
+        .map_err(|error| bevy::log::error!("Can't load mask '{}': {}", path, error))
+        .ok()
+}
+
+/// Passing `endless` instead of a mask file plays minesweeper on an endless board
+const ENDLESS_ARG: &str = "endless";
+
+fn endless_options() -> Option<EndlessOptions> {
+    std::env::args()
+        .nth(2)
+        .filter(|arg| arg == ENDLESS_ARG)
+        .map(|_| EndlessOptions::default())
+}
+
+/// The accessibility settings of minesweeper are kept in this file in the [config_dir]
+const ACCESSIBILITY_FILE: &str = "accessibility.ron";
+
+/// Folder of the user for the settings of the game, like `~/.config/game_collection` on Linux. It's left to the
+/// working directory only on systems that don't say where the home of the user is
+fn config_dir() -> PathBuf {
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    let base = if cfg!(windows) {
+        var("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.unwrap_or_default().join("game_collection")
+}
+
+fn accessibility_options() -> AccessibilityOptions {
+    match AccessibilityOptions::load(config_dir().join(ACCESSIBILITY_FILE)) {
+        Ok(options) => options,
+        // Nothing was saved yet
+        Err(SettingsError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+            Default::default()
+        }
+        Err(error) => {
+            bevy::log::error!("Can't load the accessibility settings: {}", error);
+            Default::default()
+        }
+    }
+}
+
+/// Saves the accessibility settings whenever they are changed
+pub fn save_accessibility_options(options: Res<AccessibilityOptions>) {
+    // They were just loaded
+    if options.is_added() {
+        return;
+    }
+    if let Err(error) = options.save(config_dir().join(ACCESSIBILITY_FILE)) {
+        bevy::log::error!("Can't save the accessibility settings: {}", error);
     }
 }
 
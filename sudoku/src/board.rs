@@ -0,0 +1,149 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    components::{
+        board_marker::BoardMarker, cell::Cell, digit_text_marker::DigitTextMarker,
+        marks_text_marker::MarksTextMarker,
+    },
+    helpers::board_layout,
+    options::SudokuOptions,
+    puzzle::{Sudoku, CELL_COUNT},
+    resources::game::Game,
+    states::plugin_state::SudokuState,
+    texture_handles::TextureHandles,
+    SudokuPlugin, BACKGROUND_Z, FOREGROUND_Z, MARKS_COLOR,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > SudokuPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn create_board(
+        mut commands: Commands,
+        windows: Query<&Window, With<PrimaryWindow>>,
+        options: Option<Res<SudokuOptions>>,
+        textures: Option<Res<TextureHandles>>,
+        old_board: Query<Entity, With<BoardMarker>>,
+        mut app_exit_events: ResMut<Events<bevy::app::AppExit>>,
+        mut next_state: ResMut<NextState<SudokuState>>,
+    ) {
+        let window = match windows.get_single() {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+
+        // Despawn old board if it exists
+        if let Ok(t) = old_board.get_single() {
+            commands.entity(t).despawn_recursive();
+        }
+
+        let textures = match textures {
+            Some(t) => t,
+            None => {
+                bevy::log::error!("No texture handles. Can't continue.");
+                app_exit_events.send(bevy::app::AppExit);
+                return;
+            }
+        };
+
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+
+        let (puzzle, solution) = Sudoku::generate(options.difficulty, &mut rand::thread_rng());
+        bevy::log::info!(
+            "Generated {:?} puzzle with {} givens",
+            options.difficulty,
+            puzzle.given_count()
+        );
+        bevy::log::debug!("\n{}", puzzle);
+
+        let (tile_size, board_position) = board_layout(window, &options);
+        let mut game = Game::new(puzzle, solution, tile_size, options.box_gap);
+        game.origin = board_position.xy();
+
+        commands
+            .spawn((
+                Name::new("Board"),
+                SpatialBundle {
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                },
+                BoardMarker,
+            ))
+            .with_children(|parent| {
+                for index in 0..CELL_COUNT {
+                    parent
+                        .spawn((
+                            SpriteBundle {
+                                sprite: Sprite {
+                                    color: Color::WHITE,
+                                    custom_size: Some(Vec2::splat(
+                                        tile_size - options.tile_padding,
+                                    )),
+                                    ..Default::default()
+                                },
+                                texture: textures.cell.clone(),
+                                transform: Transform::from_translation(
+                                    game.translation(index, BACKGROUND_Z),
+                                ),
+                                ..Default::default()
+                            },
+                            Name::new(format!("Cell: {}", index)),
+                            Cell(index),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text2dBundle {
+                                    text: Text::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font_size: tile_size * 0.6,
+                                            ..Default::default()
+                                        },
+                                    )
+                                    .with_justify(JustifyText::Center),
+                                    transform: Transform::from_xyz(0., 0., FOREGROUND_Z),
+                                    ..Default::default()
+                                },
+                                DigitTextMarker,
+                            ));
+                            parent.spawn((
+                                Text2dBundle {
+                                    text: Text::from_section(
+                                        String::new(),
+                                        TextStyle {
+                                            font_size: tile_size * 0.25,
+                                            color: MARKS_COLOR,
+                                            ..Default::default()
+                                        },
+                                    )
+                                    .with_justify(JustifyText::Center),
+                                    transform: Transform::from_xyz(0., 0., FOREGROUND_Z),
+                                    ..Default::default()
+                                },
+                                MarksTextMarker,
+                            ));
+                        });
+                }
+            });
+
+        commands.insert_resource(game);
+        next_state.set(SudokuState::Running);
+    }
+
+    pub fn reset_event_handler(
+        mut state: ResMut<NextState<SudokuState>>,
+        mut events: EventReader<TyResetEvent>,
+    ) {
+        if events.read().count() == 0 {
+            return;
+        }
+        state.set(SudokuState::NewGame);
+    }
+}
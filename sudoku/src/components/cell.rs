@@ -0,0 +1,13 @@
+use bevy::prelude::*;
+
+/// A cell of the sudoku. Contains the index into the [crate::puzzle::Sudoku], counted row by row from the top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub struct Cell(pub usize);
+
+impl std::ops::Deref for Cell {
+    type Target = usize;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
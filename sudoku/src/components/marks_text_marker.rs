@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Marks the text showing the pencil marks of a cell
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct MarksTextMarker;
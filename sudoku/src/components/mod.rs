@@ -0,0 +1,4 @@
+pub mod board_marker;
+pub mod cell;
+pub mod digit_text_marker;
+pub mod marks_text_marker;
@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+/// Everything the player can do. Sent by the input system and applied to the [crate::resources::game::Game]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub(crate) enum SudokuActionEvent {
+    /// Select the cell with this index
+    Select(usize),
+    /// Move the selection by this many columns and rows
+    MoveSelection(i8, i8),
+    /// Enter a digit, or toggle a pencil mark if `pencil` is set
+    Enter {
+        digit: u8,
+        pencil: bool,
+    },
+    /// Clear the digit and pencil marks of the selected cell
+    Clear,
+    /// Switch between entering digits and pencil marks
+    TogglePencil,
+    Undo,
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::options::{SudokuOptions, TileSize};
+
+/// Computes the tile size and the position of the bottom left corner of the board
+pub(crate) fn board_layout(window: &Window, options: &SudokuOptions) -> (f32, Vec3) {
+    let tile_size = match options.tile_size {
+        TileSize::Fixed(v) => v,
+        TileSize::Adaptive { min, max } => {
+            let available = window.width().min(window.height()) - 2. * options.box_gap;
+            (available / 9.).clamp(min, max)
+        }
+    };
+    let board_size = board_size(tile_size, options.box_gap);
+    (
+        tile_size,
+        Vec3::new(-(board_size / 2.), -(board_size / 2.), 0.),
+    )
+}
+
+/// Width and height of the board including the gaps between the boxes
+pub(crate) fn board_size(tile_size: f32, box_gap: f32) -> f32 {
+    9. * tile_size + 2. * box_gap
+}
+
+/// Offset of the start of a row or column from the board origin
+pub(crate) fn line_offset(line: usize, tile_size: f32, box_gap: f32) -> f32 {
+    line as f32 * tile_size + (line / 3) as f32 * box_gap
+}
+
+/// Text for the pencil marks of a cell as a 3x3 block of digits
+pub(crate) fn marks_string(marks: u16) -> String {
+    (1..=9)
+        .map(|digit| {
+            let char = match marks & 1 << digit != 0 {
+                true => (b'0' + digit) as char,
+                false => ' ',
+            };
+            match digit {
+                3 | 6 => format!("{}\n", char),
+                9 => char.to_string(),
+                _ => format!("{} ", char),
+            }
+        })
+        .collect()
+}
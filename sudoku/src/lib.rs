@@ -0,0 +1,104 @@
+pub(crate) mod board;
+pub(crate) mod components;
+pub(crate) mod events;
+pub(crate) mod helpers;
+pub mod options;
+pub mod puzzle;
+pub(crate) mod resources;
+pub(crate) mod states;
+pub(crate) mod systems;
+pub(crate) mod texture_handles;
+
+use bevy::prelude::*;
+
+use crate::{
+    events::SudokuActionEvent, resources::plugin_options::PluginOptions,
+    states::plugin_state::SudokuState,
+};
+
+pub(crate) const BACKGROUND_Z: f32 = 0.;
+pub(crate) const FOREGROUND_Z: f32 = 1.;
+
+pub(crate) const GIVEN_COLOR: Color = Color::BLACK;
+pub(crate) const ENTERED_COLOR: Color = Color::rgb(0., 0.02, 0.67);
+pub(crate) const CONFLICT_COLOR: Color = Color::rgb(0.67, 0., 0.);
+pub(crate) const MARKS_COLOR: Color = Color::rgb(0.85, 0.85, 0.85);
+pub(crate) const SELECTED_TINT: Color = Color::rgb(1., 0.9, 0.5);
+pub(crate) const PEER_TINT: Color = Color::rgb(0.85, 0.9, 1.);
+
+pub struct SudokuPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+where
+    TyRunState: States + PartialEq,
+    TyPauseState: States + PartialEq,
+    TyResetEvent: Event + Clone,
+    TyWonEvent: Event + Clone,
+    TyLostEvent: Event + Clone,
+{
+    pub run_state: TyRunState,
+    pub pause_state: TyPauseState,
+    pub reset_event: TyResetEvent,
+    pub won_event: TyWonEvent,
+    pub lost_event: TyLostEvent,
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > Plugin for SudokuPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Events need to be already added to the engine!
+    fn build(&self, app: &mut App) {
+        app.init_state::<SudokuState>()
+            .add_event::<SudokuActionEvent>()
+            .insert_resource(PluginOptions {
+                won_event: self.won_event.clone(),
+            })
+            .add_systems(Startup, crate::texture_handles::load_textures)
+            .add_systems(
+                Update,
+                (
+                    Self::create_board
+                        .run_if(in_state(SudokuState::NewGame))
+                        .run_if(in_state(self.run_state.clone())),
+                    crate::systems::rescale::rescale,
+                    crate::systems::input::input.run_if(
+                        in_state(SudokuState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    Self::action_event_handler.run_if(in_state(SudokuState::Running)),
+                    crate::systems::refresh::refresh.run_if(in_state(SudokuState::Running)),
+                    Self::reset_event_handler,
+                ),
+            );
+        bevy::log::info!("Plugin loaded.");
+    }
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > SudokuPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn new(
+        run_state: TyRunState,
+        pause_state: TyPauseState,
+        reset_event: TyResetEvent,
+        won_event: TyWonEvent,
+        lost_event: TyLostEvent,
+    ) -> Self {
+        Self {
+            run_state,
+            pause_state,
+            reset_event,
+            won_event,
+            lost_event,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::puzzle::Difficulty;
+
+/// Determines wether the tiles should be fixed (don't change with window scaling) or adaptive (change with window resizing but only above a minimum and below a maximum)
+#[derive(Debug, Clone, Resource)]
+pub enum TileSize {
+    Fixed(f32),
+    Adaptive { min: f32, max: f32 },
+}
+
+/// Vairous options relating to the sudoku
+#[derive(Debug, Clone, Resource)]
+pub struct SudokuOptions {
+    /// Difficulty of newly generated puzzles
+    pub difficulty: Difficulty,
+    /// Tile world size
+    pub tile_size: TileSize,
+    /// Padding between tiles
+    pub tile_padding: f32,
+    /// Extra space between the 3x3 boxes
+    pub box_gap: f32,
+}
+
+impl Default for TileSize {
+    fn default() -> Self {
+        Self::Adaptive {
+            min: 20.0,
+            max: 80.0,
+        }
+    }
+}
+
+impl Default for SudokuOptions {
+    fn default() -> Self {
+        Self {
+            difficulty: Default::default(),
+            tile_size: Default::default(),
+            tile_padding: 2.,
+            box_gap: 6.,
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use rand::{seq::SliceRandom, RngCore};
+
+/// Number of cells in a 9x9 sudoku
+pub const CELL_COUNT: usize = 81;
+
+/// How many givens a generated puzzle keeps. Fewer givens usually means a harder puzzle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+    Expert,
+}
+
+impl Difficulty {
+    /// The generator removes givens until it reaches this count or no more can be removed without losing uniqueness
+    pub const fn target_givens(&self) -> usize {
+        match self {
+            Self::Easy => 40,
+            Self::Medium => 32,
+            Self::Hard => 27,
+            Self::Expert => 22,
+        }
+    }
+}
+
+/// A 9x9 grid of digits where 0 is an empty cell. Cells are stored row by row starting top left
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Sudoku {
+    cells: [u8; CELL_COUNT],
+}
+
+impl Default for Sudoku {
+    fn default() -> Self {
+        Self {
+            cells: [0; CELL_COUNT],
+        }
+    }
+}
+
+impl Sudoku {
+    /// Parses 81 characters where `1`-`9` are digits and `0` or `.` are empty cells. Whitespace is ignored
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut sudoku = Self::default();
+        let mut index = 0;
+        for char in text.chars().filter(|c| !c.is_whitespace()) {
+            if index >= CELL_COUNT {
+                return None;
+            }
+            sudoku.cells[index] = match char {
+                '.' | '0' => 0,
+                '1'..='9' => char as u8 - b'0',
+                _ => return None,
+            };
+            index += 1;
+        }
+        (index == CELL_COUNT).then_some(sudoku)
+    }
+
+    pub fn get(&self, index: usize) -> u8 {
+        self.cells[index]
+    }
+
+    pub fn set(&mut self, index: usize, digit: u8) {
+        self.cells[index] = digit;
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cells.iter().all(|digit| *digit != 0)
+    }
+
+    pub fn given_count(&self) -> usize {
+        self.cells.iter().filter(|digit| **digit != 0).count()
+    }
+
+    /// Returns true if another cell in the same row, column or box holds the same digit
+    pub fn has_conflict(&self, index: usize) -> bool {
+        let digit = self.cells[index];
+        digit != 0 && peers(index).any(|peer| peer != index && self.cells[peer] == digit)
+    }
+
+    /// Bitmask of the digits that can still be placed in a cell. Bit n stands for digit n
+    pub fn candidates(&self, index: usize) -> u16 {
+        let used = peers(index)
+            .filter(|peer| *peer != index)
+            .fold(0u16, |mask, peer| mask | 1 << self.cells[peer]);
+        !used & ALL_DIGITS
+    }
+
+    /// Solves the sudoku with backtracking, always branching on the cell with the fewest candidates
+    pub fn solve(&self) -> Option<Self> {
+        let mut sudoku = *self;
+        let mut solutions = 0;
+        let mut first = None;
+        sudoku.search(&mut solutions, 1, &mut first, &mut None);
+        first
+    }
+
+    /// Counts solutions but stops once `limit` is reached
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut sudoku = *self;
+        let mut solutions = 0;
+        sudoku.search(&mut solutions, limit, &mut None, &mut None);
+        solutions
+    }
+
+    /// Generates a puzzle with exactly one solution. Returns the puzzle and its solution
+    pub fn generate(difficulty: Difficulty, rng: &mut dyn RngCore) -> (Self, Self) {
+        let mut solution = None;
+        let mut solutions = 0;
+        Self::default().search(&mut solutions, 1, &mut solution, &mut Some(&mut *rng));
+        // An empty grid always has a solution
+        let solution = solution.unwrap();
+
+        let mut puzzle = solution;
+        let mut order: Vec<usize> = (0..CELL_COUNT).collect();
+        order.shuffle(rng);
+
+        for index in order {
+            if puzzle.given_count() <= difficulty.target_givens() {
+                break;
+            }
+            let digit = puzzle.cells[index];
+            puzzle.cells[index] = 0;
+            if puzzle.count_solutions(2) != 1 {
+                puzzle.cells[index] = digit;
+            }
+        }
+
+        (puzzle, solution)
+    }
+
+    /// Returns true once `limit` solutions were found. The first solution is stored in `first`.
+    /// If `rng` is set the digits are tried in random order which is used to generate new grids
+    fn search(
+        &mut self,
+        solutions: &mut usize,
+        limit: usize,
+        first: &mut Option<Self>,
+        rng: &mut Option<&mut dyn RngCore>,
+    ) -> bool {
+        // Find the empty cell with the fewest candidates
+        let mut best: Option<(usize, u16)> = None;
+        for index in 0..CELL_COUNT {
+            if self.cells[index] != 0 {
+                continue;
+            }
+            let candidates = self.candidates(index);
+            if candidates == 0 {
+                return false;
+            }
+            if best.is_none_or(|(_, mask)| candidates.count_ones() < mask.count_ones()) {
+                best = Some((index, candidates));
+            }
+        }
+
+        let (index, candidates) = match best {
+            Some(t) => t,
+            None => {
+                *solutions += 1;
+                if first.is_none() {
+                    *first = Some(*self);
+                }
+                return *solutions >= limit;
+            }
+        };
+
+        let mut digits: Vec<u8> = (1..=9)
+            .filter(|digit| candidates & 1 << digit != 0)
+            .collect();
+        if let Some(rng) = rng.as_mut() {
+            digits.shuffle(rng);
+        }
+
+        for digit in digits {
+            self.cells[index] = digit;
+            if self.search(solutions, limit, first, rng) {
+                self.cells[index] = 0;
+                return true;
+            }
+        }
+        self.cells[index] = 0;
+        false
+    }
+}
+
+impl std::fmt::Display for Sudoku {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in 0..9 {
+            let line = (0..9)
+                .map(|column| match self.cells[row * 9 + column] {
+                    0 => '.',
+                    digit => (b'0' + digit) as char,
+                })
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+const ALL_DIGITS: u16 = 0b11_1111_1110;
+
+/// All cells in the same row, column and box as `index`, including `index` itself
+pub fn peers(index: usize) -> impl Iterator<Item = usize> {
+    let row = index / 9;
+    let column = index % 9;
+    let box_row = row / 3 * 3;
+    let box_column = column / 3 * 3;
+    (0..9)
+        .map(move |i| row * 9 + i)
+        .chain((0..9).map(move |i| i * 9 + column))
+        .chain((0..9).map(move |i| (box_row + i / 3) * 9 + box_column + i % 3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = "
+        53..7....
+        6..195...
+        .98....6.
+        8...6...3
+        4..8.3..1
+        7...2...6
+        .6....28.
+        ...419..5
+        ....8..79";
+
+    #[test]
+    fn solves_known_puzzle() {
+        let solution = Sudoku::parse(PUZZLE).unwrap().solve().unwrap();
+
+        assert!(solution.is_complete());
+        assert!((0..CELL_COUNT).all(|index| !solution.has_conflict(index)));
+        assert_eq!(solution.to_string().lines().next(), Some("534678912"));
+    }
+
+    #[test]
+    fn detects_multiple_solutions() {
+        assert_eq!(Sudoku::parse(PUZZLE).unwrap().count_solutions(2), 1);
+        assert_eq!(Sudoku::default().count_solutions(2), 2);
+    }
+
+    #[test]
+    fn generated_puzzle_is_unique() {
+        let (puzzle, solution) = Sudoku::generate(Difficulty::Medium, &mut rand::thread_rng());
+
+        assert_eq!(puzzle.count_solutions(2), 1);
+        assert_eq!(puzzle.solve(), Some(solution));
+        assert!(puzzle.given_count() < CELL_COUNT);
+    }
+
+    #[test]
+    fn conflicts_are_found() {
+        let mut sudoku = Sudoku::parse(PUZZLE).unwrap();
+        sudoku.set(2, 5);
+
+        assert!(sudoku.has_conflict(2));
+        assert!(sudoku.has_conflict(0));
+        assert!(!sudoku.has_conflict(4));
+    }
+}
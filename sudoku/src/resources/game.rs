@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+
+use crate::{
+    helpers::{board_size, line_offset},
+    puzzle::{Sudoku, CELL_COUNT},
+};
+
+/// The previous state of a cell before the player changed it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edit {
+    pub index: usize,
+    pub digit: u8,
+    pub marks: u16,
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct Game {
+    /// The generated puzzle. Non zero cells are givens and can't be changed
+    pub puzzle: Sudoku,
+    pub solution: Sudoku,
+    /// The puzzle with the digits the player entered
+    pub current: Sudoku,
+    /// Pencil marks of every cell. Bit n stands for digit n
+    pub marks: [u16; CELL_COUNT],
+    pub selected: Option<usize>,
+    pub pencil_mode: bool,
+    pub history: Vec<Edit>,
+    pub origin: Vec2,
+    pub tile_size: f32,
+    pub box_gap: f32,
+    /// The won event is only sent once per game
+    pub won: bool,
+}
+
+impl Game {
+    pub fn new(puzzle: Sudoku, solution: Sudoku, tile_size: f32, box_gap: f32) -> Self {
+        Self {
+            puzzle,
+            solution,
+            current: puzzle,
+            marks: [0; CELL_COUNT],
+            selected: None,
+            pencil_mode: false,
+            history: Vec::new(),
+            origin: Vec2::ZERO,
+            tile_size,
+            box_gap,
+            won: false,
+        }
+    }
+
+    pub fn is_given(&self, index: usize) -> bool {
+        self.puzzle.get(index) != 0
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.current == self.solution
+    }
+
+    pub fn move_selection(&mut self, (x, y): (i8, i8)) {
+        let index = self.selected.unwrap_or(0);
+        let column = (index % 9) as i8 + x;
+        let row = (index / 9) as i8 + y;
+        self.selected = Some(row.rem_euclid(9) as usize * 9 + column.rem_euclid(9) as usize);
+    }
+
+    /// Places a digit in the selected cell. Entering the digit the cell already holds clears it.
+    /// Returns false if nothing changed
+    pub fn enter(&mut self, digit: u8) -> bool {
+        let index = match self.editable_selection() {
+            Some(t) => t,
+            None => return false,
+        };
+        self.push_history(index);
+        let digit = match self.current.get(index) == digit {
+            true => 0,
+            false => digit,
+        };
+        self.current.set(index, digit);
+        true
+    }
+
+    /// Toggles a pencil mark of the selected cell. Returns false if nothing changed
+    pub fn toggle_mark(&mut self, digit: u8) -> bool {
+        let index = match self.editable_selection() {
+            Some(t) => t,
+            None => return false,
+        };
+        self.push_history(index);
+        self.marks[index] ^= 1 << digit;
+        true
+    }
+
+    /// Removes the digit and all pencil marks of the selected cell. Returns false if nothing changed
+    pub fn clear(&mut self) -> bool {
+        let index = match self.editable_selection() {
+            Some(t) => t,
+            None => return false,
+        };
+        if self.current.get(index) == 0 && self.marks[index] == 0 {
+            return false;
+        }
+        self.push_history(index);
+        self.current.set(index, 0);
+        self.marks[index] = 0;
+        true
+    }
+
+    /// Reverts the last edit. Returns false if there was nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(edit) => {
+                self.current.set(edit.index, edit.digit);
+                self.marks[edit.index] = edit.marks;
+                self.selected = Some(edit.index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Converts a world position to the index of the cell below it
+    pub fn cell_at(&self, position: Vec2) -> Option<usize> {
+        let local = position - self.origin;
+        let column = self.line_at(local.x)?;
+        // Row 0 is at the top but the world origin is bottom left
+        let row = 8 - self.line_at(local.y)?;
+        Some(row * 9 + column)
+    }
+
+    /// Local translation of the center of a cell on the board
+    pub fn translation(&self, index: usize, z: f32) -> Vec3 {
+        let column = index % 9;
+        let row = 8 - index / 9;
+        Vec3::new(
+            line_offset(column, self.tile_size, self.box_gap) + self.tile_size * 0.5,
+            line_offset(row, self.tile_size, self.box_gap) + self.tile_size * 0.5,
+            z,
+        )
+    }
+
+    fn line_at(&self, offset: f32) -> Option<usize> {
+        if offset < 0. || offset > board_size(self.tile_size, self.box_gap) {
+            return None;
+        }
+        (0..9).find(|line| {
+            let start = line_offset(*line, self.tile_size, self.box_gap);
+            offset >= start && offset < start + self.tile_size
+        })
+    }
+
+    fn editable_selection(&self) -> Option<usize> {
+        self.selected.filter(|index| !self.is_given(*index))
+    }
+
+    fn push_history(&mut self, index: usize) {
+        self.history.push(Edit {
+            index,
+            digit: self.current.get(index),
+            marks: self.marks[index],
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undo_restores_digit_and_marks() {
+        let (puzzle, solution) = Sudoku::generate(Default::default(), &mut rand::thread_rng());
+        let mut game = Game::new(puzzle, solution, 10., 0.);
+        let index = (0..CELL_COUNT)
+            .find(|index| !game.is_given(*index))
+            .unwrap();
+        game.selected = Some(index);
+
+        assert!(game.toggle_mark(3));
+        assert!(game.enter(5));
+        assert_eq!(game.current.get(index), 5);
+
+        assert!(game.undo());
+        assert_eq!(game.current.get(index), 0);
+        assert_eq!(game.marks[index], 1 << 3);
+
+        assert!(game.undo());
+        assert_eq!(game.marks[index], 0);
+        assert!(!game.undo());
+    }
+
+    #[test]
+    fn givens_cant_be_changed() {
+        let (puzzle, solution) = Sudoku::generate(Default::default(), &mut rand::thread_rng());
+        let mut game = Game::new(puzzle, solution, 10., 0.);
+        game.selected = (0..CELL_COUNT).find(|index| game.is_given(*index));
+
+        assert!(!game.enter(1));
+        assert!(!game.clear());
+        assert!(game.history.is_empty());
+    }
+
+    #[test]
+    fn cell_at_matches_translation() {
+        let game = Game::new(Sudoku::default(), Sudoku::default(), 10., 2.);
+        for index in [0, 8, 40, 72, 80] {
+            assert_eq!(game.cell_at(game.translation(index, 0.).xy()), Some(index));
+        }
+    }
+}
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, States)]
+pub enum SudokuState {
+    #[default]
+    NewGame,
+    Running,
+}
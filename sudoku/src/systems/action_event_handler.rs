@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+
+use crate::{
+    events::SudokuActionEvent,
+    resources::{game::Game, plugin_options::PluginOptions},
+    SudokuPlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > SudokuPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Applies the player actions to the game and sends the won event once the puzzle is solved
+    pub(crate) fn action_event_handler(
+        plugin_options: Res<PluginOptions<TyWonEvent>>,
+        mut game: ResMut<Game>,
+        mut action_evr: EventReader<SudokuActionEvent>,
+        mut won_event_writer: EventWriter<TyWonEvent>,
+    ) {
+        for event in action_evr.read() {
+            match *event {
+                SudokuActionEvent::Select(index) => game.selected = Some(index),
+                SudokuActionEvent::MoveSelection(x, y) => game.move_selection((x, y)),
+                SudokuActionEvent::Enter { digit, pencil } => {
+                    if pencil || game.pencil_mode {
+                        game.toggle_mark(digit);
+                    } else {
+                        game.enter(digit);
+                    }
+                }
+                SudokuActionEvent::Clear => {
+                    game.clear();
+                }
+                SudokuActionEvent::TogglePencil => game.pencil_mode = !game.pencil_mode,
+                SudokuActionEvent::Undo => {
+                    game.undo();
+                }
+            }
+        }
+
+        if !game.won && game.is_solved() {
+            game.won = true;
+            won_event_writer.send(plugin_options.won_event.clone());
+        }
+    }
+}
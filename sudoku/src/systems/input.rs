@@ -0,0 +1,84 @@
+use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{events::SudokuActionEvent, resources::game::Game};
+
+/// Turns clicks and key presses into [SudokuActionEvent]s.
+/// Digits enter numbers, holding shift enters pencil marks instead, P toggles pencil mode,
+/// backspace clears a cell, ctrl+Z or U undoes the last edit and the arrow keys move the selection
+pub fn input(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    game: Res<Game>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut keyboard_event_read: EventReader<KeyboardInput>,
+    mut button_event_read: EventReader<MouseButtonInput>,
+    mut action_ewr: EventWriter<SudokuActionEvent>,
+) {
+    if let Ok(window) = windows.get_single() {
+        for event in button_event_read.read() {
+            if event.state != ButtonState::Pressed || event.button != MouseButton::Left {
+                continue;
+            }
+            let cursor = match window.cursor_position() {
+                Some(t) => t,
+                None => continue,
+            };
+            // Window to world space. Window y grows downwards
+            let position = Vec2::new(
+                cursor.x - window.width() / 2.,
+                window.height() / 2. - cursor.y,
+            );
+            if let Some(index) = game.cell_at(position) {
+                bevy::log::debug!("Selecting cell {}", index);
+                action_ewr.send(SudokuActionEvent::Select(index));
+            }
+        }
+    }
+
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let control = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+
+    for event in keyboard_event_read.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let action = match event.key_code {
+            KeyCode::ArrowUp => SudokuActionEvent::MoveSelection(0, -1),
+            KeyCode::ArrowDown => SudokuActionEvent::MoveSelection(0, 1),
+            KeyCode::ArrowLeft => SudokuActionEvent::MoveSelection(-1, 0),
+            KeyCode::ArrowRight => SudokuActionEvent::MoveSelection(1, 0),
+            KeyCode::Backspace | KeyCode::Delete | KeyCode::Digit0 | KeyCode::Numpad0 => {
+                SudokuActionEvent::Clear
+            }
+            KeyCode::KeyP => SudokuActionEvent::TogglePencil,
+            KeyCode::KeyU => SudokuActionEvent::Undo,
+            KeyCode::KeyZ if control => SudokuActionEvent::Undo,
+            key => match digit(key) {
+                Some(digit) => SudokuActionEvent::Enter {
+                    digit,
+                    pencil: shift,
+                },
+                None => continue,
+            },
+        };
+        action_ewr.send(action);
+    }
+}
+
+fn digit(key: KeyCode) -> Option<u8> {
+    match key {
+        KeyCode::Digit1 | KeyCode::Numpad1 => Some(1),
+        KeyCode::Digit2 | KeyCode::Numpad2 => Some(2),
+        KeyCode::Digit3 | KeyCode::Numpad3 => Some(3),
+        KeyCode::Digit4 | KeyCode::Numpad4 => Some(4),
+        KeyCode::Digit5 | KeyCode::Numpad5 => Some(5),
+        KeyCode::Digit6 | KeyCode::Numpad6 => Some(6),
+        KeyCode::Digit7 | KeyCode::Numpad7 => Some(7),
+        KeyCode::Digit8 | KeyCode::Numpad8 => Some(8),
+        KeyCode::Digit9 | KeyCode::Numpad9 => Some(9),
+        _ => None,
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        cell::Cell, digit_text_marker::DigitTextMarker, marks_text_marker::MarksTextMarker,
+    },
+    helpers::marks_string,
+    puzzle::peers,
+    resources::game::Game,
+    CONFLICT_COLOR, ENTERED_COLOR, GIVEN_COLOR, PEER_TINT, SELECTED_TINT,
+};
+
+/// Updates digits, pencil marks, conflict colours and the selection highlight whenever the game changed
+#[allow(clippy::type_complexity)]
+pub fn refresh(
+    game: Res<Game>,
+    mut cells: Query<(&Cell, &mut Sprite, &Children)>,
+    mut digits: Query<&mut Text, (With<DigitTextMarker>, Without<MarksTextMarker>)>,
+    mut marks: Query<&mut Text, (With<MarksTextMarker>, Without<DigitTextMarker>)>,
+) {
+    if !game.is_changed() {
+        return;
+    }
+
+    let highlighted: Vec<usize> = match game.selected {
+        Some(selected) => peers(selected).collect(),
+        None => Vec::new(),
+    };
+
+    for (cell, mut sprite, children) in cells.iter_mut() {
+        let index = **cell;
+        sprite.color = if game.selected == Some(index) {
+            SELECTED_TINT
+        } else if highlighted.contains(&index) {
+            PEER_TINT
+        } else {
+            Color::WHITE
+        };
+
+        let digit = game.current.get(index);
+        for child in children.iter() {
+            if let Ok(mut text) = digits.get_mut(*child) {
+                let color = if game.current.has_conflict(index) {
+                    CONFLICT_COLOR
+                } else if game.is_given(index) {
+                    GIVEN_COLOR
+                } else {
+                    ENTERED_COLOR
+                };
+                for section in text.sections.iter_mut() {
+                    section.value = match digit {
+                        0 => String::new(),
+                        digit => digit.to_string(),
+                    };
+                    section.style.color = color;
+                }
+            }
+            if let Ok(mut text) = marks.get_mut(*child) {
+                for section in text.sections.iter_mut() {
+                    section.value = match digit {
+                        0 => marks_string(game.marks[index]),
+                        _ => String::new(),
+                    };
+                }
+            }
+        }
+    }
+}
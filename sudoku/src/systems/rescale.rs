@@ -0,0 +1,70 @@
+use bevy::{prelude::*, window::PrimaryWindow, window::WindowResized};
+
+use crate::{
+    components::{
+        board_marker::BoardMarker, cell::Cell, digit_text_marker::DigitTextMarker,
+        marks_text_marker::MarksTextMarker,
+    },
+    helpers::board_layout,
+    options::SudokuOptions,
+    resources::game::Game,
+    BACKGROUND_Z,
+};
+
+/// Changes tile size and position if window size changed
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+pub fn rescale(
+    mut board_component: Query<&mut Transform, With<BoardMarker>>,
+    mut cells: Query<(&mut Transform, &mut Sprite, &Cell), Without<BoardMarker>>,
+    mut digits: Query<&mut Text, (With<DigitTextMarker>, Without<MarksTextMarker>)>,
+    mut marks: Query<&mut Text, (With<MarksTextMarker>, Without<DigitTextMarker>)>,
+    mut game: Option<ResMut<Game>>,
+    mut resize_event: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    options: Option<Res<SudokuOptions>>,
+) {
+    if resize_event.read().count() == 0 {
+        return;
+    }
+
+    let game = match &mut game {
+        Some(t) => t,
+        None => return,
+    };
+
+    let window = match windows.get_single() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    let (tile_size, board_position) = board_layout(window, &options);
+    game.tile_size = tile_size;
+    game.origin = board_position.xy();
+
+    for mut transform in board_component.iter_mut() {
+        *transform = Transform::from_translation(board_position);
+    }
+
+    for (mut transform, mut sprite, cell) in cells.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
+        transform.translation = game.translation(**cell, BACKGROUND_Z);
+    }
+
+    for mut text in digits.iter_mut() {
+        for section in text.sections.iter_mut() {
+            section.style.font_size = tile_size * 0.6;
+        }
+    }
+
+    for mut text in marks.iter_mut() {
+        for section in text.sections.iter_mut() {
+            section.style.font_size = tile_size * 0.25;
+        }
+    }
+}
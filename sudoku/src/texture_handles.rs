@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+/// Sudoku shares the uncovered minesweeper tile as its cell background
+const CELL: &str = "./minesweeper/0.png";
+
+#[derive(Debug, Clone, Resource)]
+pub struct TextureHandles {
+    /// Background of every cell. Tinted to show selection and highlights
+    pub cell: Handle<Image>,
+}
+
+/// The cells are drawn while their texture loads, a missing texture only leaves them blank
+pub(crate) fn load_textures(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(TextureHandles {
+        cell: asset_server.load::<Image>(CELL),
+    });
+}
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    components::{
+        board_marker::BoardMarker, cell_marker::CellMarker, coordinates::CoordinateU16,
+        score_marker::ScoreMarker, tile_animation::TileAnimation, tile_marker::TileMarker,
+        tile_text_marker::TileTextMarker,
+    },
+    grid::{Grid, GridTile},
+    helpers::{board_layout, font_size, text_color, tile_color},
+    options::GridOptions,
+    resources::{game::Game, score::Score},
+    states::plugin_state::TwentyFortyEightState,
+    TwentyFortyEightPlugin, BACKGROUND_Z, FOREGROUND_Z, SCORE_MARGIN, TEXT_Z,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > TwentyFortyEightPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn create_board(
+        mut commands: Commands,
+        windows: Query<&Window, With<PrimaryWindow>>,
+        options: Option<Res<GridOptions>>,
+        old_board: Query<Entity, With<BoardMarker>>,
+        mut score: ResMut<Score>,
+        mut next_state: ResMut<NextState<TwentyFortyEightState>>,
+    ) {
+        let window = match windows.get_single() {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+
+        // Despawn old board if it exists
+        if let Ok(t) = old_board.get_single() {
+            commands.entity(t).despawn_recursive();
+        }
+
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+
+        let mut grid = Grid::empty(options.grid_size.0, options.grid_size.1);
+        let mut rng = rand::thread_rng();
+        grid.spawn_random(&mut rng);
+        grid.spawn_random(&mut rng);
+        bevy::log::debug!("\n{}", grid);
+
+        let (tile_size, board_position) =
+            board_layout(window, &options, (*grid.width(), *grid.height()));
+
+        let board = commands
+            .spawn((
+                Name::new("Board"),
+                SpatialBundle {
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                },
+                BoardMarker,
+            ))
+            .id();
+
+        let mut game = Game {
+            grid,
+            tiles: HashMap::new(),
+            tile_size,
+            won: false,
+        };
+
+        commands.entity(board).with_children(|parent| {
+            for y in 0..*game.grid.height() {
+                for x in 0..*game.grid.width() {
+                    let coordinate = CoordinateU16 { x, y };
+                    parent.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: Color::rgb_u8(205, 193, 180),
+                                custom_size: Some(Vec2::splat(tile_size - options.tile_padding)),
+                                ..Default::default()
+                            },
+                            transform: Transform::from_translation(
+                                game.translation(coordinate.as_vec2(), BACKGROUND_Z),
+                            ),
+                            ..Default::default()
+                        },
+                        Name::new(format!("Cell: {}", coordinate)),
+                        coordinate,
+                        CellMarker,
+                    ));
+                }
+            }
+
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        score_string(&score),
+                        TextStyle {
+                            font_size: SCORE_MARGIN * 0.5,
+                            color: Color::WHITE,
+                            ..Default::default()
+                        },
+                    )
+                    .with_justify(JustifyText::Center),
+                    transform: Transform::from_translation(score_translation(&game)),
+                    ..Default::default()
+                },
+                Name::new("Score"),
+                ScoreMarker,
+            ));
+        });
+
+        let tiles: Vec<(CoordinateU16, GridTile)> = game.grid.iter().collect();
+        for (coordinate, tile) in tiles {
+            let animation =
+                TileAnimation::pop(coordinate.as_vec2(), 0., options.animation_duration);
+            let entity = spawn_tile(
+                &mut commands,
+                board,
+                &game,
+                &options,
+                coordinate,
+                tile,
+                animation,
+            );
+            game.tiles.insert(tile.id, entity);
+        }
+
+        score.reset();
+        commands.insert_resource(game);
+        next_state.set(TwentyFortyEightState::Running);
+    }
+
+    pub fn reset_event_handler(
+        mut state: ResMut<NextState<TwentyFortyEightState>>,
+        mut events: EventReader<TyResetEvent>,
+    ) {
+        if events.read().count() == 0 {
+            return;
+        }
+        state.set(TwentyFortyEightState::NewGame);
+    }
+}
+
+/// Spawns a tile as a child of the board and returns its entity
+pub(crate) fn spawn_tile(
+    commands: &mut Commands,
+    board: Entity,
+    game: &Game,
+    options: &GridOptions,
+    coordinate: CoordinateU16,
+    tile: GridTile,
+    animation: TileAnimation,
+) -> Entity {
+    let mut transform =
+        Transform::from_translation(game.translation(animation.position(), FOREGROUND_Z));
+    transform.scale = Vec3::splat(animation.scale());
+
+    let entity = commands
+        .spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: tile_color(tile.value),
+                    custom_size: Some(Vec2::splat(game.tile_size - options.tile_padding)),
+                    ..Default::default()
+                },
+                transform,
+                ..Default::default()
+            },
+            Name::new(format!("Tile: {}", tile.id)),
+            coordinate,
+            TileMarker { value: tile.value },
+            animation,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        tile.value.to_string(),
+                        TextStyle {
+                            font_size: font_size(tile.value, game.tile_size),
+                            color: text_color(tile.value),
+                            ..Default::default()
+                        },
+                    )
+                    .with_justify(JustifyText::Center),
+                    transform: Transform::from_xyz(0., 0., TEXT_Z - FOREGROUND_Z),
+                    ..Default::default()
+                },
+                TileTextMarker,
+            ));
+        })
+        .id();
+    commands.entity(board).add_child(entity);
+    entity
+}
+
+pub(crate) fn score_string(score: &Score) -> String {
+    format!("Score: {}    Best: {}", score.current, score.best)
+}
+
+/// Local translation of the score text, centered above the grid
+pub(crate) fn score_translation(game: &Game) -> Vec3 {
+    Vec3::new(
+        *game.grid.width() as f32 * game.tile_size / 2.,
+        *game.grid.height() as f32 * game.tile_size + SCORE_MARGIN / 2.,
+        TEXT_Z,
+    )
+}
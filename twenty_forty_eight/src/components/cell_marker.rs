@@ -0,0 +1,5 @@
+use bevy::{ecs::query::QueryFilter, prelude::*};
+
+/// Marks the empty background cells behind the tiles
+#[derive(Debug, Clone, Copy, Component, QueryFilter)]
+pub struct CellMarker;
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component)]
+pub struct CoordinateU16 {
+    pub x: u16,
+    pub y: u16,
+}
+
+impl CoordinateU16 {
+    pub fn as_vec2(&self) -> Vec2 {
+        Vec2::new(self.x as f32, self.y as f32)
+    }
+}
+
+impl std::fmt::Display for CoordinateU16 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({};{})", self.x, self.y)
+    }
+}
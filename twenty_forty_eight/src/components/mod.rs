@@ -0,0 +1,7 @@
+pub mod board_marker;
+pub mod cell_marker;
+pub mod coordinates;
+pub mod score_marker;
+pub mod tile_animation;
+pub mod tile_marker;
+pub mod tile_text_marker;
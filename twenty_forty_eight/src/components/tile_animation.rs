@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+/// Moves and scales a tile over time. Positions are in grid space so the animation survives a rescale
+#[derive(Debug, Clone, Component)]
+pub struct TileAnimation {
+    pub from: Vec2,
+    pub to: Vec2,
+    pub from_scale: f32,
+    pub to_scale: f32,
+    /// Seconds to wait before the animation starts
+    pub delay: f32,
+    pub duration: f32,
+    pub elapsed: f32,
+    /// Despawn the tile once the animation is finished. Used for tiles that got merged into another one
+    pub despawn: bool,
+}
+
+impl TileAnimation {
+    pub fn slide(from: Vec2, to: Vec2, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            from_scale: 1.,
+            to_scale: 1.,
+            delay: 0.,
+            duration,
+            elapsed: 0.,
+            despawn: false,
+        }
+    }
+
+    /// Grows a tile from nothing after the slide animation finished
+    pub fn pop(at: Vec2, delay: f32, duration: f32) -> Self {
+        Self {
+            from: at,
+            to: at,
+            from_scale: 0.,
+            to_scale: 1.,
+            delay,
+            duration,
+            elapsed: 0.,
+            despawn: false,
+        }
+    }
+
+    /// Progress between 0 and 1
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0. {
+            return 1.;
+        }
+        ((self.elapsed - self.delay) / self.duration).clamp(0., 1.)
+    }
+
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.delay + self.duration
+    }
+
+    pub fn position(&self) -> Vec2 {
+        self.from.lerp(self.to, ease_out(self.progress()))
+    }
+
+    pub fn scale(&self) -> f32 {
+        self.from_scale + (self.to_scale - self.from_scale) * ease_out(self.progress())
+    }
+}
+
+fn ease_out(t: f32) -> f32 {
+    1. - (1. - t) * (1. - t)
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+/// Marks a tile entity. Contains the value of the [crate::grid::GridTile] it displays
+#[derive(Debug, Clone, Copy, Component)]
+pub struct TileMarker {
+    pub value: u32,
+}
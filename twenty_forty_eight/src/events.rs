@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::grid::Direction;
+
+/// Event that is sent when the player swipes or presses an arrow key. Contains the direction the tiles should slide in
+#[derive(Debug, Clone, Copy, Event)]
+pub(crate) struct SlideEvent(pub Direction);
+
+impl std::ops::Deref for SlideEvent {
+    type Target = Direction;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
@@ -0,0 +1,353 @@
+use rand::Rng;
+
+use crate::components::coordinates::CoordinateU16;
+
+/// The direction the tiles are slid in. `Up` moves the tiles towards row 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// A numbered tile. The id stays the same while the tile moves around and is used to find the matching entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridTile {
+    pub id: u32,
+    pub value: u32,
+}
+
+/// A tile that changed its position during a slide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileMove {
+    pub id: u32,
+    pub from: CoordinateU16,
+    pub to: CoordinateU16,
+}
+
+/// Two tiles that were merged into a new one during a slide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileMerge {
+    pub consumed: [u32; 2],
+    pub result: GridTile,
+    pub at: CoordinateU16,
+}
+
+/// Everything that happened during a single slide
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlideOutcome {
+    pub moves: Vec<TileMove>,
+    pub merges: Vec<TileMerge>,
+    /// Sum of all newly merged tile values
+    pub score: u32,
+}
+
+impl SlideOutcome {
+    /// A slide that didn't move anything isn't a valid turn
+    pub fn changed(&self) -> bool {
+        !self.moves.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    width: u16,
+    height: u16,
+    cells: Vec<Option<GridTile>>,
+    next_id: u32,
+}
+
+impl Grid {
+    pub fn empty(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![None; width as usize * height as usize],
+            next_id: 0,
+        }
+    }
+
+    /// Builds a grid from rows of values where 0 is an empty cell. Row 0 is the top row
+    pub fn from_rows(rows: &[&[u32]]) -> Self {
+        let height = rows.len() as u16;
+        let width = rows.first().map(|row| row.len()).unwrap_or(0) as u16;
+        let mut grid = Self::empty(width, height);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                if *value == 0 {
+                    continue;
+                }
+                let tile = grid.new_tile(*value);
+                grid.set(
+                    CoordinateU16 {
+                        x: x as u16,
+                        y: y as u16,
+                    },
+                    Some(tile),
+                );
+            }
+        }
+        grid
+    }
+
+    /// Places a 2 (or with a 10% chance a 4) on a random empty cell
+    pub fn spawn_random<R: Rng>(&mut self, rng: &mut R) -> Option<(CoordinateU16, GridTile)> {
+        let empty: Vec<usize> = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_none())
+            .map(|(index, _)| index)
+            .collect();
+        if empty.is_empty() {
+            return None;
+        }
+
+        let index = empty[rng.gen_range(0..empty.len())];
+        let value = if rng.gen_bool(0.9) { 2 } else { 4 };
+        let tile = self.new_tile(value);
+        self.cells[index] = Some(tile);
+        Some((self.coordinate_of(index), tile))
+    }
+
+    /// Slides and merges all tiles. Every tile can only be part of one merge per slide
+    pub fn slide(&mut self, direction: Direction) -> SlideOutcome {
+        let mut outcome = SlideOutcome::default();
+
+        for line in 0..self.line_count(direction) {
+            let coordinates = self.line(direction, line);
+            let mut write = 0;
+            let mut mergeable = false;
+
+            for read in 0..coordinates.len() {
+                let tile = match self.take(coordinates[read]) {
+                    Some(t) => t,
+                    None => continue,
+                };
+
+                if mergeable {
+                    let target = coordinates[write - 1];
+                    // Unwrap is fine because `mergeable` is only set after writing a tile
+                    let previous = self[target].unwrap();
+                    if previous.value == tile.value {
+                        let result = self.new_tile(tile.value * 2);
+                        self.set(target, Some(result));
+                        outcome.moves.push(TileMove {
+                            id: tile.id,
+                            from: coordinates[read],
+                            to: target,
+                        });
+                        outcome.merges.push(TileMerge {
+                            consumed: [previous.id, tile.id],
+                            result,
+                            at: target,
+                        });
+                        outcome.score += result.value;
+                        mergeable = false;
+                        continue;
+                    }
+                }
+
+                self.set(coordinates[write], Some(tile));
+                if write != read {
+                    outcome.moves.push(TileMove {
+                        id: tile.id,
+                        from: coordinates[read],
+                        to: coordinates[write],
+                    });
+                }
+                write += 1;
+                mergeable = true;
+            }
+        }
+
+        outcome
+    }
+
+    /// Returns true if there is an empty cell or two equal neighbouring tiles
+    pub fn has_moves(&self) -> bool {
+        if self.cells.iter().any(|cell| cell.is_none()) {
+            return true;
+        }
+        (0..self.height).any(|y| {
+            (0..self.width).any(|x| {
+                let value = self[(x, y)].map(|tile| tile.value);
+                (x + 1 < self.width && self[(x + 1, y)].map(|tile| tile.value) == value)
+                    || (y + 1 < self.height && self[(x, y + 1)].map(|tile| tile.value) == value)
+            })
+        })
+    }
+
+    pub fn max_value(&self) -> u32 {
+        self.cells
+            .iter()
+            .flatten()
+            .map(|tile| tile.value)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (CoordinateU16, GridTile)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell)| cell.map(|tile| (self.coordinate_of(index), tile)))
+    }
+
+    pub fn width(&self) -> &u16 {
+        &self.width
+    }
+
+    pub fn height(&self) -> &u16 {
+        &self.height
+    }
+
+    fn new_tile(&mut self, value: u32) -> GridTile {
+        self.next_id += 1;
+        GridTile {
+            id: self.next_id,
+            value,
+        }
+    }
+
+    fn coordinate_of(&self, index: usize) -> CoordinateU16 {
+        CoordinateU16 {
+            x: (index % self.width as usize) as u16,
+            y: (index / self.width as usize) as u16,
+        }
+    }
+
+    fn index_of(&self, coordinate: CoordinateU16) -> usize {
+        coordinate.x as usize + coordinate.y as usize * self.width as usize
+    }
+
+    fn set(&mut self, coordinate: CoordinateU16, cell: Option<GridTile>) {
+        let index = self.index_of(coordinate);
+        self.cells[index] = cell;
+    }
+
+    fn take(&mut self, coordinate: CoordinateU16) -> Option<GridTile> {
+        let index = self.index_of(coordinate);
+        self.cells[index].take()
+    }
+
+    fn line_count(&self, direction: Direction) -> u16 {
+        match direction {
+            Direction::Up | Direction::Down => self.width,
+            Direction::Left | Direction::Right => self.height,
+        }
+    }
+
+    /// All coordinates of a row or column, ordered from the edge the tiles are slid towards
+    fn line(&self, direction: Direction, line: u16) -> Vec<CoordinateU16> {
+        match direction {
+            Direction::Up => (0..self.height)
+                .map(|y| CoordinateU16 { x: line, y })
+                .collect(),
+            Direction::Down => (0..self.height)
+                .rev()
+                .map(|y| CoordinateU16 { x: line, y })
+                .collect(),
+            Direction::Left => (0..self.width)
+                .map(|x| CoordinateU16 { x, y: line })
+                .collect(),
+            Direction::Right => (0..self.width)
+                .rev()
+                .map(|x| CoordinateU16 { x, y: line })
+                .collect(),
+        }
+    }
+}
+
+impl std::ops::Index<(u16, u16)> for Grid {
+    type Output = Option<GridTile>;
+
+    fn index(&self, (x, y): (u16, u16)) -> &Self::Output {
+        &self.cells[x as usize + y as usize * self.width as usize]
+    }
+}
+
+impl std::ops::Index<CoordinateU16> for Grid {
+    type Output = Option<GridTile>;
+
+    fn index(&self, coordinate: CoordinateU16) -> &Self::Output {
+        &self.cells[self.index_of(coordinate)]
+    }
+}
+
+impl std::fmt::Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            let row = (0..self.width)
+                .map(|x| match self[(x, y)] {
+                    Some(tile) => format!("{:>5}", tile.value),
+                    None => format!("{:>5}", "."),
+                })
+                .collect::<String>();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(grid: &Grid) -> Vec<Vec<u32>> {
+        (0..*grid.height())
+            .map(|y| {
+                (0..*grid.width())
+                    .map(|x| grid[(x, y)].map(|tile| tile.value).unwrap_or(0))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn slide_left_merges_pairs_once() {
+        let mut grid =
+            Grid::from_rows(&[&[2, 2, 2, 2], &[4, 0, 4, 8], &[0, 0, 0, 2], &[2, 4, 8, 16]]);
+        let outcome = grid.slide(Direction::Left);
+
+        assert_eq!(
+            values(&grid),
+            vec![
+                vec![4, 4, 0, 0],
+                vec![8, 8, 0, 0],
+                vec![2, 0, 0, 0],
+                vec![2, 4, 8, 16],
+            ]
+        );
+        assert_eq!(outcome.merges.len(), 3);
+        assert_eq!(outcome.score, 4 + 4 + 8);
+    }
+
+    #[test]
+    fn slide_down_moves_towards_last_row() {
+        let mut grid = Grid::from_rows(&[&[2, 0], &[0, 0], &[2, 4]]);
+        grid.slide(Direction::Down);
+
+        assert_eq!(values(&grid), vec![vec![0, 0], vec![0, 0], vec![4, 4]]);
+    }
+
+    #[test]
+    fn blocked_slide_does_not_change() {
+        let mut grid = Grid::from_rows(&[&[2, 4], &[8, 16]]);
+        let outcome = grid.slide(Direction::Left);
+
+        assert!(!outcome.changed());
+        assert!(!grid.has_moves());
+    }
+
+    #[test]
+    fn spawn_fills_empty_cell() {
+        let mut grid = Grid::from_rows(&[&[2, 4], &[8, 0]]);
+        let (coordinate, tile) = grid.spawn_random(&mut rand::thread_rng()).unwrap();
+
+        assert_eq!(coordinate, CoordinateU16 { x: 1, y: 1 });
+        assert!(tile.value == 2 || tile.value == 4);
+        assert!(grid.spawn_random(&mut rand::thread_rng()).is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use bevy::prelude::{Color, Vec2, Vec3, Window};
+
+use crate::{
+    options::{GridOptions, TileSize},
+    SCORE_MARGIN,
+};
+
+/// Fits the grid into the window while leaving `margin` pixels free for the score above the grid
+pub(crate) fn adaptive_tile_size(
+    window: &Window,
+    (min, max): (f32, f32),
+    (width, height): (u16, u16),
+    margin: f32,
+) -> f32 {
+    let max_width = window.width() / width as f32;
+    let max_height = (window.height() - margin) / height as f32;
+    max_width.min(max_height).clamp(min, max)
+}
+
+/// Background colour of a tile with the given value
+pub(crate) fn tile_color(value: u32) -> Color {
+    match value {
+        2 => Color::rgb_u8(238, 228, 218),
+        4 => Color::rgb_u8(237, 224, 200),
+        8 => Color::rgb_u8(242, 177, 121),
+        16 => Color::rgb_u8(245, 149, 99),
+        32 => Color::rgb_u8(246, 124, 95),
+        64 => Color::rgb_u8(246, 94, 59),
+        128 => Color::rgb_u8(237, 207, 114),
+        256 => Color::rgb_u8(237, 204, 97),
+        512 => Color::rgb_u8(237, 200, 80),
+        1024 => Color::rgb_u8(237, 197, 63),
+        2048 => Color::rgb_u8(237, 194, 46),
+        _ => Color::rgb_u8(60, 58, 50),
+    }
+}
+
+/// Text colour of a tile with the given value
+pub(crate) fn text_color(value: u32) -> Color {
+    match value {
+        2 | 4 => Color::rgb_u8(119, 110, 101),
+        _ => Color::rgb_u8(249, 246, 242),
+    }
+}
+
+/// Shrinks the font for values with many digits so they still fit on the tile
+pub(crate) fn font_size(value: u32, tile_size: f32) -> f32 {
+    let digits = value.to_string().len() as f32;
+    tile_size * 0.5 * (2. / digits.max(2.))
+}
+
+/// Computes the tile size and the position of the bottom left corner of the grid
+pub(crate) fn board_layout(
+    window: &Window,
+    options: &GridOptions,
+    (width, height): (u16, u16),
+) -> (f32, Vec3) {
+    let tile_size = match options.tile_size {
+        TileSize::Fixed(v) => v,
+        TileSize::Adaptive { min, max } => {
+            adaptive_tile_size(window, (min, max), (width, height), SCORE_MARGIN)
+        }
+    };
+    let board_size = Vec2::new(width as f32 * tile_size, height as f32 * tile_size);
+    // Shift the grid down so the score fits above it
+    let position = Vec3::new(
+        -(board_size.x / 2.),
+        -(board_size.y / 2.) - SCORE_MARGIN / 2.,
+        0.,
+    );
+    (tile_size, position)
+}
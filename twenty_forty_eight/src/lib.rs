@@ -0,0 +1,105 @@
+pub(crate) mod board;
+pub(crate) mod components;
+pub(crate) mod events;
+pub mod grid;
+pub(crate) mod helpers;
+pub mod options;
+pub(crate) mod resources;
+pub(crate) mod states;
+pub(crate) mod systems;
+
+use bevy::prelude::*;
+
+use crate::{
+    events::SlideEvent,
+    resources::{plugin_options::PluginOptions, score::Score},
+    states::plugin_state::TwentyFortyEightState,
+};
+
+pub(crate) const BACKGROUND_Z: f32 = 0.;
+pub(crate) const FOREGROUND_Z: f32 = 1.;
+pub(crate) const TEXT_Z: f32 = 2.;
+/// Space above the grid reserved for the score
+pub(crate) const SCORE_MARGIN: f32 = 60.;
+
+pub struct TwentyFortyEightPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+where
+    TyRunState: States + PartialEq,
+    TyPauseState: States + PartialEq,
+    TyResetEvent: Event + Clone,
+    TyWonEvent: Event + Clone,
+    TyLostEvent: Event + Clone,
+{
+    pub run_state: TyRunState,
+    pub pause_state: TyPauseState,
+    pub reset_event: TyResetEvent,
+    pub won_event: TyWonEvent,
+    pub lost_event: TyLostEvent,
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > Plugin
+    for TwentyFortyEightPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Events need to be already added to the engine!
+    fn build(&self, app: &mut App) {
+        app.init_state::<TwentyFortyEightState>()
+            .add_event::<SlideEvent>()
+            .init_resource::<Score>()
+            .insert_resource(PluginOptions {
+                won_event: self.won_event.clone(),
+                lost_event: self.lost_event.clone(),
+            })
+            .add_systems(
+                Update,
+                (
+                    Self::create_board
+                        .run_if(in_state(TwentyFortyEightState::NewGame))
+                        .run_if(in_state(self.run_state.clone())),
+                    crate::systems::rescale::rescale,
+                    crate::systems::input::input.run_if(
+                        in_state(TwentyFortyEightState::Running)
+                            .and_then(in_state(self.run_state.clone()))
+                            .and_then(not(in_state(self.pause_state.clone()))),
+                    ),
+                    Self::slide_event_handler.run_if(in_state(TwentyFortyEightState::Running)),
+                    crate::systems::animate::animate
+                        .run_if(in_state(TwentyFortyEightState::Running)),
+                    crate::systems::score_text::score_text
+                        .run_if(in_state(TwentyFortyEightState::Running)),
+                    Self::reset_event_handler,
+                ),
+            );
+        bevy::log::info!("Plugin loaded.");
+    }
+}
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > TwentyFortyEightPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    pub fn new(
+        run_state: TyRunState,
+        pause_state: TyPauseState,
+        reset_event: TyResetEvent,
+        won_event: TyWonEvent,
+        lost_event: TyLostEvent,
+    ) -> Self {
+        Self {
+            run_state,
+            pause_state,
+            reset_event,
+            won_event,
+            lost_event,
+        }
+    }
+}
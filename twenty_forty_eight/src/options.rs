@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+
+/// Determines wether the tiles should be fixed (don't change with window scaling) or adaptive (change with window resizing but only above a minimum and below a maximum)
+#[derive(Debug, Clone, Resource)]
+pub enum TileSize {
+    Fixed(f32),
+    Adaptive { min: f32, max: f32 },
+}
+
+/// Vairous options relating to the grid
+#[derive(Debug, Clone, Resource)]
+pub struct GridOptions {
+    /// Grid size
+    pub grid_size: (u16, u16),
+    /// Tile value that wins the game
+    pub win_value: u32,
+    /// Tile world size
+    pub tile_size: TileSize,
+    /// Padding between tiles
+    pub tile_padding: f32,
+    /// Length of the slide and merge animations in seconds. 0 disables them
+    pub animation_duration: f32,
+    /// Minimum distance in pixels a mouse drag or touch has to travel to count as a swipe
+    pub swipe_threshold: f32,
+}
+
+impl Default for TileSize {
+    fn default() -> Self {
+        Self::Adaptive {
+            min: 20.0,
+            max: 150.0,
+        }
+    }
+}
+
+impl Default for GridOptions {
+    fn default() -> Self {
+        Self {
+            grid_size: (4, 4),
+            win_value: 2048,
+            tile_size: Default::default(),
+            tile_padding: 8.,
+            animation_duration: 0.1,
+            swipe_threshold: 30.,
+        }
+    }
+}
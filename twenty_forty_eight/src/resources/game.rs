@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Resource)]
+pub struct Game {
+    pub grid: Grid,
+    /// Maps the id of a [crate::grid::GridTile] to the entity displaying it
+    pub tiles: HashMap<u32, Entity>,
+    pub tile_size: f32,
+    /// The won event is only sent once per game. Playing on afterwards is allowed
+    pub won: bool,
+}
+
+impl Game {
+    /// Converts a position in grid space to the local translation of a tile on the board. Row 0 is the top row
+    pub fn translation(&self, position: Vec2, z: f32) -> Vec3 {
+        Vec3::new(
+            position.x * self.tile_size + self.tile_size * 0.5,
+            (*self.grid.height() as f32 - position.y - 1.) * self.tile_size + self.tile_size * 0.5,
+            z,
+        )
+    }
+}
@@ -0,0 +1,3 @@
+pub mod game;
+pub mod plugin_options;
+pub mod score;
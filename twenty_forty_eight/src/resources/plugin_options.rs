@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// Can't pass the plugin as &self so I need to make a resource which holds the data the systems need
+#[derive(Debug, Clone, Resource)]
+pub struct PluginOptions<TyWonEvent, TyLostEvent>
+where
+    TyWonEvent: Event + Clone,
+    TyLostEvent: Event + Clone,
+{
+    pub won_event: TyWonEvent,
+    pub lost_event: TyLostEvent,
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+/// Score of the current game and the best score since the app was started
+#[derive(Debug, Clone, Copy, Default, Resource)]
+pub struct Score {
+    pub current: u32,
+    pub best: u32,
+}
+
+impl Score {
+    pub fn add(&mut self, points: u32) {
+        self.current += points;
+        self.best = self.best.max(self.current);
+    }
+
+    pub fn reset(&mut self) {
+        self.current = 0;
+    }
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+
+use crate::{components::tile_animation::TileAnimation, resources::game::Game, FOREGROUND_Z};
+
+/// Advances all [TileAnimation]s and removes them (or the whole tile) once they are finished
+pub fn animate(
+    mut commands: Commands,
+    time: Res<Time>,
+    game: Res<Game>,
+    mut tiles: Query<(Entity, &mut Transform, &mut TileAnimation)>,
+) {
+    for (entity, mut transform, mut animation) in tiles.iter_mut() {
+        animation.elapsed += time.delta_seconds();
+        transform.translation = game.translation(animation.position(), FOREGROUND_Z);
+        transform.scale = Vec3::splat(animation.scale());
+
+        if !animation.finished() {
+            continue;
+        }
+        match animation.despawn {
+            true => commands.entity(entity).despawn_recursive(),
+            false => {
+                commands.entity(entity).remove::<TileAnimation>();
+            }
+        }
+    }
+}
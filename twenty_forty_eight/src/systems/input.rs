@@ -0,0 +1,83 @@
+use bevy::{
+    input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+use crate::{events::SlideEvent, grid::Direction, options::GridOptions};
+
+/// Turns arrow keys, WASD, mouse drags and touch swipes into [SlideEvent]s
+#[allow(clippy::too_many_arguments)]
+pub fn input(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    options: Option<Res<GridOptions>>,
+    touches: Res<Touches>,
+    mut keyboard_event_read: EventReader<KeyboardInput>,
+    mut button_event_read: EventReader<MouseButtonInput>,
+    mut drag_start: Local<Option<Vec2>>,
+    mut slide_ewr: EventWriter<SlideEvent>,
+) {
+    let threshold = match options {
+        Some(t) => t.swipe_threshold,
+        None => GridOptions::default().swipe_threshold,
+    };
+
+    for event in keyboard_event_read.read() {
+        if let ButtonState::Pressed = event.state {
+            let direction = match event.key_code {
+                KeyCode::ArrowUp | KeyCode::KeyW => Direction::Up,
+                KeyCode::ArrowDown | KeyCode::KeyS => Direction::Down,
+                KeyCode::ArrowLeft | KeyCode::KeyA => Direction::Left,
+                KeyCode::ArrowRight | KeyCode::KeyD => Direction::Right,
+                _ => continue,
+            };
+            bevy::log::debug!("Sliding {:?}", direction);
+            slide_ewr.send(SlideEvent(direction));
+        }
+    }
+
+    if let Ok(window) = windows.get_single() {
+        for event in button_event_read.read() {
+            if event.button != MouseButton::Left {
+                continue;
+            }
+            match event.state {
+                ButtonState::Pressed => *drag_start = window.cursor_position(),
+                ButtonState::Released => {
+                    if let (Some(start), Some(end)) = (drag_start.take(), window.cursor_position())
+                    {
+                        if let Some(direction) = swipe_direction(end - start, threshold) {
+                            slide_ewr.send(SlideEvent(direction));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for touch in touches.iter_just_released() {
+        if let Some(direction) =
+            swipe_direction(touch.position() - touch.start_position(), threshold)
+        {
+            slide_ewr.send(SlideEvent(direction));
+        }
+    }
+}
+
+/// Window coordinates grow downwards, so a positive y delta is a swipe down
+fn swipe_direction(delta: Vec2, threshold: f32) -> Option<Direction> {
+    if delta.length() < threshold {
+        return None;
+    }
+    if delta.x.abs() > delta.y.abs() {
+        match delta.x > 0. {
+            true => Some(Direction::Right),
+            false => Some(Direction::Left),
+        }
+    } else {
+        match delta.y > 0. {
+            true => Some(Direction::Down),
+            false => Some(Direction::Up),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod animate;
+pub mod input;
+pub mod rescale;
+pub mod score_text;
+pub mod slide_event_handler;
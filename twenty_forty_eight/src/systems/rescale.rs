@@ -0,0 +1,103 @@
+use bevy::{prelude::*, window::PrimaryWindow, window::WindowResized};
+
+use crate::{
+    board::score_translation,
+    components::{
+        board_marker::BoardMarker, cell_marker::CellMarker, coordinates::CoordinateU16,
+        score_marker::ScoreMarker, tile_animation::TileAnimation, tile_marker::TileMarker,
+        tile_text_marker::TileTextMarker,
+    },
+    helpers::{board_layout, font_size},
+    options::GridOptions,
+    resources::game::Game,
+    BACKGROUND_Z, FOREGROUND_Z,
+};
+
+/// Changes tile size and position if window size changed
+#[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
+pub fn rescale(
+    mut board_component: Query<&mut Transform, With<BoardMarker>>,
+    mut cells: Query<
+        (&mut Transform, &mut Sprite, &CoordinateU16),
+        (With<CellMarker>, Without<BoardMarker>),
+    >,
+    mut tiles: Query<
+        (
+            &mut Transform,
+            &mut Sprite,
+            &CoordinateU16,
+            Option<&TileAnimation>,
+        ),
+        (With<TileMarker>, Without<BoardMarker>, Without<CellMarker>),
+    >,
+    mut texts: Query<(&mut Text, &Parent), With<TileTextMarker>>,
+    values: Query<&TileMarker>,
+    mut score_text: Query<
+        &mut Transform,
+        (
+            With<ScoreMarker>,
+            Without<BoardMarker>,
+            Without<CellMarker>,
+            Without<TileMarker>,
+        ),
+    >,
+    mut game: Option<ResMut<Game>>,
+    mut resize_event: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    options: Option<Res<GridOptions>>,
+) {
+    if resize_event.read().count() == 0 {
+        return;
+    }
+
+    let game = match &mut game {
+        Some(t) => t,
+        None => return,
+    };
+
+    let window = match windows.get_single() {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    let options = match options {
+        Some(t) => t.clone(),
+        None => Default::default(),
+    };
+
+    let (tile_size, board_position) =
+        board_layout(window, &options, (*game.grid.width(), *game.grid.height()));
+    game.tile_size = tile_size;
+
+    for mut transform in board_component.iter_mut() {
+        *transform = Transform::from_translation(board_position);
+    }
+
+    for (mut transform, mut sprite, coordinate) in cells.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
+        transform.translation = game.translation(coordinate.as_vec2(), BACKGROUND_Z);
+    }
+
+    for (mut transform, mut sprite, coordinate, animation) in tiles.iter_mut() {
+        sprite.custom_size = Some(Vec2::splat(tile_size - options.tile_padding));
+        // Animated tiles get positioned by the animation system
+        if animation.is_none() {
+            transform.translation = game.translation(coordinate.as_vec2(), FOREGROUND_Z);
+        }
+    }
+
+    for (mut text, parent) in texts.iter_mut() {
+        let value = match values.get(parent.get()) {
+            Ok(t) => t.value,
+            Err(_) => continue,
+        };
+        for section in text.sections.iter_mut() {
+            section.style.font_size = font_size(value, tile_size);
+        }
+    }
+
+    for mut transform in score_text.iter_mut() {
+        transform.translation = score_translation(game);
+    }
+}
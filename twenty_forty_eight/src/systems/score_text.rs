@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::{board::score_string, components::score_marker::ScoreMarker, resources::score::Score};
+
+/// Updates the score text when the score changed
+pub fn score_text(score: Res<Score>, mut texts: Query<&mut Text, With<ScoreMarker>>) {
+    if !score.is_changed() {
+        return;
+    }
+    for mut text in texts.iter_mut() {
+        if let Some(section) = text.sections.first_mut() {
+            section.value = score_string(&score);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::{
+    board::spawn_tile,
+    components::{board_marker::BoardMarker, tile_animation::TileAnimation},
+    events::SlideEvent,
+    options::GridOptions,
+    resources::{game::Game, plugin_options::PluginOptions, score::Score},
+    TwentyFortyEightPlugin,
+};
+
+impl<
+        TyRunState: States + PartialEq,
+        TyPauseState: States + PartialEq,
+        TyResetEvent: Event + Clone,
+        TyWonEvent: Event + Clone,
+        TyLostEvent: Event + Clone,
+    > TwentyFortyEightPlugin<TyRunState, TyPauseState, TyResetEvent, TyWonEvent, TyLostEvent>
+{
+    /// Slides the grid, animates the moved and merged tiles, spawns a new tile and sends the won or lost event
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn slide_event_handler(
+        plugin_options: Res<PluginOptions<TyWonEvent, TyLostEvent>>,
+        options: Option<Res<GridOptions>>,
+        mut commands: Commands,
+        mut game: ResMut<Game>,
+        mut score: ResMut<Score>,
+        board: Query<Entity, With<BoardMarker>>,
+        mut slide_evr: EventReader<SlideEvent>,
+        mut won_event_writer: EventWriter<TyWonEvent>,
+        mut lost_event_writer: EventWriter<TyLostEvent>,
+    ) {
+        let board = match board.get_single() {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+
+        let options = match options {
+            Some(t) => t.clone(),
+            None => Default::default(),
+        };
+        let duration = options.animation_duration;
+
+        for event in slide_evr.read() {
+            let outcome = game.grid.slide(**event);
+            if !outcome.changed() {
+                continue;
+            }
+
+            let moves: HashMap<u32, TileAnimation> = outcome
+                .moves
+                .iter()
+                .map(|m| {
+                    (
+                        m.id,
+                        TileAnimation::slide(m.from.as_vec2(), m.to.as_vec2(), duration),
+                    )
+                })
+                .collect();
+
+            for tile_move in outcome.moves.iter() {
+                if let Some(entity) = game.tiles.get(&tile_move.id) {
+                    commands
+                        .entity(*entity)
+                        .insert((tile_move.to, moves[&tile_move.id].clone()));
+                }
+            }
+
+            for merge in outcome.merges.iter() {
+                // Both merged tiles slide to the target and disappear under the new tile
+                for id in merge.consumed {
+                    let entity = match game.tiles.remove(&id) {
+                        Some(t) => t,
+                        None => continue,
+                    };
+                    let mut animation = moves.get(&id).cloned().unwrap_or_else(|| {
+                        TileAnimation::slide(merge.at.as_vec2(), merge.at.as_vec2(), duration)
+                    });
+                    animation.despawn = true;
+                    commands.entity(entity).insert(animation);
+                }
+
+                let animation = TileAnimation::pop(merge.at.as_vec2(), duration, duration);
+                let entity = spawn_tile(
+                    &mut commands,
+                    board,
+                    &game,
+                    &options,
+                    merge.at,
+                    merge.result,
+                    animation,
+                );
+                game.tiles.insert(merge.result.id, entity);
+            }
+
+            score.add(outcome.score);
+
+            if let Some((coordinate, tile)) = game.grid.spawn_random(&mut rand::thread_rng()) {
+                let animation = TileAnimation::pop(coordinate.as_vec2(), duration, duration);
+                let entity = spawn_tile(
+                    &mut commands,
+                    board,
+                    &game,
+                    &options,
+                    coordinate,
+                    tile,
+                    animation,
+                );
+                game.tiles.insert(tile.id, entity);
+            }
+            bevy::log::debug!("\n{}", game.grid);
+
+            if !game.won && game.grid.max_value() >= options.win_value {
+                game.won = true;
+                won_event_writer.send(plugin_options.won_event.clone());
+            }
+
+            if !game.grid.has_moves() {
+                lost_event_writer.send(plugin_options.lost_event.clone());
+                return;
+            }
+        }
+    }
+}